@@ -0,0 +1,262 @@
+//! Frame and per-LED cost of every shader and of a few representative presets, over the manual
+//! layout and both bundled MadMapper projects.
+//!
+//! `shaders/<layout>` renders each shader alone in the left slot, with a solid colour in the right
+//! and colourise slots, on a single thread. Its throughput is the per-LED cost of the shader.
+//! `presets/<layout>` renders the full mix graph of each preset in parallel chunks, like the LED
+//! worker, so its time is the wall time of the show's main pass.
+//!
+//! After the criterion run, a summary lists the frame time of each preset on each layout against
+//! the frame budget of `LedOutputFps::Fps90`. This covers the main pass alone: crossfades render
+//! the outgoing preset too, and the GUI's shader previews add more, so leave headroom.
+//!
+//! Presets are rendered through the LED worker's own `render_preset_graph` and `preset_uniforms`,
+//! with a slow sine standing in for the audio envelope that modulates their params.
+//!
+//! Run with `cargo bench -p cohen_gig --bench render`.
+
+#[allow(dead_code)]
+#[path = "../src/conf.rs"]
+mod conf;
+#[allow(dead_code)]
+#[path = "../src/layout.rs"]
+mod layout;
+#[allow(dead_code)]
+#[path = "../src/mad_mapper.rs"]
+mod mad_mapper;
+#[allow(dead_code)]
+#[path = "../src/render.rs"]
+mod render;
+#[allow(dead_code)]
+#[path = "../src/shader.rs"]
+mod shader;
+
+use criterion::{black_box, Criterion, Throughput};
+use nannou::prelude::*;
+use shader_shared::{CachedLedShaderInput, Shader, Uniforms, ALL_SHADERS};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::path::Path;
+use std::time::Instant;
+
+/// Presets covering the heaviest and most common shader pairings, by file stem.
+const PRESETS: &[&str] = &[
+    "acid-escher-hue",
+    "exit-music-gilmore-spiraling",
+    "bloom-gradient-plus-tunnel-prj",
+    "spiral-intersect-square-tunnel",
+    "karma-police-linerelax",
+    "life-led-wall-press-space-cycle",
+];
+/// The bundled MadMapper projects within `assets/map_mapper_projects`.
+const MAD_PROJECTS: &[&str] = &["SJ02-JOSH_COHEN-MM5-01.mad", "SJ02-JOSH_COHEN-MM6-01.mad"];
+/// The rate the show runs at, used to advance `time` between frames.
+const FRAME_FPS: conf::LedOutputFps = conf::LedOutputFps::Fps90;
+/// Frames rendered before timing each preset for the summary.
+const SUMMARY_WARMUP_FRAMES: u32 = 20;
+/// Frames timed for each preset for the summary.
+const SUMMARY_FRAMES: u32 = 200;
+/// The rate of the sine standing in for the audio envelope.
+const AUDIO_ENVELOPE_HZ: f32 = 0.5;
+
+struct Setup {
+    led_layout: conf::LedLayout,
+    layouts: Vec<Layout>,
+    presets: Vec<conf::Preset>,
+}
+
+struct Layout {
+    name: String,
+    inputs: Vec<CachedLedShaderInput>,
+}
+
+/// The colours of the previous frame, fed back to the shaders, and the frame being rendered.
+struct Frame {
+    last_colors: Vec<LinSrgb>,
+    output: Vec<LinSrgb>,
+}
+
+impl Setup {
+    fn load() -> Self {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let (mut global, presets) = conf::load(&assets);
+        global.led_layout.normalise();
+
+        let mut layouts = vec![Layout {
+            name: "manual".to_string(),
+            inputs: layout::manual_shader_inputs(&global.led_layout),
+        }];
+        for file_name in MAD_PROJECTS {
+            let path = assets.join("map_mapper_projects").join(file_name);
+            let project = mad_mapper::parse(&path)
+                .unwrap_or_else(|err| panic!("failed to parse {:?}: {}", path, err));
+            layouts.push(Layout {
+                name: file_name.trim_end_matches(".mad").to_string(),
+                inputs: layout::resolve_from_mad_project(&project).shader_inputs,
+            });
+        }
+
+        let presets = PRESETS
+            .iter()
+            .map(|&id| {
+                presets
+                    .list
+                    .iter()
+                    .find(|preset| preset.id == id)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        panic!("no preset {:?} in {:?}", id, conf::presets_dir(&assets))
+                    })
+            })
+            .collect();
+
+        Setup {
+            led_layout: global.led_layout,
+            layouts,
+            presets,
+        }
+    }
+}
+
+impl Frame {
+    fn new(led_count: usize) -> Self {
+        Frame {
+            last_colors: vec![lin_srgb(0.0, 0.0, 0.0); led_count],
+            output: vec![lin_srgb(0.0, 0.0, 0.0); led_count],
+        }
+    }
+
+    fn render(&mut self, inputs: &[CachedLedShaderInput], uniforms: &Uniforms) {
+        ::shader::shader_batch(inputs, &self.last_colors, uniforms, &mut self.output).unwrap();
+        std::mem::swap(&mut self.last_colors, &mut self.output);
+    }
+
+    fn render_par(&mut self, inputs: &[CachedLedShaderInput], uniforms: &Uniforms) {
+        render::render_preset_graph(
+            shader::BUILTIN,
+            inputs,
+            uniforms,
+            &self.last_colors,
+            &mut self.output,
+        )
+        .unwrap();
+        std::mem::swap(&mut self.last_colors, &mut self.output);
+    }
+}
+
+fn frame_secs() -> f32 {
+    1.0 / FRAME_FPS
+        .fps_limit()
+        .expect("the show's frame rate is capped")
+}
+
+/// The given shader in the left slot, passed through untouched by the rest of the mix.
+fn shader_uniforms(led_layout: &conf::LedLayout, shader: Shader) -> Uniforms {
+    Uniforms {
+        resolution: layout::shader_resolution(led_layout),
+        ..Uniforms::solo(shader, 0.0)
+    }
+}
+
+/// The uniforms the LED worker renders the preset with at `time`.
+fn preset_uniforms(led_layout: &conf::LedLayout, preset: &conf::Preset, time: f32) -> Uniforms {
+    let audio_envelope = 0.5 + 0.5 * (time * AUDIO_ENVELOPE_HZ * TAU).sin();
+    render::preset_uniforms(
+        preset,
+        led_layout,
+        time,
+        audio_envelope,
+        [0.5; 3],
+        HashMap::new(),
+    )
+}
+
+fn shaders(c: &mut Criterion, setup: &Setup) {
+    for layout in &setup.layouts {
+        let mut group = c.benchmark_group(format!("shaders/{}", layout.name));
+        group.throughput(Throughput::Elements(layout.inputs.len() as u64));
+        for shader in ALL_SHADERS {
+            let mut uniforms = shader_uniforms(&setup.led_layout, *shader);
+            let mut frame = Frame::new(layout.inputs.len());
+            group.bench_function(shader.name(), |b| {
+                b.iter(|| {
+                    uniforms.time += frame_secs();
+                    frame.render(&layout.inputs, &uniforms);
+                    black_box(&frame.last_colors);
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+fn presets(c: &mut Criterion, setup: &Setup) {
+    for layout in &setup.layouts {
+        let mut group = c.benchmark_group(format!("presets/{}", layout.name));
+        group.throughput(Throughput::Elements(layout.inputs.len() as u64));
+        for preset in &setup.presets {
+            let mut time = 0.0;
+            let mut frame = Frame::new(layout.inputs.len());
+            group.bench_function(&preset.id, |b| {
+                b.iter(|| {
+                    time += frame_secs();
+                    let uniforms = preset_uniforms(&setup.led_layout, preset, time);
+                    frame.render_par(&layout.inputs, &uniforms);
+                    black_box(&frame.last_colors);
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+/// Print the mean frame time of each preset on each layout and whether it fits the budget.
+fn frame_budget_summary(setup: &Setup) {
+    let budget_ms = frame_secs() * 1_000.0;
+    println!(
+        "\nMain pass frame time against the {} budget of {:.2} ms",
+        FRAME_FPS.label(),
+        budget_ms
+    );
+    println!(
+        "{:<34} {:<26} {:>6} {:>9} {:>7} {:>5}",
+        "preset", "layout", "LEDs", "ms/frame", "ns/LED", "fits"
+    );
+    for preset in &setup.presets {
+        for layout in &setup.layouts {
+            let mut time = 0.0;
+            let mut frame = Frame::new(layout.inputs.len());
+            let mut render = |frames: u32| {
+                for _ in 0..frames {
+                    time += frame_secs();
+                    let uniforms = preset_uniforms(&setup.led_layout, preset, time);
+                    frame.render_par(&layout.inputs, &uniforms);
+                }
+            };
+            render(SUMMARY_WARMUP_FRAMES);
+            let started = Instant::now();
+            render(SUMMARY_FRAMES);
+            let frame_ms = started.elapsed().as_secs_f32() * 1_000.0 / SUMMARY_FRAMES as f32;
+            let led_ns = frame_ms * 1_000_000.0 / layout.inputs.len().max(1) as f32;
+            let fits = if frame_ms <= budget_ms { "yes" } else { "NO" };
+            println!(
+                "{:<34} {:<26} {:>6} {:>9.3} {:>7.1} {:>5}",
+                preset.id,
+                layout.name,
+                layout.inputs.len(),
+                frame_ms,
+                led_ns,
+                fits
+            );
+        }
+    }
+}
+
+fn main() {
+    let setup = Setup::load();
+    let mut criterion = Criterion::default().configure_from_args();
+    shaders(&mut criterion, &setup);
+    presets(&mut criterion, &setup);
+    criterion.final_summary();
+    frame_budget_summary(&setup);
+}
@@ -0,0 +1,265 @@
+//! A minimal Art-Net (ArtDmx) sender.
+//!
+//! Payloads handed to `send_property_values` are the same start-code-prefixed buffers that we
+//! build for sACN. The start code is stripped before packing as ArtDmx carries raw slot data only.
+//! Universes are numbered as for sACN, from 1, and shifted down by the configured offset to give
+//! Art-Net port-addresses, which are numbered from 0.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+
+/// The UDP port on which Art-Net nodes listen.
+pub const ARTNET_PORT: u16 = 6454;
+
+const ARTNET_ID: &[u8; 8] = b"Art-Net\0";
+const OP_DMX: u16 = 0x5000;
+const OP_SYNC: u16 = 0x5200;
+const PROTOCOL_VERSION: u16 = 14;
+const ARTDMX_HEADER_LEN: usize = 18;
+const MAX_DMX_SLOTS: usize = 512;
+/// Port-addresses are 15 bits: a 7-bit Net, 4-bit Sub-Net and 4-bit Universe.
+const MAX_PORT_ADDRESS: u16 = 0x7FFF;
+
+pub struct ArtNetSender {
+    socket: UdpSocket,
+    destination: SocketAddr,
+    /// ArtSync must be broadcast, even when ArtDmx is unicast.
+    sync_destination: SocketAddr,
+    /// Subtracted from each universe to give its port-address.
+    universe_offset: u16,
+    /// The next sequence number of each port-address.
+    sequences: HashMap<u16, u8>,
+}
+
+impl ArtNetSender {
+    /// Bind a sender on the given interface.
+    ///
+    /// When `destination_ip` is `None` packets are broadcast to `255.255.255.255`. ArtSync is
+    /// always broadcast, to `sync_ip` if given or else to the subnet of the interface or
+    /// destination (see `sync_broadcast_ip`).
+    ///
+    /// Broadcasts can't leave a loopback interface, so on one everything is sent to localhost
+    /// instead, for a previs running on the same machine.
+    pub fn new(
+        interface_ip: Option<Ipv4Addr>,
+        destination_ip: Option<Ipv4Addr>,
+        sync_ip: Option<Ipv4Addr>,
+        universe_offset: u16,
+    ) -> io::Result<Self> {
+        let bind_ip = interface_ip.unwrap_or(Ipv4Addr::UNSPECIFIED);
+        let socket = UdpSocket::bind(SocketAddr::new(bind_ip.into(), 0))?;
+        let (destination_ip, sync_ip) = if bind_ip.is_loopback() {
+            let localhost = destination_ip
+                .filter(|ip| ip.is_loopback())
+                .unwrap_or(Ipv4Addr::LOCALHOST);
+            (localhost, localhost)
+        } else {
+            socket.set_broadcast(true)?;
+            let sync_ip =
+                sync_ip.unwrap_or_else(|| sync_broadcast_ip(interface_ip, destination_ip));
+            (destination_ip.unwrap_or(Ipv4Addr::BROADCAST), sync_ip)
+        };
+        Ok(Self {
+            socket,
+            destination: SocketAddr::new(destination_ip.into(), ARTNET_PORT),
+            sync_destination: SocketAddr::new(sync_ip.into(), ARTNET_PORT),
+            universe_offset,
+            sequences: HashMap::new(),
+        })
+    }
+
+    /// Send a start-code-prefixed DMX payload for the given universe.
+    ///
+    /// Fails without sending if the universe has no port-address under the configured offset.
+    pub fn send_property_values(
+        &mut self,
+        universe: u16,
+        property_values: &[u8],
+    ) -> io::Result<()> {
+        let port_address = port_address(universe, self.universe_offset)?;
+        let slots = property_values.get(1..).unwrap_or(&[]);
+        let sequence = self.sequences.entry(port_address).or_insert(1);
+        let packet = build_art_dmx_packet(*sequence, port_address, slots);
+        self.socket.send_to(&packet, self.destination)?;
+        // Sequence 0 disables re-ordering on the receiver, so skip it when wrapping.
+        *sequence = sequence.checked_add(1).unwrap_or(1);
+        Ok(())
+    }
+
+    /// Broadcast an ArtSync, asking nodes to output the ArtDmx data they have buffered.
+    pub fn send_sync(&mut self) -> io::Result<()> {
+        self.socket
+            .send_to(&build_art_sync_packet(), self.sync_destination)?;
+        Ok(())
+    }
+}
+
+/// The port-address a universe is sent to, `universe - offset`.
+pub fn port_address(universe: u16, offset: u16) -> io::Result<u16> {
+    universe
+        .checked_sub(offset)
+        .filter(|&port_address| port_address <= MAX_PORT_ADDRESS)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "universe {} has no Art-Net port-address with a universe offset of {}",
+                    universe, offset
+                ),
+            )
+        })
+}
+
+/// The directed broadcast address ArtSync is sent to.
+///
+/// Art-Net 4 requires ArtSync to be broadcast. The subnet is taken from the interface, falling
+/// back to the unicast destination. Addresses in the Art-Net primary (2.x.x.x) and secondary
+/// (10.x.x.x) ranges use their standard /8 mask and all others are assumed to be /24, so networks
+/// with another mask need the address set in the config. Without either address the limited
+/// broadcast address is used.
+pub fn sync_broadcast_ip(
+    interface_ip: Option<Ipv4Addr>,
+    destination_ip: Option<Ipv4Addr>,
+) -> Ipv4Addr {
+    let subnet_ip = interface_ip
+        .filter(|ip| !ip.is_unspecified() && !ip.is_loopback())
+        .or_else(|| destination_ip.filter(|ip| !ip.is_broadcast() && !ip.is_unspecified()));
+    match subnet_ip {
+        Some(ip) => {
+            let [a, b, c, _] = ip.octets();
+            match a {
+                2 | 10 => Ipv4Addr::new(a, 255, 255, 255),
+                _ => Ipv4Addr::new(a, b, c, 255),
+            }
+        }
+        None => Ipv4Addr::BROADCAST,
+    }
+}
+
+/// Pack an ArtDmx packet.
+///
+/// Art-Net requires an even slot count between 2 and 512, so the data is zero padded as needed
+/// and truncated to 512 slots.
+pub fn build_art_dmx_packet(sequence: u8, port_address: u16, slots: &[u8]) -> Vec<u8> {
+    let slots = &slots[..slots.len().min(MAX_DMX_SLOTS)];
+    let length = (slots.len().max(2) + 1) & !1;
+    let mut packet = Vec::with_capacity(ARTDMX_HEADER_LEN + length);
+    packet.extend_from_slice(ARTNET_ID);
+    packet.extend_from_slice(&OP_DMX.to_le_bytes());
+    packet.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+    packet.push(sequence);
+    // Physical input port. Informational only.
+    packet.push(0);
+    // SubUni (low byte) followed by Net (high 7 bits).
+    packet.push((port_address & 0xFF) as u8);
+    packet.push(((port_address >> 8) & 0x7F) as u8);
+    packet.extend_from_slice(&(length as u16).to_be_bytes());
+    packet.extend_from_slice(slots);
+    packet.resize(ARTDMX_HEADER_LEN + length, 0);
+    packet
+}
+
+/// Pack an ArtSync packet.
+pub fn build_art_sync_packet() -> Vec<u8> {
+    let mut packet = Vec::with_capacity(14);
+    packet.extend_from_slice(ARTNET_ID);
+    packet.extend_from_slice(&OP_SYNC.to_le_bytes());
+    packet.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+    // Aux1 and Aux2, both transmitted as zero.
+    packet.extend_from_slice(&[0, 0]);
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_art_dmx_packet, build_art_sync_packet, port_address, sync_broadcast_ip, ArtNetSender,
+    };
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn build_art_dmx_packet_packs_header_and_slots() {
+        let packet = build_art_dmx_packet(12, 0x0123, &[11, 22, 33, 44, 55, 66]);
+
+        assert_eq!(&packet[0..8], b"Art-Net\0");
+        // OpDmx is little endian, protocol version big endian.
+        assert_eq!(&packet[8..10], &[0x00, 0x50]);
+        assert_eq!(&packet[10..12], &[0, 14]);
+        assert_eq!(packet[12], 12);
+        assert_eq!(packet[13], 0);
+        assert_eq!(packet[14], 0x23);
+        assert_eq!(packet[15], 0x01);
+        assert_eq!(&packet[16..18], &[0, 6]);
+        assert_eq!(&packet[18..], &[11, 22, 33, 44, 55, 66]);
+    }
+
+    #[test]
+    fn build_art_dmx_packet_pads_to_even_length() {
+        let packet = build_art_dmx_packet(1, 7, &[1, 2, 3]);
+
+        assert_eq!(&packet[16..18], &[0, 4]);
+        assert_eq!(&packet[18..], &[1, 2, 3, 0]);
+
+        let empty = build_art_dmx_packet(1, 7, &[]);
+        assert_eq!(&empty[16..18], &[0, 2]);
+        assert_eq!(&empty[18..], &[0, 0]);
+    }
+
+    #[test]
+    fn build_art_sync_packet_packs_header() {
+        let packet = build_art_sync_packet();
+
+        assert_eq!(&packet[0..8], b"Art-Net\0");
+        assert_eq!(&packet[8..], &[0x00, 0x52, 0, 14, 0, 0]);
+    }
+
+    #[test]
+    fn universes_are_offset_to_port_addresses() {
+        assert_eq!(port_address(1, 1).unwrap(), 0);
+        assert_eq!(port_address(17, 1).unwrap(), 0x10);
+        assert_eq!(port_address(1, 0).unwrap(), 1);
+        // Below the offset, or beyond the 15-bit port-address.
+        assert!(port_address(0, 1).is_err());
+        assert!(port_address(0x8001, 1).is_err());
+    }
+
+    #[test]
+    fn loopback_interface_sends_to_localhost() {
+        let sender = ArtNetSender::new(Some(Ipv4Addr::LOCALHOST), None, None, 1).unwrap();
+        assert_eq!(sender.destination.ip(), Ipv4Addr::LOCALHOST);
+        assert_eq!(sender.sync_destination.ip(), Ipv4Addr::LOCALHOST);
+    }
+
+    #[test]
+    fn configured_sync_ip_overrides_the_subnet_broadcast() {
+        let interface = Ipv4Addr::UNSPECIFIED;
+        let sync_ip = Ipv4Addr::new(192, 168, 3, 255);
+        let sender = ArtNetSender::new(Some(interface), None, Some(sync_ip), 1).unwrap();
+        assert_eq!(sender.sync_destination.ip(), sync_ip);
+
+        let sender = ArtNetSender::new(Some(interface), Some([192, 168, 1, 50].into()), None, 1);
+        assert_eq!(
+            sender.unwrap().sync_destination.ip(),
+            Ipv4Addr::new(192, 168, 1, 255)
+        );
+    }
+
+    #[test]
+    fn sync_broadcast_ip_uses_the_subnet_broadcast() {
+        let ip = |s: &str| s.parse::<Ipv4Addr>().ok();
+        assert_eq!(
+            sync_broadcast_ip(ip("2.0.0.10"), ip("2.0.0.50")),
+            Ipv4Addr::new(2, 255, 255, 255)
+        );
+        assert_eq!(
+            sync_broadcast_ip(None, ip("192.168.1.50")),
+            Ipv4Addr::new(192, 168, 1, 255)
+        );
+        assert_eq!(
+            sync_broadcast_ip(ip("10.1.2.3"), None),
+            Ipv4Addr::new(10, 255, 255, 255)
+        );
+        assert_eq!(sync_broadcast_ip(None, None), Ipv4Addr::BROADCAST);
+    }
+}
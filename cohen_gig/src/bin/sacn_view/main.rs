@@ -0,0 +1,323 @@
+//! A standalone sACN receiver for checking the app's output without a lighting desk or MadMapper.
+//!
+//! Listens for sACN on port 5568, reconstructs the LED image from the same config and MadMapper
+//! project as the app and either draws it in a window or, with `--headless`, writes it out as PNG
+//! frames.
+//!
+//! ```text
+//! cargo run --bin sacn_view -- [--config PATH] [--mad PATH] [--interface IP]
+//!     [--headless --out DIR [--fps N] [--frames N]]
+//! ```
+
+#[allow(dead_code)]
+#[path = "../../conf.rs"]
+mod conf;
+#[allow(dead_code)]
+#[path = "../../mad_mapper.rs"]
+mod mad_mapper;
+mod pixel_map;
+#[allow(dead_code)]
+#[path = "../../sacn_discovery.rs"]
+mod sacn_discovery;
+#[allow(dead_code)]
+#[path = "../../sacn_receiver.rs"]
+mod sacn_receiver;
+// Only used by the receiver's tests.
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../../sacn_sender.rs"]
+mod sacn_sender;
+
+use nannou::image::{Rgb, RgbImage};
+use nannou::prelude::*;
+use pixel_map::PixelMap;
+use sacn_receiver::SacnReceiver;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Packets from this source name are ignored, so pick one that nothing else uses.
+const VIEWER_SOURCE_NAME: &str = "Cohen sACN View";
+const DEFAULT_HEADLESS_FPS: f32 = 30.0;
+const WINDOW_W: u32 = 1200;
+const WINDOW_H: u32 = 600;
+
+const USAGE: &str = "usage: sacn_view [--config PATH] [--mad PATH] [--interface IP] \
+                     [--headless --out DIR [--fps N] [--frames N]]";
+
+/// Command line options.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Args {
+    /// Defaults to `config.json` in the project assets.
+    config: Option<PathBuf>,
+    /// Overrides the config's MadMapper project.
+    mad: Option<PathBuf>,
+    /// Join the multicast group of each universe on this interface. Without it only unicast and
+    /// localhost packets are received.
+    interface: Option<Ipv4Addr>,
+    headless: bool,
+    out: Option<PathBuf>,
+    fps: Option<f32>,
+    /// Stop after writing this many frames.
+    frames: Option<u64>,
+}
+
+struct Model {
+    receiver: SacnReceiver,
+    pixel_map: PixelMap,
+}
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.headless {
+        if let Err(err) = run_headless(&args) {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    } else {
+        nannou::app(model).update(update).run();
+    }
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("`{}` needs a value", arg))
+            };
+            match arg.as_str() {
+                "--config" => parsed.config = Some(value()?.into()),
+                "--mad" => parsed.mad = Some(value()?.into()),
+                "--interface" => {
+                    let ip = value()?;
+                    let ip = ip
+                        .parse()
+                        .map_err(|err| format!("Invalid interface IP `{}`: {}", ip, err))?;
+                    parsed.interface = Some(ip);
+                }
+                "--headless" => parsed.headless = true,
+                "--out" => parsed.out = Some(value()?.into()),
+                "--fps" => {
+                    let fps = value()?;
+                    match fps.parse::<f32>() {
+                        Ok(fps) if fps > 0.0 => parsed.fps = Some(fps),
+                        _ => return Err(format!("Invalid frame rate `{}`", fps)),
+                    }
+                }
+                "--frames" => {
+                    let frames = value()?;
+                    let frames = frames
+                        .parse()
+                        .map_err(|err| format!("Invalid frame count `{}`: {}", frames, err))?;
+                    parsed.frames = Some(frames);
+                }
+                _ => return Err(format!("Unknown argument `{}`", arg)),
+            }
+        }
+        if parsed.headless && parsed.out.is_none() {
+            return Err("`--headless` needs `--out`".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+/// Build the pixel map from the config and project, then start listening.
+fn start(args: &Args) -> Result<Model, String> {
+    let config_path = match &args.config {
+        Some(path) => path.clone(),
+        None => {
+            let assets = nannou::app::find_assets_path()
+                .map_err(|err| format!("Couldn't find the `assets` directory: {}", err))?;
+            conf::config_path(&assets)
+        }
+    };
+    let mut config: conf::GlobalConfig = nannou::io::load_from_json(&config_path)
+        .map_err(|err| format!("Couldn't load {}: {}", config_path.display(), err))?;
+    config.led_layout.normalise();
+
+    let mad_path = args
+        .mad
+        .clone()
+        .or_else(|| config.madmapper_project_path.as_ref().map(PathBuf::from));
+    let pixel_map = match mad_path {
+        Some(path) => {
+            let project = mad_mapper::parse(&path)?;
+            PixelMap::from_mad_project(&project, &config.channel_layouts, config.dmx_bit_depth)
+        }
+        None => PixelMap::from_led_layout(
+            &config.led_layout,
+            config.led_start_universe,
+            &config.channel_layouts,
+            config.dmx_bit_depth,
+        ),
+    };
+
+    let universes = pixel_map.universes();
+    let bind_addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), sacn_receiver::SACN_PORT);
+    let receiver =
+        SacnReceiver::bind(bind_addr, &universes, VIEWER_SOURCE_NAME).map_err(|err| {
+            format!(
+                "Couldn't listen for sACN on port {}: {}",
+                sacn_receiver::SACN_PORT,
+                err
+            )
+        })?;
+    if let Some(interface_ip) = args.interface {
+        receiver
+            .join_multicast(interface_ip)
+            .map_err(|err| format!("Couldn't join multicast on {}: {}", interface_ip, err))?;
+    }
+    eprintln!(
+        "Listening for {} LEDs on universes {:?}",
+        pixel_map.rows.iter().map(Vec::len).sum::<usize>(),
+        universes
+    );
+    Ok(Model {
+        receiver,
+        pixel_map,
+    })
+}
+
+/// Write a PNG every frame interval while any of our universes are being received.
+fn run_headless(args: &Args) -> Result<(), String> {
+    let mut model = start(args)?;
+    let out = args.out.as_deref().expect("checked by `Args::parse`");
+    std::fs::create_dir_all(out)
+        .map_err(|err| format!("Couldn't create {}: {}", out.display(), err))?;
+    let interval = Duration::from_secs_f32(1.0 / args.fps.unwrap_or(DEFAULT_HEADLESS_FPS));
+    let mut written = 0;
+    while args.frames.is_none_or(|frames| written < frames) {
+        let started = Instant::now();
+        model
+            .receiver
+            .poll(started)
+            .map_err(|err| format!("Couldn't receive sACN: {}", err))?;
+        if !model.receiver.active_universes().is_empty() {
+            let path = out.join(format!("frame_{:05}.png", written));
+            write_png(&path, &decode(&model))?;
+            written += 1;
+        }
+        std::thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+    Ok(())
+}
+
+/// The latest colour of every LED, one row per drawn row.
+fn decode(model: &Model) -> Vec<Vec<[u8; 3]>> {
+    model.pixel_map.decode(|universe| {
+        model
+            .receiver
+            .universe(universe)
+            .map(|received| &received.payload[..])
+    })
+}
+
+/// One image pixel per LED. Short rows are padded with black.
+fn write_png(path: &Path, image: &[Vec<[u8; 3]>]) -> Result<(), String> {
+    let width = image.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let height = image.len().max(1);
+    let png = RgbImage::from_fn(width as u32, height as u32, |x, y| {
+        let rgb = image
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or([0; 3]);
+        Rgb(rgb)
+    });
+    png.save(path)
+        .map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+}
+
+fn model(app: &App) -> Model {
+    let args = Args::parse(std::env::args().skip(1)).expect("arguments were checked in `main`");
+    let model = start(&args).unwrap_or_else(|err| panic!("{}", err));
+    app.new_window()
+        .title("COHEN GIG - sACN VIEW")
+        .size(WINDOW_W, WINDOW_H)
+        .view(view)
+        .build()
+        .expect("failed to build sACN view window");
+    model
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    if let Err(err) = model.receiver.poll(Instant::now()) {
+        eprintln!("Couldn't receive sACN: {}", err);
+    }
+}
+
+/// Each row is drawn as a line of LEDs, spread across the window like the app's previs.
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    let w = app.window_rect();
+    let image = decode(model);
+    let row_count = image.len();
+    let y_scale = w.h() * 0.4;
+    let x_scale = w.w() * 0.45;
+    for (row_ix, row) in image.iter().enumerate() {
+        let y = if row_count <= 1 {
+            0.0
+        } else {
+            (1.0 - (row_ix as f32 / (row_count - 1) as f32) * 2.0) * y_scale
+        };
+        let vs = row.iter().enumerate().map(|(ix, &[r, g, b])| {
+            let x = if row.len() <= 1 {
+                0.0
+            } else {
+                (ix as f32 / (row.len() - 1) as f32) * 2.0 - 1.0
+            };
+            let colour = rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+            (pt2(x * x_scale, y), colour)
+        });
+        draw.polyline().weight(5.0).points_colored(vs);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Args;
+    use std::net::Ipv4Addr;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_headless_options() {
+        let args = parse(&[
+            "--headless",
+            "--out",
+            "frames",
+            "--interface",
+            "10.0.0.2",
+            "--frames",
+            "3",
+        ])
+        .unwrap();
+        assert!(args.headless);
+        assert_eq!(args.out, Some("frames".into()));
+        assert_eq!(args.interface, Some(Ipv4Addr::new(10, 0, 0, 2)));
+        assert_eq!(args.frames, Some(3));
+    }
+
+    #[test]
+    fn rejects_incomplete_options() {
+        assert!(parse(&["--headless"]).is_err());
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["--fps", "0"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+}
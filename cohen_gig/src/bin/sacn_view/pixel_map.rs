@@ -0,0 +1,227 @@
+//! Where each LED's channels are found in the incoming universes.
+//!
+//! This mirrors the packing in the app's `build_sacn_payloads`: pixels are laid out from the
+//! first slot of a row or fixture's start universe, and a pixel that wouldn't fit in the rest of
+//! a universe starts on the next one instead.
+
+use crate::conf::{ChannelLayouts, ChannelOrder, DmxBitDepth, LedLayout, PixelChannel};
+use crate::mad_mapper::MadProject;
+use sacn::packet::UNIVERSE_CHANNEL_CAPACITY;
+
+/// The DMX channels used per LED by the manual layout.
+const MANUAL_CHANNELS_PER_PIXEL: usize = 3;
+
+/// The DMX address of a single LED.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PixelAddress {
+    pub universe: u16,
+    /// Index of the pixel's first slot within a start-code-prefixed payload.
+    pub slot: usize,
+    pub order: ChannelOrder,
+    pub bit_depth: DmxBitDepth,
+}
+
+/// Every LED's address, grouped into the rows in which they are drawn.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PixelMap {
+    pub rows: Vec<Vec<PixelAddress>>,
+}
+
+impl PixelMap {
+    /// The manual layout, sent sequentially from `start_universe`, top row first.
+    pub fn from_led_layout(
+        led_layout: &LedLayout,
+        start_universe: u16,
+        channel_layouts: &ChannelLayouts,
+        bit_depth: DmxBitDepth,
+    ) -> Self {
+        let order = channel_layouts.default;
+        let addresses = pixel_addresses(
+            start_universe,
+            led_layout.led_count(),
+            MANUAL_CHANNELS_PER_PIXEL,
+            order,
+            bit_depth,
+        );
+        let rows = addresses
+            .chunks(led_layout.leds_per_row().max(1))
+            .map(<[_]>::to_vec)
+            .collect();
+        PixelMap { rows }
+    }
+
+    /// One row per MadMapper fixture, ordered top to bottom as in the app's preview.
+    pub fn from_mad_project(
+        project: &MadProject,
+        channel_layouts: &ChannelLayouts,
+        bit_depth: DmxBitDepth,
+    ) -> Self {
+        let rows = project
+            .fixtures_by_row()
+            .into_iter()
+            .map(|fixture| {
+                pixel_addresses(
+                    fixture.universe,
+                    fixture.pixel_count,
+                    fixture.channels_per_pixel as usize,
+                    channel_layouts.for_fixture(&fixture.name),
+                    bit_depth,
+                )
+            })
+            .collect();
+        PixelMap { rows }
+    }
+
+    /// Every universe that carries at least one LED, in ascending order.
+    pub fn universes(&self) -> Vec<u16> {
+        let mut universes: Vec<u16> = self.rows.iter().flatten().map(|a| a.universe).collect();
+        universes.sort_unstable();
+        universes.dedup();
+        universes
+    }
+
+    /// The longest row, i.e. the width of the reconstructed image.
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Reconstruct every LED's colour, reading payloads through `universe`.
+    ///
+    /// LEDs on universes that aren't being received are black.
+    pub fn decode<'a>(&self, universe: impl Fn(u16) -> Option<&'a [u8]>) -> Vec<Vec<[u8; 3]>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|address| match universe(address.universe) {
+                        Some(payload) => decode_pixel(address, payload),
+                        None => [0; 3],
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The addresses of `count` pixels packed from the first slot of `start_universe`.
+fn pixel_addresses(
+    start_universe: u16,
+    count: usize,
+    channels_per_pixel: usize,
+    order: ChannelOrder,
+    bit_depth: DmxBitDepth,
+) -> Vec<PixelAddress> {
+    // The packer never shrinks a pixel below the channels its order needs.
+    let pixel_len = channels_per_pixel.max(order.channels().len() * bit_depth.slots_per_channel());
+    let mut universe = start_universe;
+    let mut payload_len = 1;
+    let mut addresses = Vec::with_capacity(count);
+    for _ in 0..count {
+        addresses.push(PixelAddress {
+            universe,
+            slot: payload_len,
+            order,
+            bit_depth,
+        });
+        payload_len += pixel_len;
+        if payload_len + pixel_len > UNIVERSE_CHANNEL_CAPACITY {
+            universe = universe.wrapping_add(1);
+            payload_len = 1;
+        }
+    }
+    addresses
+}
+
+/// Read a pixel back to 8-bit RGB, folding any white and amber channels into the colour.
+///
+/// The levels are those on the wire, so calibration and dimming are included.
+pub fn decode_pixel(address: &PixelAddress, payload: &[u8]) -> [u8; 3] {
+    let slots_per_channel = address.bit_depth.slots_per_channel();
+    let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+    for (ix, channel) in address.order.channels().iter().enumerate() {
+        let slot = address.slot + ix * slots_per_channel;
+        let value = match address.bit_depth {
+            DmxBitDepth::Eight => payload.get(slot).map(|&v| v as f32 / u8::MAX as f32),
+            DmxBitDepth::Sixteen => payload
+                .get(slot..slot + 2)
+                .map(|v| u16::from_be_bytes([v[0], v[1]]) as f32 / u16::MAX as f32),
+        };
+        let value = value.unwrap_or(0.0);
+        match channel {
+            PixelChannel::Red => r += value,
+            PixelChannel::Green => g += value,
+            PixelChannel::Blue => b += value,
+            PixelChannel::White => {
+                r += value;
+                g += value;
+                b += value;
+            }
+            PixelChannel::Amber => {
+                r += value;
+                g += value * 0.5;
+            }
+        }
+    }
+    [r, g, b].map(|v: f32| (v.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_pixel, pixel_addresses, PixelAddress, PixelMap};
+    use crate::conf::{ChannelLayouts, ChannelOrder, DmxBitDepth, LedLayout};
+
+    #[test]
+    fn pixels_are_not_split_across_universes() {
+        let addresses = pixel_addresses(5, 171, 3, ChannelOrder::Rgb, DmxBitDepth::Eight);
+        assert_eq!(addresses[0].universe, 5);
+        assert_eq!(addresses[0].slot, 1);
+        // 170 RGB pixels fill a universe, leaving the last two slots unused.
+        assert_eq!(addresses[169].universe, 5);
+        assert_eq!(addresses[169].slot, 508);
+        assert_eq!(addresses[170].universe, 6);
+        assert_eq!(addresses[170].slot, 1);
+
+        let addresses = pixel_addresses(1, 86, 3, ChannelOrder::Rgb, DmxBitDepth::Sixteen);
+        assert_eq!(addresses[84].slot, 505);
+        assert_eq!((addresses[85].universe, addresses[85].slot), (2, 1));
+    }
+
+    #[test]
+    fn manual_layout_rows_start_from_the_top_left() {
+        let led_layout = LedLayout {
+            leds_per_metre: 2,
+            metres_per_row: 2,
+            row_count: 2,
+        };
+        let map = PixelMap::from_led_layout(
+            &led_layout,
+            1,
+            &ChannelLayouts::default(),
+            DmxBitDepth::Eight,
+        );
+        assert_eq!(map.rows.len(), 2);
+        assert_eq!(map.width(), 4);
+        assert_eq!(map.rows[1][0].slot, 13);
+        assert_eq!(map.universes(), vec![1]);
+
+        let mut payload = vec![0; 25];
+        payload[13..16].copy_from_slice(&[255, 128, 0]);
+        let image = map.decode(|universe| (universe == 1).then_some(&payload[..]));
+        assert_eq!(image[0][0], [0, 0, 0]);
+        assert_eq!(image[1][0], [255, 128, 0]);
+        // Unreceived universes are drawn black.
+        assert_eq!(map.decode(|_| None)[1][0], [0, 0, 0]);
+    }
+
+    #[test]
+    fn white_and_sixteen_bit_channels_fold_into_rgb() {
+        let address = PixelAddress {
+            universe: 1,
+            slot: 1,
+            order: ChannelOrder::Grbw,
+            bit_depth: DmxBitDepth::Sixteen,
+        };
+        let payload = [0, 0, 0, 255, 255, 0, 0, 128, 0];
+        assert_eq!(decode_pixel(&address, &payload), [255, 128, 128]);
+    }
+}
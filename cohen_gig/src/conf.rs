@@ -1,17 +1,11 @@
 use nannou::io::{load_from_json, save_to_json};
 use serde::{Deserialize, Serialize};
-use shader_shared::{
-    AcidGradient, BarTest, BlendMode, BlinkyCircles, BwGradient, ColourGrid, ColourPalettes,
-    EscherTilings, GilmoreAcid, GradientBars, HoopLoop, ImitationRiley, JustRelax, LifeLedWall,
-    LightPatternGenerator, LineGradient, Metafall, MitchWash, ParticleZoom, RadialKeta,
-    RadialLines, RowTest, SatisSpiraling, Shader, ShaderParams, ShapeEnvelopes, SolidHsvColour,
-    SolidRgbColour, SpiralIntersect, SquareTunnel, ThePulse, ToneMapping, TunnelProjection,
-    TwoDTiles, VertColourGradient,
-};
+use shader_shared::{BlendMode, Shader, ShaderParams, ToneMapping};
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::net::{AddrParseError, Ipv4Addr};
+use std::net::{AddrParseError, Ipv4Addr, SocketAddr};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Global runtime configuration.
 ///
@@ -23,6 +17,10 @@ pub struct GlobalConfig {
     /// Whether or not DMX is enabled.
     #[serde(default)]
     pub dmx_on: bool,
+    /// Output frames over which the LEDs fade to black when DMX is switched off or the app
+    /// exits, before the streams are terminated. 0 terminates straight away.
+    #[serde(default)]
+    pub dmx_off_fade_frames: u32,
     /// Whether or not the LED previs window is visible.
     #[serde(default = "default::preview_window_on")]
     pub preview_window_on: bool,
@@ -36,10 +34,67 @@ pub struct GlobalConfig {
     pub fade_to_black: FadeToBlack,
     #[serde(default = "default::sacn_interface_ip")]
     pub sacn_interface_ip: String,
+    /// A second interface on which all DMX output is repeated, for a redundant show network.
+    /// Leave blank to disable.
+    #[serde(default)]
+    pub mirror_interface_ip: String,
+    /// The protocol used to send LED data to the pixel controllers.
+    #[serde(default)]
+    pub dmx_protocol: DmxProtocol,
+    /// Unicast destination for Art-Net output. Leave blank to broadcast.
+    #[serde(default = "default::artnet_destination_ip")]
+    pub artnet_destination_ip: String,
+    /// Subtracted from each universe to give its Art-Net port-address, so that with the default
+    /// of 1 universe 1 is sent as port-address 0:0:0.
+    #[serde(default = "default::artnet_universe_offset")]
+    pub artnet_universe_offset: u16,
+    /// Where ArtSync is broadcast, for subnets whose broadcast address can't be guessed. Leave
+    /// blank to derive it from the interface or destination.
+    #[serde(default)]
+    pub artnet_sync_ip: String,
+    /// Destinations for the OPC and DDP protocols, which send whole pixel frames.
+    #[serde(default)]
+    pub pixel_destinations: PixelDestinations,
+    /// When set, every frame is tagged with this E1.31 synchronization universe and released
+    /// with a single sync packet once all universes have been sent.
+    #[serde(default)]
+    pub sacn_sync_universe: Option<u16>,
+    /// Source priority sent with each output universe. Universes without an entry use the E1.31
+    /// default of 100.
+    #[serde(default)]
+    pub sacn_priorities: BTreeMap<u16, u8>,
+    /// sACN receivers to unicast each output universe to, for networks that block multicast.
+    /// Universes with destinations are sent to each of them instead of their multicast group.
+    #[serde(default)]
+    pub sacn_unicast_destinations: BTreeMap<u16, Vec<String>>,
+    /// Incoming sACN, e.g. from a lighting console, merged into our output before sending.
+    #[serde(default)]
+    pub sacn_input: SacnInput,
     #[serde(default)]
     pub led_output_fps: LedOutputFps,
+    /// How the isolated shader previews shown in the GUI are rendered.
+    #[serde(default)]
+    pub shader_previews: ShaderPreviews,
     #[serde(default)]
     pub led_layout: LedLayout,
+    /// DMX channel order for the manual layout and for each MadMapper fixture.
+    #[serde(default)]
+    pub channel_layouts: ChannelLayouts,
+    /// Colour calibration profiles and their fixture or row assignments.
+    #[serde(default)]
+    pub calibration: Calibration,
+    /// Resolution of each colour channel sent over DMX.
+    #[serde(default)]
+    pub dmx_bit_depth: DmxBitDepth,
+    /// Carry each LED's quantisation error into the next frame to smooth slow, dim fades.
+    #[serde(default)]
+    pub led_dither: bool,
+    /// Estimated power draw budgets used to automatically limit brightness.
+    #[serde(default)]
+    pub power_limit: PowerLimit,
+    /// Photosensitivity safety stage that suppresses rapid large-area flashing.
+    #[serde(default)]
+    pub strobe_limit: StrobeLimit,
     /// Optional path to a MadMapper .mad project file.
     /// When Some, the layout and DMX addressing are derived from this file
     /// instead of the manual `led_layout` and `led_start_universe` fields.
@@ -168,76 +223,43 @@ struct StoredPreset {
     shader_mod_amounts_right: Vec<f32>,
 }
 
-/// Sparse on-disk storage for shader params.
-///
-/// Only the active shader for each slot is populated when saving, which avoids
-/// persisting the full parameter blob for every shader in every preset.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
-struct SparseShaderParams {
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    acid_gradient: Option<AcidGradient>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    blinky_circles: Option<BlinkyCircles>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    bw_gradient: Option<BwGradient>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    colour_grid: Option<ColourGrid>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    escher_tilings: Option<EscherTilings>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    gilmore_acid: Option<GilmoreAcid>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    gradient_bars: Option<GradientBars>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    just_relax: Option<JustRelax>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    life_led_wall: Option<LifeLedWall>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    light_pattern_generator: Option<LightPatternGenerator>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    line_gradient: Option<LineGradient>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    metafall: Option<Metafall>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    particle_zoom: Option<ParticleZoom>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    radial_lines: Option<RadialLines>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    satis_spiraling: Option<SatisSpiraling>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    spiral_intersect: Option<SpiralIntersect>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    square_tunnel: Option<SquareTunnel>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    the_pulse: Option<ThePulse>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    tunnel_projection: Option<TunnelProjection>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    vert_colour_gradient: Option<VertColourGradient>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    solid_hsv_colour: Option<SolidHsvColour>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    solid_rgb_colour: Option<SolidRgbColour>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    colour_palettes: Option<ColourPalettes>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    mitch_wash: Option<MitchWash>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    shape_envelopes: Option<ShapeEnvelopes>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    row_test: Option<RowTest>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    bar_test: Option<BarTest>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    two_d_tiles: Option<TwoDTiles>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    radial_keta: Option<RadialKeta>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    imitation_riley: Option<ImitationRiley>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    hoop_loop: Option<HoopLoop>,
+// Generates `SparseShaderParams` with a field for each shader in the registry.
+macro_rules! sparse_shader_params {
+    ($($(#[$prepare:ident])? $Shader:ident, $field:ident, $name:literal $params:tt)*) => {
+        /// Sparse on-disk storage for shader params.
+        ///
+        /// Only the active shader for each slot is populated when saving, which avoids
+        /// persisting the full parameter blob for every shader in every preset.
+        #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct SparseShaderParams {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                $field: Option<shader_shared::$Shader>,
+            )*
+        }
+
+        impl SparseShaderParams {
+            fn from_runtime(shader: Shader, params: &ShaderParams) -> Self {
+                let mut sparse = SparseShaderParams::default();
+                match shader {
+                    $(Shader::$Shader => sparse.$field = Some(params.$field),)*
+                }
+                sparse
+            }
+
+            fn into_runtime(self, shader: Shader) -> ShaderParams {
+                let mut params = ShaderParams::default();
+                match shader {
+                    $(Shader::$Shader => params.$field = self.$field.unwrap_or_default(),)*
+                }
+                params
+            }
+        }
+    };
 }
 
+shader_shared::for_each_shader!(sparse_shader_params);
+
 /// Fade to black parameters for each kind of fixture.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FadeToBlack {
@@ -256,6 +278,211 @@ pub enum LedOutputFps {
     Fps40,
 }
 
+/// How the isolated left, right and colourise previews above each shader column are rendered.
+///
+/// They are only rendered while the GUI is showing them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderPreviews {
+    #[serde(default)]
+    pub rate: ShaderPreviewRate,
+    #[serde(default)]
+    pub resolution: ShaderPreviewResolution,
+}
+
+/// How often the shader previews are rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShaderPreviewRate {
+    Off,
+    Fps5,
+    Fps15,
+    Fps30,
+    EveryFrame,
+}
+
+/// The share of LEDs rendered for the shader previews. The rest show the nearest rendered LED.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShaderPreviewResolution {
+    Full,
+    /// Every second LED along each row and column.
+    Half,
+    /// Every fourth LED along each row and column.
+    Quarter,
+}
+
+/// The order and kind of the DMX channels that make up a single pixel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelOrder {
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+    Rgbw,
+    Grbw,
+    Bgrw,
+    Wrgb,
+    Rgba,
+}
+
+/// A single colour channel within a pixel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelChannel {
+    Red,
+    Green,
+    Blue,
+    White,
+    Amber,
+}
+
+/// DMX channel layouts for the manual layout and individual MadMapper fixtures.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelLayouts {
+    /// Used for the manual layout and for any fixture without an override.
+    #[serde(default)]
+    pub default: ChannelOrder,
+    /// Overrides keyed by MadMapper fixture name.
+    #[serde(default)]
+    pub fixtures: BTreeMap<String, ChannelOrder>,
+}
+
+/// Pixel controller addresses for the OPC and DDP protocols.
+///
+/// Addresses are either `ip` or `ip:port`. Fixtures sharing an address are sent as one frame in
+/// fixture order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PixelDestinations {
+    /// Used for the manual layout and for any fixture without an override.
+    #[serde(default)]
+    pub default: String,
+    /// Overrides keyed by MadMapper fixture name.
+    #[serde(default)]
+    pub fixtures: BTreeMap<String, String>,
+}
+
+/// The power model used to estimate current draw and limit brightness per PSU group.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PowerLimit {
+    /// Whether groups are scaled down when they exceed their budget. Estimates are always shown.
+    #[serde(default)]
+    pub enabled: bool,
+    /// The current drawn by a single colour channel at full level.
+    #[serde(default = "default::power_limit::milliamps_per_channel")]
+    pub milliamps_per_channel: f32,
+    #[serde(default)]
+    pub groups: Vec<PsuGroup>,
+}
+
+/// Settings for the strobe safety limiter.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StrobeLimit {
+    #[serde(default)]
+    pub enabled: bool,
+    /// The highest large-area flash rate let through unfiltered.
+    #[serde(default = "default::strobe_limit::max_flash_hz")]
+    pub max_flash_hz: f32,
+    /// The fraction of LEDs that must flash faster than `max_flash_hz` to count as a large area.
+    #[serde(default = "default::strobe_limit::area_fraction")]
+    pub area_fraction: f32,
+}
+
+/// A set of fixtures or manual layout rows fed by the same power supply.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PsuGroup {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default::power_limit::budget_amps")]
+    pub budget_amps: f32,
+    /// MadMapper fixture names powered by this group.
+    #[serde(default)]
+    pub fixtures: Vec<String>,
+    /// Manual layout row indices powered by this group.
+    #[serde(default)]
+    pub rows: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DmxBitDepth {
+    Eight,
+    /// Each channel is sent as a coarse/fine pair of slots, most significant first.
+    Sixteen,
+}
+
+/// Colour correction applied to a pixel as it is packed into DMX.
+///
+/// Shaders stay in linear space; profiles only affect the bytes that go out on the wire.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationProfile {
+    /// Transfer curve exponent applied to each channel. `1.0` is linear.
+    #[serde(default = "default::calibration::gamma")]
+    pub gamma: f32,
+    /// Optional lookup table mapping evenly spaced inputs in `0..=1` to outputs.
+    ///
+    /// When it holds two or more points it is used in place of `gamma`.
+    #[serde(default)]
+    pub lut: Vec<f32>,
+    /// Per-channel RGB gains applied before the curve.
+    #[serde(default = "default::calibration::white_balance")]
+    pub white_balance: [f32; 3],
+    /// The brightness ceiling applied after the curve.
+    #[serde(default = "default::calibration::max_level")]
+    pub max_level: f32,
+}
+
+/// Named calibration profiles and where they are applied.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Calibration {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, CalibrationProfile>,
+    /// Profile names keyed by MadMapper fixture name.
+    #[serde(default)]
+    pub fixtures: BTreeMap<String, String>,
+    /// Profile names keyed by manual layout row index.
+    #[serde(default)]
+    pub rows: BTreeMap<usize, String>,
+}
+
+/// Settings for receiving sACN and merging it into our output.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SacnInput {
+    #[serde(default)]
+    pub enabled: bool,
+    /// The universes to listen on. Data for any other universe is ignored.
+    #[serde(default)]
+    pub universes: Vec<u16>,
+    #[serde(default)]
+    pub merge_mode: MergeMode,
+    /// The universe whose channels drive app controls through the MIDI mapping, letting a house
+    /// console run the show. Received regardless of `enabled` or DMX output.
+    #[serde(default)]
+    pub control_universe: Option<u16>,
+    /// Listen for E1.31 universe discovery and list the other sources on the network. Like the
+    /// control universe, this works regardless of `enabled` or DMX output.
+    #[serde(default)]
+    pub discover_sources: bool,
+}
+
+/// How incoming DMX is combined with our own output for the same universe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MergeMode {
+    /// Highest takes precedence: each slot takes the higher of the two levels.
+    Htp,
+    /// Latest takes precedence: each slot follows whichever source changed it last.
+    Ltp,
+    /// The source with the higher universe priority wins outright, falling back to HTP on a tie.
+    Priority,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DmxProtocol {
+    Sacn,
+    ArtNet,
+    /// Open Pixel Control over TCP.
+    Opc,
+    /// Distributed Display Protocol over UDP.
+    Ddp,
+}
+
 pub fn config_path(assets: &Path) -> PathBuf {
     assets.join("config.json")
 }
@@ -377,13 +604,31 @@ impl Default for GlobalConfig {
     fn default() -> Self {
         GlobalConfig {
             dmx_on: Default::default(),
+            dmx_off_fade_frames: 0,
             preview_window_on: default::preview_window_on(),
             audio_input_device: default::audio_input_device(),
             led_start_universe: default::led_start_universe(),
             fade_to_black: Default::default(),
             sacn_interface_ip: default::sacn_interface_ip(),
+            mirror_interface_ip: String::new(),
+            dmx_protocol: Default::default(),
+            artnet_destination_ip: default::artnet_destination_ip(),
+            artnet_universe_offset: default::artnet_universe_offset(),
+            artnet_sync_ip: String::new(),
+            pixel_destinations: Default::default(),
+            sacn_sync_universe: None,
+            sacn_priorities: Default::default(),
+            sacn_unicast_destinations: Default::default(),
+            sacn_input: Default::default(),
             led_output_fps: Default::default(),
+            shader_previews: Default::default(),
             led_layout: Default::default(),
+            channel_layouts: Default::default(),
+            calibration: Default::default(),
+            dmx_bit_depth: Default::default(),
+            led_dither: false,
+            power_limit: Default::default(),
+            strobe_limit: Default::default(),
             madmapper_project_path: None,
             preset_lerp_secs: Default::default(),
             master_speed: default::master_speed(),
@@ -480,6 +725,268 @@ impl Default for LedOutputFps {
     }
 }
 
+impl Default for ShaderPreviewRate {
+    fn default() -> Self {
+        Self::Fps30
+    }
+}
+
+impl Default for ShaderPreviewResolution {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl Default for ChannelOrder {
+    fn default() -> Self {
+        Self::Rgb
+    }
+}
+
+impl ChannelOrder {
+    pub const ALL: [Self; 11] = [
+        Self::Rgb,
+        Self::Rbg,
+        Self::Grb,
+        Self::Gbr,
+        Self::Brg,
+        Self::Bgr,
+        Self::Rgbw,
+        Self::Grbw,
+        Self::Bgrw,
+        Self::Wrgb,
+        Self::Rgba,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rgb => "RGB",
+            Self::Rbg => "RBG",
+            Self::Grb => "GRB",
+            Self::Gbr => "GBR",
+            Self::Brg => "BRG",
+            Self::Bgr => "BGR",
+            Self::Rgbw => "RGBW",
+            Self::Grbw => "GRBW",
+            Self::Bgrw => "BGRW",
+            Self::Wrgb => "WRGB",
+            Self::Rgba => "RGBA (Amber)",
+        }
+    }
+
+    /// The channels of a pixel in the order they are addressed.
+    pub fn channels(self) -> &'static [PixelChannel] {
+        use PixelChannel::{Amber as A, Blue as B, Green as G, Red as R, White as W};
+        match self {
+            Self::Rgb => &[R, G, B],
+            Self::Rbg => &[R, B, G],
+            Self::Grb => &[G, R, B],
+            Self::Gbr => &[G, B, R],
+            Self::Brg => &[B, R, G],
+            Self::Bgr => &[B, G, R],
+            Self::Rgbw => &[R, G, B, W],
+            Self::Grbw => &[G, R, B, W],
+            Self::Bgrw => &[B, G, R, W],
+            Self::Wrgb => &[W, R, G, B],
+            Self::Rgba => &[R, G, B, A],
+        }
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|order| *order == self)
+            .expect("ChannelOrder variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
+impl ChannelLayouts {
+    /// The channel order for the named fixture, falling back to the default.
+    pub fn for_fixture(&self, fixture_name: &str) -> ChannelOrder {
+        self.fixtures
+            .get(fixture_name)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+impl PixelDestinations {
+    /// The address for the named fixture, falling back to the default.
+    pub fn for_fixture(&self, fixture_name: &str) -> &str {
+        self.fixtures
+            .get(fixture_name)
+            .map(String::as_str)
+            .unwrap_or(&self.default)
+    }
+}
+
+impl Default for PowerLimit {
+    fn default() -> Self {
+        PowerLimit {
+            enabled: false,
+            milliamps_per_channel: default::power_limit::milliamps_per_channel(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl Default for StrobeLimit {
+    fn default() -> Self {
+        StrobeLimit {
+            enabled: false,
+            max_flash_hz: default::strobe_limit::max_flash_hz(),
+            area_fraction: default::strobe_limit::area_fraction(),
+        }
+    }
+}
+
+impl Default for DmxBitDepth {
+    fn default() -> Self {
+        Self::Eight
+    }
+}
+
+impl DmxBitDepth {
+    pub const ALL: [Self; 2] = [Self::Eight, Self::Sixteen];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Eight => "8-bit",
+            Self::Sixteen => "16-bit",
+        }
+    }
+
+    /// The number of DMX slots occupied by each colour channel.
+    pub fn slots_per_channel(self) -> usize {
+        match self {
+            Self::Eight => 1,
+            Self::Sixteen => 2,
+        }
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|depth| *depth == self)
+            .expect("DmxBitDepth variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
+impl Default for CalibrationProfile {
+    fn default() -> Self {
+        CalibrationProfile {
+            gamma: default::calibration::gamma(),
+            lut: Vec::new(),
+            white_balance: default::calibration::white_balance(),
+            max_level: default::calibration::max_level(),
+        }
+    }
+}
+
+impl Calibration {
+    /// The profile assigned to the named fixture, if any.
+    pub fn for_fixture(&self, fixture_name: &str) -> Option<&CalibrationProfile> {
+        self.fixtures
+            .get(fixture_name)
+            .and_then(|name| self.profiles.get(name))
+    }
+
+    /// The profile assigned to the given manual layout row, if any.
+    pub fn for_row(&self, row: usize) -> Option<&CalibrationProfile> {
+        self.rows.get(&row).and_then(|name| self.profiles.get(name))
+    }
+
+    /// Add a new default profile with a unique name, returning the name.
+    pub fn add_profile(&mut self) -> String {
+        let name = (1..)
+            .map(|n| format!("Profile {}", n))
+            .find(|name| !self.profiles.contains_key(name))
+            .expect("ran out of profile names");
+        self.profiles
+            .insert(name.clone(), CalibrationProfile::default());
+        name
+    }
+
+    /// Remove the named profile along with any assignments to it.
+    pub fn remove_profile(&mut self, name: &str) {
+        self.profiles.remove(name);
+        self.fixtures.retain(|_, profile| profile != name);
+        self.rows.retain(|_, profile| profile != name);
+    }
+}
+
+impl Default for MergeMode {
+    fn default() -> Self {
+        Self::Htp
+    }
+}
+
+impl MergeMode {
+    pub const ALL: [Self; 3] = [Self::Htp, Self::Ltp, Self::Priority];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Htp => "HTP",
+            Self::Ltp => "LTP",
+            Self::Priority => "Priority",
+        }
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|mode| *mode == self)
+            .expect("MergeMode variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
+impl Default for DmxProtocol {
+    fn default() -> Self {
+        Self::Sacn
+    }
+}
+
+impl DmxProtocol {
+    pub const ALL: [Self; 4] = [Self::Sacn, Self::ArtNet, Self::Opc, Self::Ddp];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sacn => "sACN (E1.31)",
+            Self::ArtNet => "Art-Net",
+            Self::Opc => "OPC",
+            Self::Ddp => "DDP",
+        }
+    }
+
+    /// Whether the protocol sends whole pixel frames per destination rather than DMX universes.
+    pub fn sends_pixel_frames(self) -> bool {
+        matches!(self, Self::Opc | Self::Ddp)
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|protocol| *protocol == self)
+            .expect("DmxProtocol variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
 impl LedOutputFps {
     pub const ALL: [Self; 7] = [
         Self::Free,
@@ -527,6 +1034,87 @@ impl LedOutputFps {
     }
 }
 
+impl ShaderPreviews {
+    /// Whether the previews are rendered and shown at all.
+    pub fn is_on(&self) -> bool {
+        self.rate != ShaderPreviewRate::Off
+    }
+}
+
+impl ShaderPreviewRate {
+    pub const ALL: [Self; 5] = [
+        Self::Off,
+        Self::Fps5,
+        Self::Fps15,
+        Self::Fps30,
+        Self::EveryFrame,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Fps5 => "5 FPS",
+            Self::Fps15 => "15 FPS",
+            Self::Fps30 => "30 FPS",
+            Self::EveryFrame => "Every Frame",
+        }
+    }
+
+    /// The minimum interval between renders, or `None` if the previews are off.
+    pub fn interval(self) -> Option<Duration> {
+        match self {
+            Self::Off => None,
+            Self::Fps5 => Some(Duration::from_secs_f32(1.0 / 5.0)),
+            Self::Fps15 => Some(Duration::from_secs_f32(1.0 / 15.0)),
+            Self::Fps30 => Some(Duration::from_secs_f32(1.0 / 30.0)),
+            Self::EveryFrame => Some(Duration::ZERO),
+        }
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|rate| *rate == self)
+            .expect("ShaderPreviewRate variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
+impl ShaderPreviewResolution {
+    pub const ALL: [Self; 3] = [Self::Full, Self::Half, Self::Quarter];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Full => "Full",
+            Self::Half => "Half",
+            Self::Quarter => "Quarter",
+        }
+    }
+
+    /// Only every `divisor`th LED along each row and column is rendered.
+    pub fn divisor(self) -> usize {
+        match self {
+            Self::Full => 1,
+            Self::Half => 2,
+            Self::Quarter => 4,
+        }
+    }
+
+    pub fn to_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|resolution| *resolution == self)
+            .expect("ShaderPreviewResolution variant missing from ALL")
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+}
+
 impl Default for LedLayout {
     fn default() -> Self {
         let mut led_layout = LedLayout {
@@ -667,112 +1255,6 @@ impl StoredPreset {
     }
 }
 
-impl SparseShaderParams {
-    fn from_runtime(shader: Shader, params: &ShaderParams) -> Self {
-        let mut sparse = SparseShaderParams::default();
-        match shader {
-            Shader::SolidHsvColour => sparse.solid_hsv_colour = Some(params.solid_hsv_colour),
-            Shader::SolidRgbColour => sparse.solid_rgb_colour = Some(params.solid_rgb_colour),
-            Shader::ColourPalettes => sparse.colour_palettes = Some(params.colour_palettes),
-            Shader::AcidGradient => sparse.acid_gradient = Some(params.acid_gradient),
-            Shader::BlinkyCircles => sparse.blinky_circles = Some(params.blinky_circles),
-            Shader::BwGradient => sparse.bw_gradient = Some(params.bw_gradient),
-            Shader::ColourGrid => sparse.colour_grid = Some(params.colour_grid),
-            Shader::EscherTilings => sparse.escher_tilings = Some(params.escher_tilings),
-            Shader::GilmoreAcid => sparse.gilmore_acid = Some(params.gilmore_acid),
-            Shader::GradientBars => sparse.gradient_bars = Some(params.gradient_bars),
-            Shader::JustRelax => sparse.just_relax = Some(params.just_relax),
-            Shader::LifeLedWall => sparse.life_led_wall = Some(params.life_led_wall),
-            Shader::LightPatternGenerator => {
-                sparse.light_pattern_generator = Some(params.light_pattern_generator);
-            }
-            Shader::LineGradient => sparse.line_gradient = Some(params.line_gradient),
-            Shader::Metafall => sparse.metafall = Some(params.metafall),
-            Shader::ParticleZoom => sparse.particle_zoom = Some(params.particle_zoom),
-            Shader::RadialLines => sparse.radial_lines = Some(params.radial_lines),
-            Shader::SatisSpiraling => sparse.satis_spiraling = Some(params.satis_spiraling),
-            Shader::SpiralIntersect => sparse.spiral_intersect = Some(params.spiral_intersect),
-            Shader::SquareTunnel => sparse.square_tunnel = Some(params.square_tunnel),
-            Shader::ThePulse => sparse.the_pulse = Some(params.the_pulse),
-            Shader::TunnelProjection => sparse.tunnel_projection = Some(params.tunnel_projection),
-            Shader::VertColourGradient => {
-                sparse.vert_colour_gradient = Some(params.vert_colour_gradient);
-            }
-            Shader::MitchWash => sparse.mitch_wash = Some(params.mitch_wash),
-            Shader::ShapeEnvelopes => sparse.shape_envelopes = Some(params.shape_envelopes),
-            Shader::RowTest => sparse.row_test = Some(params.row_test),
-            Shader::BarTest => sparse.bar_test = Some(params.bar_test),
-            Shader::TwoDTiles => sparse.two_d_tiles = Some(params.two_d_tiles),
-            Shader::RadialKeta => sparse.radial_keta = Some(params.radial_keta),
-            Shader::ImitationRiley => sparse.imitation_riley = Some(params.imitation_riley),
-            Shader::HoopLoop => sparse.hoop_loop = Some(params.hoop_loop),
-        }
-        sparse
-    }
-
-    fn into_runtime(self, shader: Shader) -> ShaderParams {
-        let mut params = ShaderParams::default();
-        match shader {
-            Shader::SolidHsvColour => {
-                params.solid_hsv_colour = self.solid_hsv_colour.unwrap_or_default()
-            }
-            Shader::SolidRgbColour => {
-                params.solid_rgb_colour = self.solid_rgb_colour.unwrap_or_default()
-            }
-            Shader::ColourPalettes => {
-                params.colour_palettes = self.colour_palettes.unwrap_or_default()
-            }
-            Shader::AcidGradient => params.acid_gradient = self.acid_gradient.unwrap_or_default(),
-            Shader::BlinkyCircles => {
-                params.blinky_circles = self.blinky_circles.unwrap_or_default()
-            }
-            Shader::BwGradient => params.bw_gradient = self.bw_gradient.unwrap_or_default(),
-            Shader::ColourGrid => params.colour_grid = self.colour_grid.unwrap_or_default(),
-            Shader::EscherTilings => {
-                params.escher_tilings = self.escher_tilings.unwrap_or_default()
-            }
-            Shader::GilmoreAcid => params.gilmore_acid = self.gilmore_acid.unwrap_or_default(),
-            Shader::GradientBars => params.gradient_bars = self.gradient_bars.unwrap_or_default(),
-            Shader::JustRelax => params.just_relax = self.just_relax.unwrap_or_default(),
-            Shader::LifeLedWall => params.life_led_wall = self.life_led_wall.unwrap_or_default(),
-            Shader::LightPatternGenerator => {
-                params.light_pattern_generator = self.light_pattern_generator.unwrap_or_default()
-            }
-            Shader::LineGradient => params.line_gradient = self.line_gradient.unwrap_or_default(),
-            Shader::Metafall => params.metafall = self.metafall.unwrap_or_default(),
-            Shader::ParticleZoom => params.particle_zoom = self.particle_zoom.unwrap_or_default(),
-            Shader::RadialLines => params.radial_lines = self.radial_lines.unwrap_or_default(),
-            Shader::SatisSpiraling => {
-                params.satis_spiraling = self.satis_spiraling.unwrap_or_default()
-            }
-            Shader::SpiralIntersect => {
-                params.spiral_intersect = self.spiral_intersect.unwrap_or_default()
-            }
-            Shader::SquareTunnel => params.square_tunnel = self.square_tunnel.unwrap_or_default(),
-            Shader::ThePulse => params.the_pulse = self.the_pulse.unwrap_or_default(),
-            Shader::TunnelProjection => {
-                params.tunnel_projection = self.tunnel_projection.unwrap_or_default()
-            }
-            Shader::VertColourGradient => {
-                params.vert_colour_gradient = self.vert_colour_gradient.unwrap_or_default()
-            }
-            Shader::MitchWash => params.mitch_wash = self.mitch_wash.unwrap_or_default(),
-            Shader::ShapeEnvelopes => {
-                params.shape_envelopes = self.shape_envelopes.unwrap_or_default()
-            }
-            Shader::RowTest => params.row_test = self.row_test.unwrap_or_default(),
-            Shader::BarTest => params.bar_test = self.bar_test.unwrap_or_default(),
-            Shader::TwoDTiles => params.two_d_tiles = self.two_d_tiles.unwrap_or_default(),
-            Shader::RadialKeta => params.radial_keta = self.radial_keta.unwrap_or_default(),
-            Shader::ImitationRiley => {
-                params.imitation_riley = self.imitation_riley.unwrap_or_default()
-            }
-            Shader::HoopLoop => params.hoop_loop = self.hoop_loop.unwrap_or_default(),
-        }
-        params
-    }
-}
-
 pub fn save_presets(assets: &Path, global_config: &mut GlobalConfig, presets: &Presets) {
     try_save_presets(assets, global_config, presets).expect("failed to save presets");
 }
@@ -927,6 +1409,19 @@ pub mod default {
         String::new()
     }
 
+    pub fn artnet_destination_ip() -> String {
+        String::new()
+    }
+
+    pub fn artnet_universe_offset() -> u16 {
+        1
+    }
+
+    /// The sync universe suggested when universe sync is first enabled.
+    pub fn sacn_sync_universe() -> u16 {
+        63999
+    }
+
     pub fn master_speed() -> f32 {
         1.0
     }
@@ -953,6 +1448,42 @@ pub mod default {
         }
     }
 
+    pub mod power_limit {
+        /// Typical for WS2812-style pixels.
+        pub fn milliamps_per_channel() -> f32 {
+            20.0
+        }
+
+        pub fn budget_amps() -> f32 {
+            10.0
+        }
+    }
+
+    pub mod strobe_limit {
+        /// The general flash threshold from common photosensitivity guidelines.
+        pub fn max_flash_hz() -> f32 {
+            3.0
+        }
+
+        pub fn area_fraction() -> f32 {
+            0.25
+        }
+    }
+
+    pub mod calibration {
+        pub fn gamma() -> f32 {
+            1.0
+        }
+
+        pub fn white_balance() -> [f32; 3] {
+            [1.0; 3]
+        }
+
+        pub fn max_level() -> f32 {
+            1.0
+        }
+    }
+
     pub mod presets {
         pub fn selected_preset_name() -> String {
             "Empty".to_string()
@@ -1006,6 +1537,29 @@ pub fn parse_sacn_interface_ip(value: &str) -> Result<Option<Ipv4Addr>, AddrPars
     }
 }
 
+/// Parse a list of unicast destination IPs, skipping blank entries.
+pub fn parse_unicast_destinations(values: &[String]) -> Result<Vec<Ipv4Addr>, AddrParseError> {
+    values
+        .iter()
+        .filter_map(|value| parse_sacn_interface_ip(value).transpose())
+        .collect()
+}
+
+/// Parse an `ip` or `ip:port` pixel destination, using `default_port` when none is given.
+pub fn parse_pixel_destination(
+    value: &str,
+    default_port: u16,
+) -> Result<Option<SocketAddr>, AddrParseError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Ok(None)
+    } else if let Ok(ip) = value.parse::<Ipv4Addr>() {
+        Ok(Some(SocketAddr::new(ip.into(), default_port)))
+    } else {
+        value.parse::<SocketAddr>().map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1073,8 +1627,114 @@ mod tests {
         assert_eq!(loaded.shader_params_left.the_pulse.speed, 0.42);
         assert_eq!(
             loaded.shader_params_left.acid_gradient,
-            AcidGradient::default()
+            shader_shared::AcidGradient::default()
         );
         assert_eq!(loaded.shader_params_right.acid_gradient.offset, 0.33);
     }
+
+    #[test]
+    fn every_registered_shader_round_trips_and_renders() {
+        use nannou::prelude::*;
+        use shader_shared::{CachedLedShaderInput, ParamKindMut, Uniforms, ALL_SHADERS};
+
+        for &shader in ALL_SHADERS {
+            assert_eq!(Shader::from_index(shader.to_index()), Some(shader));
+
+            // Move every param away from its default through the same reflection the GUI uses.
+            let mut params = ShaderParams::default();
+            let p = params.params_mut(shader);
+            for ix in 0..p.param_count() {
+                match p.param_mut(ix).kind {
+                    ParamKindMut::F32 { value, max } => *value = max * 0.37,
+                    ParamKindMut::F32Range { value, min, max } => *value = min + (max - min) * 0.37,
+                    ParamKindMut::Bool(value) => *value = !*value,
+                    ParamKindMut::Select { value, labels } => *value = (*value + 1) % labels.len(),
+                    ParamKindMut::Usize { value, max } => *value = (*value + 1) % (max + 1),
+                }
+            }
+            assert_ne!(params, ShaderParams::default(), "{}", shader.name());
+
+            let preset = Preset {
+                shader_left: shader,
+                shader_params_left: params,
+                ..Preset::default()
+            };
+            let json = serde_json::to_string(&StoredPreset::from_runtime(&preset)).unwrap();
+            let stored: StoredPreset = serde_json::from_str(&json).unwrap();
+            let loaded = stored.into_runtime(preset.id.clone());
+            assert_eq!(loaded.shader_params_left, params, "{}", shader.name());
+
+            let mut uniforms = Uniforms::solo(shader, 1.0);
+            uniforms.mix.right = shader;
+            uniforms.mix.colourise = shader;
+            uniforms.mix.xfade_right = 1.0;
+            uniforms.mix.params_left = params;
+            uniforms.mix.params_right = params;
+            uniforms.mix.params_colourise = params;
+            let inputs = CachedLedShaderInput::grid(4, 3);
+            let last_colors = vec![lin_srgb(0.0, 0.0, 0.0); inputs.len()];
+            let mut output = last_colors.clone();
+            ::shader::shader_batch(&inputs, &last_colors, &uniforms, &mut output)
+                .unwrap_or_else(|err| panic!("{} panicked: {}", shader.name(), err));
+            for col in output {
+                assert!(
+                    col.red.is_finite() && col.green.is_finite() && col.blue.is_finite(),
+                    "{} rendered {:?}",
+                    shader.name(),
+                    col
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn removing_calibration_profile_clears_assignments() {
+        let mut calibration = Calibration::default();
+        let first = calibration.add_profile();
+        let second = calibration.add_profile();
+        assert_eq!(first, "Profile 1");
+        assert_eq!(second, "Profile 2");
+
+        calibration
+            .fixtures
+            .insert("Fixture-Line-1".to_string(), first.clone());
+        calibration.rows.insert(0, first.clone());
+        calibration.rows.insert(1, second.clone());
+        assert!(calibration.for_fixture("Fixture-Line-1").is_some());
+
+        calibration.remove_profile(&first);
+
+        assert!(calibration.for_fixture("Fixture-Line-1").is_none());
+        assert!(calibration.for_row(0).is_none());
+        assert!(calibration.for_row(1).is_some());
+        assert_eq!(calibration.add_profile(), "Profile 1");
+    }
+
+    #[test]
+    fn parse_pixel_destination_applies_default_port() {
+        assert_eq!(parse_pixel_destination("  ", 7890), Ok(None));
+        assert_eq!(
+            parse_pixel_destination("10.0.0.5", 7890),
+            Ok(Some(SocketAddr::from(([10, 0, 0, 5], 7890))))
+        );
+        assert_eq!(
+            parse_pixel_destination("10.0.0.5:7891", 7890),
+            Ok(Some(SocketAddr::from(([10, 0, 0, 5], 7891))))
+        );
+        assert!(parse_pixel_destination("controller", 7890).is_err());
+    }
+
+    #[test]
+    fn parse_unicast_destinations_skips_blanks() {
+        let values = vec![
+            "10.0.0.5".to_string(),
+            " ".to_string(),
+            " 10.0.0.6".to_string(),
+        ];
+        assert_eq!(
+            parse_unicast_destinations(&values),
+            Ok(vec![Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(10, 0, 0, 6)])
+        );
+        assert!(parse_unicast_destinations(&["10.0.0".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,126 @@
+//! A minimal Distributed Display Protocol (DDP) sender.
+//!
+//! A frame of pixel data is split across as many packets as needed, each carrying its byte offset
+//! into the frame. The last packet sets the push flag so the controller displays the whole frame
+//! at once.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+
+/// The UDP port on which DDP controllers listen.
+pub const DDP_PORT: u16 = 4048;
+
+const HEADER_LEN: usize = 10;
+/// The most pixel data carried by a single packet, a whole number of RGB pixels.
+const MAX_DATA_PER_PACKET: usize = 1440;
+
+const FLAG_VERSION_1: u8 = 0x40;
+const FLAG_PUSH: u8 = 0x01;
+/// RGB, 8 bits per channel.
+const DATA_TYPE_RGB8: u8 = 0x0B;
+/// The controller's default output device.
+const DESTINATION_DISPLAY: u8 = 1;
+
+pub struct DdpSender {
+    socket: UdpSocket,
+    sequences: HashMap<SocketAddr, u8>,
+}
+
+impl DdpSender {
+    /// Bind a sender on the given interface.
+    pub fn new(interface_ip: Option<Ipv4Addr>) -> io::Result<Self> {
+        let bind_ip = interface_ip.unwrap_or(Ipv4Addr::UNSPECIFIED);
+        let socket = UdpSocket::bind(SocketAddr::new(bind_ip.into(), 0))?;
+        Ok(Self {
+            socket,
+            sequences: HashMap::new(),
+        })
+    }
+
+    /// Send a frame of 8-bit RGB pixel data to the given controller.
+    pub fn send_pixels(&mut self, destination: SocketAddr, rgb: &[u8]) -> io::Result<()> {
+        let sequence = self.sequences.entry(destination).or_insert(1);
+        for packet in build_ddp_packets(*sequence, rgb) {
+            self.socket.send_to(&packet, destination)?;
+        }
+        // The sequence number is 4 bits, where 0 means unused.
+        *sequence = *sequence % 15 + 1;
+        Ok(())
+    }
+}
+
+/// Split a frame into DDP packets, setting the push flag on the last.
+pub fn build_ddp_packets(sequence: u8, rgb: &[u8]) -> Vec<Vec<u8>> {
+    let chunk_count = rgb.len().div_ceil(MAX_DATA_PER_PACKET).max(1);
+    (0..chunk_count)
+        .map(|ix| {
+            let offset = ix * MAX_DATA_PER_PACKET;
+            let end = (offset + MAX_DATA_PER_PACKET).min(rgb.len());
+            let push = ix + 1 == chunk_count;
+            build_ddp_packet(sequence, offset as u32, &rgb[offset..end], push)
+        })
+        .collect()
+}
+
+/// Pack a single DDP data packet.
+pub fn build_ddp_packet(sequence: u8, offset: u32, data: &[u8], push: bool) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_LEN + data.len());
+    packet.push(if push {
+        FLAG_VERSION_1 | FLAG_PUSH
+    } else {
+        FLAG_VERSION_1
+    });
+    packet.push(sequence & 0x0F);
+    packet.push(DATA_TYPE_RGB8);
+    packet.push(DESTINATION_DISPLAY);
+    packet.extend_from_slice(&offset.to_be_bytes());
+    packet.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_ddp_packet, DdpSender, HEADER_LEN, MAX_DATA_PER_PACKET};
+    use std::net::{Ipv4Addr, UdpSocket};
+    use std::time::Duration;
+
+    #[test]
+    fn build_ddp_packet_packs_header() {
+        let packet = build_ddp_packet(3, 0x0102_0304, &[7, 8, 9], true);
+        assert_eq!(packet, vec![0x41, 3, 0x0B, 1, 1, 2, 3, 4, 0, 3, 7, 8, 9]);
+    }
+
+    #[test]
+    fn loopback_listener_receives_split_frame() {
+        let listener = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut sender = DdpSender::new(Some(Ipv4Addr::LOCALHOST)).unwrap();
+
+        let frame: Vec<u8> = (0..MAX_DATA_PER_PACKET + 6).map(|i| i as u8).collect();
+        sender.send_pixels(addr, &frame).unwrap();
+
+        let mut buffer = [0u8; 2048];
+        let len = listener.recv(&mut buffer).unwrap();
+        let first = &buffer[..len];
+        assert_eq!(first.len(), HEADER_LEN + MAX_DATA_PER_PACKET);
+        // Not pushed until the last packet.
+        assert_eq!(first[0], 0x40);
+        assert_eq!(first[1], 1);
+        assert_eq!(&first[4..8], &[0, 0, 0, 0]);
+        assert_eq!(&first[8..10], &(MAX_DATA_PER_PACKET as u16).to_be_bytes());
+        assert_eq!(&first[HEADER_LEN..], &frame[..MAX_DATA_PER_PACKET]);
+
+        let len = listener.recv(&mut buffer).unwrap();
+        let second = &buffer[..len];
+        assert_eq!(second[0], 0x41);
+        assert_eq!(second[1], 1);
+        assert_eq!(&second[4..8], &(MAX_DATA_PER_PACKET as u32).to_be_bytes());
+        assert_eq!(&second[8..10], &[0, 6]);
+        assert_eq!(&second[HEADER_LEN..], &frame[MAX_DATA_PER_PACKET..]);
+    }
+}
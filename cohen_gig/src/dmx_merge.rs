@@ -0,0 +1,140 @@
+//! Merging of incoming DMX, e.g. from a lighting console, with our own output.
+//!
+//! All payloads are start-code-prefixed. The start code itself is never merged.
+
+use crate::conf::MergeMode;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Tracks the per-universe state needed for LTP merging between frames.
+#[derive(Debug, Default)]
+pub struct DmxMerger {
+    ltp: HashMap<u16, LtpState>,
+}
+
+#[derive(Debug, Default)]
+struct LtpState {
+    last_local: Vec<u8>,
+    last_remote: Vec<u8>,
+    merged: Vec<u8>,
+}
+
+impl DmxMerger {
+    /// Merge the `remote` payload into our `local` payload for the same universe.
+    ///
+    /// `local` is extended with zeroes if `remote` carries more slots.
+    pub fn merge(
+        &mut self,
+        mode: MergeMode,
+        universe: u16,
+        local: &mut Vec<u8>,
+        local_priority: u8,
+        remote: &[u8],
+        remote_priority: u8,
+    ) {
+        match mode {
+            MergeMode::Htp => merge_htp(local, remote),
+            MergeMode::Ltp => self.ltp.entry(universe).or_default().merge(local, remote),
+            MergeMode::Priority => match remote_priority.cmp(&local_priority) {
+                Ordering::Greater => {
+                    local.clear();
+                    local.extend_from_slice(remote);
+                }
+                Ordering::Equal => merge_htp(local, remote),
+                Ordering::Less => (),
+            },
+        }
+    }
+
+    /// Forget the LTP state of any universe for which `keep` returns `false`.
+    pub fn retain(&mut self, mut keep: impl FnMut(u16) -> bool) {
+        self.ltp.retain(|&universe, _| keep(universe));
+    }
+}
+
+impl LtpState {
+    fn merge(&mut self, local: &mut Vec<u8>, remote: &[u8]) {
+        let len = local.len().max(remote.len());
+        local.resize(len, 0);
+        let mut remote = remote.to_vec();
+        remote.resize(len, 0);
+
+        if self.merged.len() == len {
+            for ix in 1..len {
+                if remote[ix] != self.last_remote[ix] {
+                    self.merged[ix] = remote[ix];
+                } else if local[ix] != self.last_local[ix] {
+                    self.merged[ix] = local[ix];
+                }
+            }
+        } else {
+            // Nothing has changed yet, so our output holds until the remote moves a slot.
+            self.merged.clone_from(local);
+        }
+
+        self.last_local.clone_from(local);
+        self.last_remote = remote;
+        local.copy_from_slice(&self.merged);
+    }
+}
+
+/// Take the higher level of each slot.
+pub fn merge_htp(local: &mut Vec<u8>, remote: &[u8]) {
+    if local.len() < remote.len() {
+        local.resize(remote.len(), 0);
+    }
+    for (local, remote) in local.iter_mut().zip(remote).skip(1) {
+        *local = (*local).max(*remote);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_htp, DmxMerger};
+    use crate::conf::MergeMode;
+
+    #[test]
+    fn htp_takes_the_higher_level_and_extends() {
+        let mut local = vec![0, 10, 200, 30];
+        merge_htp(&mut local, &[0, 50, 100, 30, 7]);
+        assert_eq!(local, vec![0, 50, 200, 30, 7]);
+    }
+
+    #[test]
+    fn ltp_follows_the_latest_change() {
+        let mut merger = DmxMerger::default();
+        let mut merge = |local: &[u8], remote: &[u8]| {
+            let mut local = local.to_vec();
+            merger.merge(MergeMode::Ltp, 1, &mut local, 100, remote, 100);
+            local
+        };
+
+        // Ours holds until the remote changes.
+        assert_eq!(merge(&[0, 10, 10], &[0, 90, 90]), vec![0, 10, 10]);
+        // The remote moves the first slot.
+        assert_eq!(merge(&[0, 10, 10], &[0, 80, 90]), vec![0, 80, 10]);
+        // Neither changes, so the last winner holds.
+        assert_eq!(merge(&[0, 10, 10], &[0, 80, 90]), vec![0, 80, 10]);
+        // We move the first slot back.
+        assert_eq!(merge(&[0, 20, 10], &[0, 80, 90]), vec![0, 20, 10]);
+    }
+
+    #[test]
+    fn priority_merge_prefers_the_higher_priority() {
+        let mut merger = DmxMerger::default();
+        let remote = [0, 50, 50];
+
+        let mut local = vec![0, 10, 100];
+        merger.merge(MergeMode::Priority, 1, &mut local, 100, &remote, 150);
+        assert_eq!(local, vec![0, 50, 50]);
+
+        let mut local = vec![0, 10, 100];
+        merger.merge(MergeMode::Priority, 1, &mut local, 100, &remote, 50);
+        assert_eq!(local, vec![0, 10, 100]);
+
+        // Equal priorities fall back to HTP.
+        let mut local = vec![0, 10, 100];
+        merger.merge(MergeMode::Priority, 1, &mut local, 100, &remote, 100);
+        assert_eq!(local, vec![0, 50, 100]);
+    }
+}
@@ -0,0 +1,448 @@
+//! Recording and replay of the DMX payloads sent by the LED worker.
+//!
+//! Recordings are a compact little-endian binary stream: the 8 byte magic `CGDMXREC` and a
+//! `u8` format version, followed by one record per universe payload:
+//!
+//! - `u64` microseconds since the recording started,
+//! - `u16` universe,
+//! - `u16` payload length,
+//! - the payload itself, start code included.
+//!
+//! Consecutive payloads that share a timestamp make up a single frame.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const MAGIC: &[u8; 8] = b"CGDMXREC";
+const FORMAT_VERSION: u8 = 1;
+
+/// The file extension used for recordings.
+pub const FILE_EXTENSION: &str = "cgdmx";
+
+/// A single frame of universe payloads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedFrame {
+    /// Time since the start of the recording.
+    pub offset: Duration,
+    pub payloads: Vec<(u16, Vec<u8>)>,
+}
+
+/// What the LED worker should record to or replay from, as requested by the GUI.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaptureRequest {
+    pub record_path: Option<PathBuf>,
+    pub replay_path: Option<PathBuf>,
+    /// Restart the replay from the beginning once it reaches the end.
+    pub replay_looping: bool,
+}
+
+/// The state of recording and replay, reported back to the GUI.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaptureStatus {
+    pub recorded_frames: u64,
+    /// The index of the next frame to replay and the total frame count.
+    pub replay_progress: Option<(usize, usize)>,
+    /// The frame count of a replay that played to its end, until another replay is requested.
+    pub finished_replay: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// Writes frames to a recording as they are sent.
+pub struct DmxRecorder<W: Write> {
+    writer: W,
+    started_at: Option<Instant>,
+    frames: u64,
+}
+
+/// Plays a recording back with its original timing.
+#[derive(Debug)]
+pub struct DmxReplay {
+    frames: Vec<RecordedFrame>,
+    looping: bool,
+    started_at: Option<Instant>,
+    next_frame: usize,
+}
+
+/// The recorder and replay owned by the LED worker.
+#[derive(Default)]
+pub struct CaptureRuntime {
+    request: CaptureRequest,
+    recorder: Option<DmxRecorder<BufWriter<File>>>,
+    replay: Option<DmxReplay>,
+    finished_replay: Option<usize>,
+    error: Option<String>,
+}
+
+impl DmxRecorder<BufWriter<File>> {
+    /// Create a recording at the given path, creating parent directories as necessary.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> DmxRecorder<W> {
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        Ok(Self {
+            writer,
+            started_at: None,
+            frames: 0,
+        })
+    }
+
+    /// Append a frame. The first frame recorded marks the start of the recording.
+    pub fn record_frame(&mut self, now: Instant, payloads: &[(u16, Vec<u8>)]) -> io::Result<()> {
+        let started_at = *self.started_at.get_or_insert(now);
+        let offset = now.saturating_duration_since(started_at);
+        write_frame(&mut self.writer, offset, payloads)?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl DmxReplay {
+    pub fn new(frames: Vec<RecordedFrame>, looping: bool) -> Self {
+        Self {
+            frames,
+            looping,
+            started_at: None,
+            next_frame: 0,
+        }
+    }
+
+    pub fn load(path: &Path, looping: bool) -> io::Result<Self> {
+        let frames = read_recording(BufReader::new(File::open(path)?))?;
+        Ok(Self::new(frames, looping))
+    }
+
+    /// The most recent frame that has become due since the last call, if any.
+    ///
+    /// Frames that were missed (e.g. due to a slow worker) are skipped rather than sent late.
+    pub fn next_due(&mut self, now: Instant) -> Option<&RecordedFrame> {
+        if self.next_frame >= self.frames.len() {
+            if !self.looping || self.frames.is_empty() {
+                return None;
+            }
+            self.next_frame = 0;
+            self.started_at = None;
+        }
+        let started_at = *self.started_at.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(started_at);
+        let mut due = None;
+        while let Some(frame) = self.frames.get(self.next_frame) {
+            if frame.offset > elapsed {
+                break;
+            }
+            due = Some(self.next_frame);
+            self.next_frame += 1;
+        }
+        due.map(|ix| &self.frames[ix])
+    }
+
+    /// The index of the next frame and the total frame count.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next_frame, self.frames.len())
+    }
+
+    /// Whether every frame has been sent and the replay won't restart.
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.frames.len() && (!self.looping || self.frames.is_empty())
+    }
+}
+
+impl CaptureRuntime {
+    /// Open or close the recorder and replay to match the latest request.
+    pub fn sync(&mut self, request: &CaptureRequest) {
+        if request.record_path != self.request.record_path {
+            self.stop_recording();
+            if let Some(path) = &request.record_path {
+                match DmxRecorder::create(path) {
+                    Ok(recorder) => self.recorder = Some(recorder),
+                    Err(err) => {
+                        self.error = Some(format!(
+                            "Couldn't create recording {}: {}",
+                            path.display(),
+                            err
+                        ))
+                    }
+                }
+            }
+        }
+
+        if request.replay_path != self.request.replay_path {
+            self.replay = None;
+            self.finished_replay = None;
+            if let Some(path) = &request.replay_path {
+                match DmxReplay::load(path, request.replay_looping) {
+                    Ok(loaded) => {
+                        self.replay = Some(loaded);
+                        self.error = None;
+                    }
+                    Err(err) => {
+                        self.error = Some(format!(
+                            "Couldn't load recording {}: {}",
+                            path.display(),
+                            err
+                        ))
+                    }
+                }
+            }
+        } else if let Some(replay) = self.replay.as_mut() {
+            replay.looping = request.replay_looping;
+        }
+
+        self.request.clone_from(request);
+    }
+
+    /// Whether a replay has frames left to send, in which case it replaces the rendered output.
+    pub fn is_replaying(&self) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|replay| !replay.is_finished())
+    }
+
+    /// Drop the replay in progress, reporting why it can't be sent.
+    ///
+    /// The replay stays stopped until another recording is chosen.
+    pub fn refuse_replay(&mut self, reason: &str) {
+        if self.replay.take().is_some() {
+            self.error = Some(reason.to_string());
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// The replay in progress. A replay that has finished is dropped so output returns to the
+    /// rendered frames.
+    pub fn replay_mut(&mut self) -> Option<&mut DmxReplay> {
+        if let Some(replay) = self.replay.take_if(|replay| replay.is_finished()) {
+            self.finished_replay = Some(replay.frames.len());
+        }
+        self.replay.as_mut()
+    }
+
+    /// Record a sent frame if a recording is in progress.
+    pub fn record(&mut self, now: Instant, payloads: &[(u16, Vec<u8>)]) {
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+        if let Err(err) = recorder.record_frame(now, payloads) {
+            self.error = Some(format!("Recording stopped: {}", err));
+            self.recorder = None;
+        }
+    }
+
+    pub fn status(&self) -> CaptureStatus {
+        CaptureStatus {
+            recorded_frames: self.recorder.as_ref().map_or(0, DmxRecorder::frames),
+            replay_progress: self.replay.as_ref().map(DmxReplay::progress),
+            finished_replay: self.finished_replay,
+            error: self.error.clone(),
+        }
+    }
+
+    fn stop_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            if let Err(err) = recorder.finish() {
+                self.error = Some(format!("Couldn't finish recording: {}", err));
+            }
+        }
+    }
+}
+
+impl Drop for CaptureRuntime {
+    fn drop(&mut self) {
+        self.stop_recording();
+    }
+}
+
+/// Write a single frame of payloads.
+pub fn write_frame<W: Write>(
+    writer: &mut W,
+    offset: Duration,
+    payloads: &[(u16, Vec<u8>)],
+) -> io::Result<()> {
+    let micros = u64::try_from(offset.as_micros()).unwrap_or(u64::MAX);
+    for (universe, payload) in payloads {
+        let len = u16::try_from(payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "payload too long"))?;
+        writer.write_all(&micros.to_le_bytes())?;
+        writer.write_all(&universe.to_le_bytes())?;
+        writer.write_all(&len.to_le_bytes())?;
+        writer.write_all(payload)?;
+    }
+    Ok(())
+}
+
+/// Read a full recording, grouping payloads into frames.
+pub fn read_recording<R: Read>(mut reader: R) -> io::Result<Vec<RecordedFrame>> {
+    let mut header = [0u8; 9];
+    reader.read_exact(&mut header)?;
+    if &header[..8] != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a DMX recording",
+        ));
+    }
+    if header[8] != FORMAT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported recording version {}", header[8]),
+        ));
+    }
+
+    let mut frames: Vec<RecordedFrame> = Vec::new();
+    let mut record_header = [0u8; 12];
+    loop {
+        match reader.read_exact(&mut record_header) {
+            Ok(()) => (),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err),
+        }
+        let micros = u64::from_le_bytes(record_header[0..8].try_into().unwrap());
+        let universe = u16::from_le_bytes([record_header[8], record_header[9]]);
+        let len = u16::from_le_bytes([record_header[10], record_header[11]]) as usize;
+        let mut payload = vec![0; len];
+        reader.read_exact(&mut payload)?;
+
+        let offset = Duration::from_micros(micros);
+        match frames.last_mut() {
+            Some(frame) if frame.offset == offset => frame.payloads.push((universe, payload)),
+            _ => frames.push(RecordedFrame {
+                offset,
+                payloads: vec![(universe, payload)],
+            }),
+        }
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_recording, CaptureRuntime, DmxRecorder, DmxReplay, RecordedFrame};
+    use std::time::{Duration, Instant};
+
+    fn frame(offset_ms: u64, payloads: Vec<(u16, Vec<u8>)>) -> RecordedFrame {
+        RecordedFrame {
+            offset: Duration::from_millis(offset_ms),
+            payloads,
+        }
+    }
+
+    #[test]
+    fn recording_round_trips_frames() {
+        let start = Instant::now();
+        let mut recorder = DmxRecorder::new(Vec::new()).unwrap();
+        recorder
+            .record_frame(start, &[(1, vec![0, 1, 2, 3]), (2, vec![0, 4])])
+            .unwrap();
+        recorder
+            .record_frame(start + Duration::from_millis(25), &[(1, vec![0, 9, 9, 9])])
+            .unwrap();
+        assert_eq!(recorder.frames(), 2);
+        let bytes = recorder.finish().unwrap();
+
+        let frames = read_recording(&bytes[..]).unwrap();
+        assert_eq!(
+            frames,
+            vec![
+                frame(0, vec![(1, vec![0, 1, 2, 3]), (2, vec![0, 4])]),
+                frame(25, vec![(1, vec![0, 9, 9, 9])]),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_recording_rejects_other_files() {
+        assert!(read_recording(&b"not a recording"[..]).is_err());
+    }
+
+    #[test]
+    fn replay_follows_recorded_timing() {
+        let frames = vec![
+            frame(0, vec![(1, vec![0, 1])]),
+            frame(20, vec![(1, vec![0, 2])]),
+            frame(40, vec![(1, vec![0, 3])]),
+        ];
+        let mut replay = DmxReplay::new(frames, false);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(replay.next_due(at(0)).unwrap().payloads[0].1, vec![0, 1]);
+        assert!(replay.next_due(at(10)).is_none());
+        // A late call skips straight to the newest due frame.
+        assert_eq!(replay.next_due(at(45)).unwrap().payloads[0].1, vec![0, 3]);
+        assert!(replay.next_due(at(100)).is_none());
+        assert_eq!(replay.progress(), (3, 3));
+    }
+
+    #[test]
+    fn looping_replay_restarts() {
+        let frames = vec![
+            frame(0, vec![(1, vec![0, 1])]),
+            frame(20, vec![(1, vec![0, 2])]),
+        ];
+        let mut replay = DmxReplay::new(frames, true);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert!(replay.next_due(at(0)).is_some());
+        assert_eq!(replay.next_due(at(20)).unwrap().payloads[0].1, vec![0, 2]);
+        assert_eq!(replay.next_due(at(30)).unwrap().payloads[0].1, vec![0, 1]);
+    }
+
+    #[test]
+    fn finished_replay_is_dropped_and_reported() {
+        let frames = vec![
+            frame(0, vec![(1, vec![0, 1])]),
+            frame(20, vec![(1, vec![0, 2])]),
+        ];
+        let mut capture = CaptureRuntime::default();
+        capture.replay = Some(DmxReplay::new(frames, false));
+        let start = Instant::now();
+
+        assert!(capture.is_replaying());
+        assert!(capture.replay_mut().unwrap().next_due(start).is_some());
+        assert!(capture.is_replaying());
+        let last = start + Duration::from_millis(20);
+        assert!(capture.replay_mut().unwrap().next_due(last).is_some());
+
+        // With every frame sent, rendering takes over again.
+        assert!(!capture.is_replaying());
+        assert!(capture.replay_mut().is_none());
+        let status = capture.status();
+        assert_eq!(status.replay_progress, None);
+        assert_eq!(status.finished_replay, Some(2));
+    }
+    #[test]
+    fn refused_replay_is_dropped_and_reported() {
+        let mut capture = CaptureRuntime::default();
+        capture.replay = Some(DmxReplay::new(vec![frame(0, vec![(1, vec![0, 1])])], true));
+
+        capture.refuse_replay("Can't replay over OPC");
+        assert!(!capture.is_replaying());
+        assert!(capture.replay_mut().is_none());
+        assert_eq!(
+            capture.status().error.as_deref(),
+            Some("Can't replay over OPC")
+        );
+    }
+}
@@ -7,6 +7,7 @@ use nannou_conrod::prelude::*;
 use nannou_conrod::Color;
 
 use shader_shared::{BlendMode, Shader, ShaderParams, ToneMapping};
+pub use shader_shared::{ParamKindMut, ParamMut, Params};
 use std::f64::consts::PI;
 use std::path::Path;
 
@@ -47,12 +48,19 @@ widget_ids! {
         live_tab_button,
         output_tab_button,
         dmx_button,
+        dmx_off_fade_dialer,
         save_config_button,
         midi_button,
         preview_window_button,
         output_fps_text,
         output_fps_ddl,
         output_fps_status_text,
+        shader_previews_text,
+        shader_preview_rate_ddl,
+        shader_preview_resolution_ddl,
+        render_passes_text,
+        dmx_protocol_text,
+        dmx_protocol_ddl,
         audio_device_ddl,
         audio_device_placeholder,
         audio_device_error_text,
@@ -60,6 +68,21 @@ widget_ids! {
         sacn_interface_ip_help_text,
         sacn_interface_ip_text_box,
         sacn_interface_ip_error_text,
+        mirror_interface_ip_text,
+        mirror_interface_ip_help_text,
+        mirror_interface_ip_text_box,
+        artnet_destination_ip_text,
+        artnet_destination_ip_help_text,
+        artnet_destination_ip_text_box,
+        artnet_universe_offset_help_text,
+        artnet_universe_offset_dialer,
+        artnet_sync_ip_help_text,
+        artnet_sync_ip_text_box,
+        pixel_destination_text,
+        pixel_destination_help_text,
+        pixel_destination_text_box,
+        sacn_sync_button,
+        sacn_sync_universe_dialer,
         shader_title_text,
         shader_state_text,
 
@@ -142,12 +165,67 @@ widget_ids! {
         sacn_output_universe_text,
         sacn_output_universe_ddl,
         sacn_output_universe_placeholder,
+        sacn_output_priority_dialer,
+        sacn_unicast_text,
+        sacn_unicast_text_box,
         sacn_output_grid_help_text,
         sacn_output_grid_bg,
         sacn_output_grid_cells[],
         sacn_output_grid_cell_values[],
         sacn_output_grid_summary_text,
         sacn_output_slot_preview_text,
+        fixture_output_title_text,
+        default_channel_order_ddl,
+        dmx_bit_depth_ddl,
+        led_dither_button,
+        power_status_text,
+        power_limit_title_text,
+        power_limit_button,
+        power_milliamps_dialer,
+        power_group_budget_dialers[],
+        power_add_group_button,
+        power_limit_help_text,
+        strobe_limit_title_text,
+        strobe_limit_button,
+        strobe_max_hz_dialer,
+        strobe_area_dialer,
+        strobe_limit_indicator_text,
+        dmx_capture_title_text,
+        dmx_record_button,
+        dmx_replay_button,
+        dmx_replay_loop_button,
+        dmx_replay_stop_button,
+        dmx_capture_status_text,
+        sacn_input_title_text,
+        sacn_input_button,
+        sacn_input_merge_ddl,
+        sacn_input_start_dialer,
+        sacn_input_count_dialer,
+        sacn_control_button,
+        sacn_control_universe_dialer,
+        sacn_input_help_text,
+        sacn_input_status_text,
+        sacn_discovery_title_text,
+        sacn_discovery_button,
+        sacn_discovery_status_text,
+        fixture_output_ddl,
+        fixture_channel_order_ddl,
+        fixture_pixel_destination_text,
+        fixture_pixel_destination_text_box,
+        fixture_calibration_ddl,
+        fixture_output_stats_text,
+        calibration_title_text,
+        calibration_profile_ddl,
+        calibration_new_button,
+        calibration_delete_button,
+        calibration_gamma_slider,
+        calibration_red_gain_slider,
+        calibration_green_gain_slider,
+        calibration_blue_gain_slider,
+        calibration_max_level_slider,
+        calibration_lut_text,
+        calibration_row_dialer,
+        calibration_row_ddl,
 
         madmapper_section_text,
         madmapper_load_button,
@@ -195,6 +273,14 @@ pub struct ShaderDropdownState {
     pub is_open: bool,
 }
 
+/// Selections for the per-fixture output and calibration editors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixtureEditorState {
+    pub selected_fixture: usize,
+    pub selected_profile: usize,
+    pub selected_row: usize,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct HoverPreviewState {
     pub hovered_rect: Option<nannou_conrod::Rect>,
@@ -234,40 +320,15 @@ pub struct UpdateContext<'a> {
     pub shader_left_dropdown: &'a mut ShaderDropdownState,
     pub shader_right_dropdown: &'a mut ShaderDropdownState,
     pub hover_preview_state: &'a mut HoverPreviewState,
-}
-
-/// Implemented for all sets of shader parameters to allow for generic GUI layout.
-pub trait Params {
-    /// The total number of parameters.
-    fn param_count(&self) -> usize;
-    /// The parameter at the given index.
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_>;
-}
-
-pub struct ParamMut<'a> {
-    pub name: &'static str,
-    pub kind: ParamKindMut<'a>,
-}
-
-pub enum ParamKindMut<'a> {
-    F32 {
-        value: &'a mut f32,
-        max: f32,
-    },
-    F32Range {
-        value: &'a mut f32,
-        min: f32,
-        max: f32,
-    },
-    Bool(&'a mut bool),
-    Select {
-        value: &'a mut usize,
-        labels: &'static [&'static str],
-    },
-    Usize {
-        value: &'a mut usize,
-        max: usize,
-    },
+    pub fixture_editor: &'a mut FixtureEditorState,
+    pub power_estimate: &'a crate::power::PowerEstimate,
+    pub strobe_intervening: bool,
+    pub dmx_capture: &'a mut crate::dmx_recording::CaptureRequest,
+    pub dmx_capture_status: &'a crate::dmx_recording::CaptureStatus,
+    pub pending_replay_dialog:
+        &'a mut Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
+    pub sacn_input_status: &'a crate::SacnInputStatus,
+    pub render_passes: &'a crate::RenderPassTimes,
 }
 
 struct ShaderWidgetState<'a> {
@@ -282,1571 +343,415 @@ struct ShaderWidgetState<'a> {
     envelope: f32,
 }
 
-impl Params for shader_shared::AcidGradient {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "zoom",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.offset,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
-
-impl Params for shader_shared::BlinkyCircles {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "zoom",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.offset,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+/// Update the user interface.
+pub fn update(ui: &mut UiCell, ctx: UpdateContext<'_>) {
+    let UpdateContext {
+        global_config,
+        presets,
+        preset_list_drag,
+        audio_input,
+        left_panel_tab,
+        sacn_output_monitor,
+        sacn_error,
+        sacn_transport_label,
+        since_start,
+        shader_activity,
+        led_colors,
+        last_preset_change,
+        assets,
+        ids,
+        mad_project,
+        resolved_layout,
+        pending_file_dialog,
+        midi_mapping,
+        midi_learn,
+        midi_values,
+        smoothing_speed,
+        smoothed_master_speed,
+        smoothed_phase_offset,
+        smoothed_preset,
+        preview_left_image_id,
+        preview_right_image_id,
+        preview_colourise_image_id,
+        preview_hover_image_id,
+        hover_preview_request,
+        shader_left_dropdown,
+        shader_right_dropdown,
+        hover_preview_state,
+        fixture_editor,
+        power_estimate,
+        strobe_intervening,
+        dmx_capture,
+        dmx_capture_status,
+        pending_replay_dialog,
+        sacn_input_status,
+        render_passes,
+    } = ctx;
+    // Clear previous frame's hover state — re-set by dropdown/list hover detection if still hovering.
+    *hover_preview_request = None;
+    hover_preview_state.hovered_rect = None;
 
-impl Params for shader_shared::BwGradient {
-    fn param_count(&self) -> usize {
-        5
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "dc",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.dc,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "amp",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.amp,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "freq",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.freq,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "mirror",
-                kind: ParamKindMut::Bool(&mut self.mirror),
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    widget::Canvas::new()
+        .pad(PAD)
+        .border(0.0)
+        .rgb(0.1, 0.1, 0.1)
+        .set(ids.background, ui);
 
-impl Params for shader_shared::ColourGrid {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "zoom_amount",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom_amount,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "colour_amount",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_amount,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    // Column canvasses.
 
-impl Params for shader_shared::EscherTilings {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "scale",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.scale,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "shape_iter",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.shape_iter,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    column_canvas(ids.background)
+        .top_left_of(ids.background)
+        .set(ids.column_1_id, ui);
 
-impl Params for shader_shared::GilmoreAcid {
-    fn param_count(&self) -> usize {
-        9
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "displace",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.displace,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "colour_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_offset,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "grid_size",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.grid_size,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "wave",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.wave,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "zoom_amount",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom_amount,
-                    max: 1.0,
-                },
-            },
-            6 => ParamMut {
-                name: "rotation_amount",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.rotation_amount,
-                    max: 1.0,
-                },
-            },
-            7 => ParamMut {
-                name: "brightness",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.brightness,
-                    max: 1.0,
-                },
-            },
-            8 => ParamMut {
-                name: "saturation",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.saturation,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    column_canvas(ids.background)
+        .align_top_of(ids.column_1_id)
+        .right_from(ids.column_1_id, PAD)
+        .set(ids.column_2_id, ui);
 
-impl Params for shader_shared::GradientBars {
-    fn param_count(&self) -> usize {
-        11
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "Easing Type",
-                kind: ParamKindMut::Select {
-                    value: &mut self.easing_type,
-                    labels: shader_shared::GRADIENT_BARS_EASING_LABELS,
-                },
-            },
-            1 => ParamMut {
-                name: "Gradient Power",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.gradient_pow,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "Balance",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.balance,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "Speed",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.speed,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "Invert Speed",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.invert_speed,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "Offset",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.offset,
-                    min: 0.0,
-                    max: 16.0,
-                },
-            },
-            6 => ParamMut {
-                name: "Use Odd Directions",
-                kind: ParamKindMut::Bool(&mut self.use_odd_dirs),
-            },
-            7 => ParamMut {
-                name: "Phase Iterations",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.phase_iter,
-                    min: 1.0,
-                    max: 16.0,
-                },
-            },
-            8 => ParamMut {
-                name: "Number of Columns",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.num_columns,
-                    min: 1.0,
-                    max: 32.0,
-                },
-            },
-            9 => ParamMut {
-                name: "X Iterations",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.x_iter,
-                    min: 1.0,
-                    max: 2.0,
-                },
-            },
-            10 => ParamMut {
-                name: "Use Columns",
-                kind: ParamKindMut::Bool(&mut self.use_columns),
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    column_canvas(ids.background)
+        .align_top_of(ids.column_1_id)
+        .right_from(ids.column_2_id, PAD)
+        .set(ids.column_3_id, ui);
 
-impl Params for shader_shared::JustRelax {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "shape_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.shape_offset,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "iter",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.iter,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    column_canvas(ids.background)
+        .align_top_of(ids.column_1_id)
+        .right_from(ids.column_3_id, PAD)
+        .set(ids.column_4_id, ui);
 
-impl Params for shader_shared::LifeLedWall {
-    fn param_count(&self) -> usize {
-        7
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "size",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.size,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "red",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.red,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "green",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.green,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "blue",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.blue,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "saturation",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.saturation,
-                    max: 1.0,
-                },
-            },
-            6 => ParamMut {
-                name: "colour_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_offset,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    text("COHEN GIG")
+        .mid_top_of(ids.column_1_id)
+        .set(ids.title_text, ui);
 
-impl Params for shader_shared::LightPatternGenerator {
-    fn param_count(&self) -> usize {
-        2
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "Zoom",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.zoom,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "Offset",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.offset,
-                    min: 0.0,
-                    max: 10.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    let tab_w = (COLUMN_W - PAD * 0.5) / 3.0;
 
-impl Params for shader_shared::TwoDTiles {
-    fn param_count(&self) -> usize {
-        5
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "size",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.size,
-                    min: 2.0,
-                    max: 40.0,
-                },
-            },
-            1 => ParamMut {
-                name: "offset",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.offset,
-                    min: 0.0,
-                    max: 20.0,
-                },
-            },
-            2 => ParamMut {
-                name: "step_thresh",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.step_thresh,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "power",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.power,
-                    min: 0.0,
-                    max: 100.0,
-                },
-            },
-            4 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.speed,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if button()
+        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Live))
+        .label("Live")
+        .w(tab_w)
+        .mid_left_of(ids.column_1_id)
+        .down(PAD * 1.5)
+        .set(ids.live_tab_button, ui)
+        .was_clicked()
+    {
+        *left_panel_tab = LeftPanelTab::Live;
+        shader_left_dropdown.is_open = false;
+        shader_right_dropdown.is_open = false;
     }
-}
 
-impl Params for shader_shared::RadialKeta {
-    fn param_count(&self) -> usize {
-        2
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.speed,
-                    min: 0.001,
-                    max: 0.3,
-                },
-            },
-            1 => ParamMut {
-                name: "iter",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.iter,
-                    min: 10.0,
-                    max: 50.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if button()
+        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Output))
+        .label("Output")
+        .right(PAD * 0.25)
+        .w(tab_w)
+        .set(ids.output_tab_button, ui)
+        .was_clicked()
+    {
+        *left_panel_tab = LeftPanelTab::Output;
+        shader_left_dropdown.is_open = false;
+        shader_right_dropdown.is_open = false;
     }
-}
 
-impl Params for shader_shared::ImitationRiley {
-    fn param_count(&self) -> usize {
-        6
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "steps",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.steps,
-                    min: 1.0,
-                    max: 40.0,
-                },
-            },
-            1 => ParamMut {
-                name: "offset",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.offset,
-                    min: 0.1,
-                    max: 2.0,
-                },
-            },
-            2 => ParamMut {
-                name: "xMirror",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.x_mirror,
-                    min: -0.1,
-                    max: 0.1,
-                },
-            },
-            3 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.speed,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "blackLevel",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.black_level,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "whiteLevel",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.white_level,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if button()
+        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Midi))
+        .label("MIDI")
+        .right(PAD * 0.25)
+        .w(tab_w)
+        .set(ids.midi_tab_button, ui)
+        .was_clicked()
+    {
+        *left_panel_tab = LeftPanelTab::Midi;
+        shader_left_dropdown.is_open = false;
+        shader_right_dropdown.is_open = false;
     }
-}
 
-impl Params for shader_shared::HoopLoop {
-    fn param_count(&self) -> usize {
-        15
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "invert",
-                kind: ParamKindMut::Bool(&mut self.invert),
-            },
-            1 => ParamMut {
-                name: "zoom",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.zoom,
-                    min: 0.0,
-                    max: 20.0,
-                },
-            },
-            2 => ParamMut {
-                name: "animate",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.animate,
-                    min: -PI as f32,
-                    max: PI as f32,
-                },
-            },
-            3 => ParamMut {
-                name: "size",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.size,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "thickness",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.thickness,
-                    min: 0.001,
-                    max: 0.5,
-                },
-            },
-            5 => ParamMut {
-                name: "lineEffect",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.line_effect,
-                    min: 0.0,
-                    max: 0.2,
-                },
-            },
-            6 => ParamMut {
-                name: "patternOffset",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.pattern_offset,
-                    min: -1.0,
-                    max: 1.0,
-                },
-            },
-            7 => ParamMut {
-                name: "rSin",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.r_sin,
-                    min: -5.0,
-                    max: 5.0,
-                },
-            },
-            8 => ParamMut {
-                name: "xCos",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.x_cos,
-                    min: -5.0,
-                    max: 5.0,
-                },
-            },
-            9 => ParamMut {
-                name: "ySin",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.y_sin,
-                    min: -5.0,
-                    max: 5.0,
-                },
-            },
-            10 => ParamMut {
-                name: "blur",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.blur,
-                    min: 0.001,
-                    max: 0.5,
-                },
-            },
-            11 => ParamMut {
-                name: "function",
-                kind: ParamKindMut::Select {
-                    value: &mut self.function,
-                    labels: shader_shared::HOOP_LOOP_FUNCTION_LABELS,
-                },
-            },
-            12 => ParamMut {
-                name: "rotate",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.rotate,
-                    min: -1.0,
-                    max: 1.0,
-                },
-            },
-            13 => ParamMut {
-                name: "posX",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.pos_x,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            14 => ParamMut {
-                name: "posY",
-                kind: ParamKindMut::F32Range {
-                    value: &mut self.pos_y,
-                    min: 0.0,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
+    match *left_panel_tab {
+        LeftPanelTab::Live => {
+            let audio_anchor =
+                set_live_sidebar_widgets(ui, ids, global_config, since_start, shader_activity);
+            crate::audio_widgets::set_widgets(
+                ui,
+                ids,
+                audio_input,
+                &mut global_config.audio_input_device,
+                smoothing_speed,
+                &mut global_config.master_speed,
+                smoothed_master_speed,
+                &mut global_config.phase_offset,
+                &mut global_config.phase_offset_mod_amount,
+                smoothed_phase_offset,
+                audio_anchor,
+            );
+            set_presets_widgets(
+                ui,
+                ids,
+                global_config,
+                presets,
+                preset_list_drag,
+                last_preset_change,
+                led_colors,
+                assets,
+                hover_preview_request,
+                hover_preview_state,
+            );
         }
-    }
-}
-
-impl Params for shader_shared::LineGradient {
-    fn param_count(&self) -> usize {
-        5
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "num_stripes",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.num_stripes,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "stripe_width",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.stripe_width,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "angle",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.angle,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "smooth_width",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.smooth_width,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
+        LeftPanelTab::Output => {
+            set_output_sidebar_widgets(
+                ui,
+                ids,
+                global_config,
+                sacn_error,
+                sacn_output_monitor,
+                mad_project,
+                resolved_layout,
+                pending_file_dialog,
+                assets,
+                render_passes,
+            );
+            set_output_monitor_widgets(
+                ui,
+                ids,
+                global_config,
+                sacn_output_monitor,
+                sacn_error,
+                sacn_transport_label,
+                mad_project.as_ref(),
+                fixture_editor,
+                power_estimate,
+            );
+            set_dmx_capture_widgets(
+                ui,
+                ids,
+                assets,
+                dmx_capture,
+                dmx_capture_status,
+                pending_replay_dialog,
+            );
+            set_sacn_input_widgets(ui, ids, global_config, sacn_input_status);
+            set_sacn_discovery_widgets(
+                ui,
+                ids,
+                global_config,
+                sacn_input_status,
+                sacn_output_monitor,
+            );
         }
-    }
-}
-
-impl Params for shader_shared::Metafall {
-    fn param_count(&self) -> usize {
-        5
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "scale",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.scale,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "red",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.red,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "green",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.green,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "blue",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.blue,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
+        LeftPanelTab::Midi => {
+            set_midi_tab_widgets(ui, ids, midi_mapping, midi_learn, midi_values, assets);
         }
     }
-}
 
-impl Params for shader_shared::ParticleZoom {
-    fn param_count(&self) -> usize {
-        4
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "density",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.density,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "shape",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.shape,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "tau",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.tau,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    // Now that preset selection is done, get easier access to the selected preset.
+    let preset = presets.selected_mut();
 
-impl Params for shader_shared::RadialLines {
-    fn param_count(&self) -> usize {
-        2
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "zoom_amount",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom_amount,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    //---------------------- LED SHADER LEFT
 
-impl Params for shader_shared::SatisSpiraling {
-    fn param_count(&self) -> usize {
-        5
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "loops",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.loops,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "mirror",
-                kind: ParamKindMut::Bool(&mut self.mirror),
-            },
-            3 => ParamMut {
-                name: "rotate",
-                kind: ParamKindMut::Bool(&mut self.rotate),
-            },
-            4 => ParamMut {
-                name: "colour_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_offset,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    text("LED Shader Left")
+        .top_left_of(ids.column_3_id)
+        .color(color::WHITE)
+        .set(ids.led_shader_left_text, ui);
 
-impl Params for shader_shared::SpiralIntersect {
-    fn param_count(&self) -> usize {
-        6
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "g1",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.g1,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "g2",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.g2,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "rot1",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.rot1,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "rot2",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.rot2,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "colours",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colours,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if let Some(image_id) = preview_left_image_id {
+        widget::Image::new(image_id)
+            .w(COLUMN_W)
+            .h(COLUMN_W * 0.3)
+            .down(10.0)
+            .set(ids.led_preview_left_image, ui);
     }
-}
 
-impl Params for shader_shared::SquareTunnel {
-    fn param_count(&self) -> usize {
-        4
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "rotation_speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.rotation_speed,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "rotation_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.rotation_offset,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "zoom",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.zoom,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    let shader_names: Vec<_> = shader_shared::ALL_SHADERS
+        .iter()
+        .map(|s| s.name())
+        .collect();
 
-impl Params for shader_shared::ThePulse {
-    fn param_count(&self) -> usize {
-        4
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "scale",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.scale,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "colour_iter",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_iter,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "thickness",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.thickness,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    let (selected, left_btn_id) = shader_dropdown(
+        ui,
+        ids.shader_left_button,
+        ids.shader_left_list,
+        shader_left_dropdown,
+        preset.shader_left,
+        &shader_names,
+        hover_preview_request,
+        hover_preview_state,
+    );
+    if let Some(shader) = selected {
+        preset.shader_left = shader;
     }
-}
-
-impl Params for shader_shared::TunnelProjection {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "res",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.res,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "y_offset",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.y_offset,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    widget::Rectangle::fill([0.0, 0.0])
+        .down_from(left_btn_id, 0.0)
+        .set(ids.shader_left_anchor, ui);
+    if shader_left_dropdown.is_open {
+        shader_right_dropdown.is_open = false;
     }
-}
 
-impl Params for shader_shared::VertColourGradient {
-    fn param_count(&self) -> usize {
-        6
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "scale",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.scale,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "colour_iter",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.colour_iter,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "line_amp",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.line_amp,
-                    max: 1.0,
-                },
-            },
-            4 => ParamMut {
-                name: "diag_amp",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.diag_amp,
-                    max: 1.0,
-                },
-            },
-            5 => ParamMut {
-                name: "border_amp",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.boarder_amp,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    let smoothed_left_values = shader_param_f32_values(
+        smoothed_preset.shader_left,
+        smoothed_preset.shader_params_left,
+    );
+    let smoothed_colourise_values = shader_param_f32_values(
+        smoothed_preset.colourise,
+        smoothed_preset.shader_params_colourise,
+    );
+    let smoothed_right_values = shader_param_f32_values(
+        smoothed_preset.shader_right,
+        smoothed_preset.shader_params_right,
+    );
 
-impl Params for shader_shared::MitchWash {
-    fn param_count(&self) -> usize {
-        2
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "pulse_speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.pulse_speed,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    let mut mod_slider_ix = 0;
+    let mut int_slider_ix = 0;
+    let mut dropdown_ix = 0;
+    let mut button_ix = 0;
 
-impl Params for shader_shared::ShapeEnvelopes {
-    fn param_count(&self) -> usize {
-        4
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "pulse_speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.pulse_speed,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "line_thickness",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.line_thickness,
-                    max: 1.0,
-                },
-            },
-            3 => ParamMut {
-                name: "shape_thickness",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.shape_thickness,
-                    max: 1.0,
-                },
+    {
+        let mod_start = mod_slider_ix;
+        let params = preset.shader_params_left.params_mut(preset.shader_left);
+        set_shader_widgets(
+            ui,
+            ids,
+            params,
+            ShaderWidgetState {
+                mod_slider_ix: &mut mod_slider_ix,
+                int_slider_ix: &mut int_slider_ix,
+                dropdown_ix: &mut dropdown_ix,
+                button_ix: &mut button_ix,
+                mod_amounts: &mut preset.shader_mod_amounts_left,
+                smoothed_values: &smoothed_left_values,
+                mod_amounts_offset: mod_start,
+                envelope: audio_input.envelope,
             },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+        );
+        preset
+            .shader_mod_amounts_left
+            .truncate(mod_slider_ix - mod_start);
     }
-}
 
-impl Params for shader_shared::RowTest {
-    fn param_count(&self) -> usize {
-        1
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "row",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.row,
-                    max: 8.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    //---------------------- COLOUR POST PROCESS SHADER
+    text("Colour Post Process")
+        .down(20.0)
+        .color(color::WHITE)
+        .set(ids.colour_post_process_text, ui);
 
-impl Params for shader_shared::BarTest {
-    fn param_count(&self) -> usize {
-        1
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "row",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.row,
-                    max: 8.0,
-                },
-            },
-            1 => ParamMut {
-                name: "bar",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.bar,
-                    max: 8.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if let Some(image_id) = preview_colourise_image_id {
+        widget::Image::new(image_id)
+            .w(COLUMN_W)
+            .h(COLUMN_W * 0.3)
+            .down(10.0)
+            .set(ids.led_preview_colourise_image, ui);
     }
-}
 
-impl Params for shader_shared::SolidHsvColour {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "hue",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.hue,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "saturation",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.saturation,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "value",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.value,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
-    }
-}
+    let colour_names: Vec<_> = shader_shared::SOLID_COLOUR_SHADERS
+        .iter()
+        .map(|s| s.name())
+        .collect();
+    let colourise_idx = preset.colourise.to_index();
 
-impl Params for shader_shared::SolidRgbColour {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "red",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.red,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "green",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.green,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "blue",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.blue,
-                    max: 1.0,
-                },
-            },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+    if let Some(selected_idx) = widget::DropDownList::new(&colour_names, Some(colourise_idx))
+        .w_h(COLUMN_W, PAD * 2.0)
+        .down(10.0)
+        .max_visible_items(15)
+        .rgb(0.176, 0.513, 0.639)
+        .label("Colour Shader")
+        .label_font_size(15)
+        .label_rgb(1.0, 1.0, 1.0)
+        .scrollbar_on_top()
+        .set(ids.colour_post_process_ddl, ui)
+    {
+        preset.colourise = Shader::from_index(selected_idx).unwrap();
     }
-}
 
-impl Params for shader_shared::ColourPalettes {
-    fn param_count(&self) -> usize {
-        3
-    }
-    fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
-        match ix {
-            0 => ParamMut {
-                name: "speed",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.speed,
-                    max: 1.0,
-                },
-            },
-            1 => ParamMut {
-                name: "interval",
-                kind: ParamKindMut::F32 {
-                    value: &mut self.interval,
-                    max: 1.0,
-                },
-            },
-            2 => ParamMut {
-                name: "selected",
-                kind: ParamKindMut::Usize {
-                    value: &mut self.selected,
-                    max: 16,
-                },
+    {
+        let mod_start = mod_slider_ix;
+        let params = preset.shader_params_colourise.params_mut(preset.colourise);
+        set_shader_widgets(
+            ui,
+            ids,
+            params,
+            ShaderWidgetState {
+                mod_slider_ix: &mut mod_slider_ix,
+                int_slider_ix: &mut int_slider_ix,
+                dropdown_ix: &mut dropdown_ix,
+                button_ix: &mut button_ix,
+                mod_amounts: &mut preset.shader_mod_amounts_colourise,
+                smoothed_values: &smoothed_colourise_values,
+                mod_amounts_offset: mod_start,
+                envelope: audio_input.envelope,
             },
-            _ => panic!("no parameter for index {}: check `param_count` impl", ix),
-        }
+        );
+        preset
+            .shader_mod_amounts_colourise
+            .truncate(mod_slider_ix - mod_start);
     }
-}
 
-/// Update the user interface.
-pub fn update(ui: &mut UiCell, ctx: UpdateContext<'_>) {
-    let UpdateContext {
-        global_config,
-        presets,
-        preset_list_drag,
-        audio_input,
-        left_panel_tab,
-        sacn_output_monitor,
-        sacn_error,
-        sacn_transport_label,
-        since_start,
-        shader_activity,
-        led_colors,
-        last_preset_change,
-        assets,
-        ids,
-        mad_project,
-        resolved_layout,
-        pending_file_dialog,
-        midi_mapping,
-        midi_learn,
-        midi_values,
-        smoothing_speed,
-        smoothed_master_speed,
-        smoothed_phase_offset,
-        smoothed_preset,
-        preview_left_image_id,
-        preview_right_image_id,
-        preview_colourise_image_id,
-        preview_hover_image_id,
-        hover_preview_request,
-        shader_left_dropdown,
-        shader_right_dropdown,
-        hover_preview_state,
-    } = ctx;
-    // Clear previous frame's hover state — re-set by dropdown/list hover detection if still hovering.
-    *hover_preview_request = None;
-    hover_preview_state.hovered_rect = None;
-
-    widget::Canvas::new()
-        .pad(PAD)
-        .border(0.0)
-        .rgb(0.1, 0.1, 0.1)
-        .set(ids.background, ui);
-
-    // Column canvasses.
-
-    column_canvas(ids.background)
-        .top_left_of(ids.background)
-        .set(ids.column_1_id, ui);
-
-    column_canvas(ids.background)
-        .align_top_of(ids.column_1_id)
-        .right_from(ids.column_1_id, PAD)
-        .set(ids.column_2_id, ui);
-
-    column_canvas(ids.background)
-        .align_top_of(ids.column_1_id)
-        .right_from(ids.column_2_id, PAD)
-        .set(ids.column_3_id, ui);
-
-    column_canvas(ids.background)
-        .align_top_of(ids.column_1_id)
-        .right_from(ids.column_3_id, PAD)
-        .set(ids.column_4_id, ui);
-
-    text("COHEN GIG")
-        .mid_top_of(ids.column_1_id)
-        .set(ids.title_text, ui);
-
-    let tab_w = (COLUMN_W - PAD * 0.5) / 3.0;
-
-    if button()
-        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Live))
-        .label("Live")
-        .w(tab_w)
-        .mid_left_of(ids.column_1_id)
-        .down(PAD * 1.5)
-        .set(ids.live_tab_button, ui)
-        .was_clicked()
-    {
-        *left_panel_tab = LeftPanelTab::Live;
-        shader_left_dropdown.is_open = false;
-        shader_right_dropdown.is_open = false;
-    }
+    text("Tone Mapping")
+        .down(20.0)
+        .color(color::WHITE)
+        .set(ids.tone_mapping_text, ui);
 
-    if button()
-        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Output))
-        .label("Output")
-        .right(PAD * 0.25)
-        .w(tab_w)
-        .set(ids.output_tab_button, ui)
-        .was_clicked()
+    let tone_mapping_names: Vec<_> = shader_shared::ALL_TONE_MAPPINGS
+        .iter()
+        .map(|tone_mapping| tone_mapping.name())
+        .collect();
+    let tone_mapping_idx = preset.tone_mapping.to_index();
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&tone_mapping_names, Some(tone_mapping_idx))
+            .w_h(COLUMN_W, PAD * 2.0)
+            .down(10.0)
+            .max_visible_items(tone_mapping_names.len().min(12))
+            .rgb(0.176, 0.513, 0.639)
+            .label("Tone Mapping")
+            .label_font_size(15)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.tone_mapping_ddl, ui)
     {
-        *left_panel_tab = LeftPanelTab::Output;
-        shader_left_dropdown.is_open = false;
-        shader_right_dropdown.is_open = false;
+        preset.tone_mapping = ToneMapping::from_index(selected_idx).unwrap();
     }
 
-    if button()
-        .color(tab_button_color(*left_panel_tab == LeftPanelTab::Midi))
-        .label("MIDI")
-        .right(PAD * 0.25)
-        .w(tab_w)
-        .set(ids.midi_tab_button, ui)
-        .was_clicked()
+    if let Some(value) = slider(preset.tone_mapping_amount, 0.0, 1.0)
+        .down(10.0)
+        .label("Tone Mapping Amount")
+        .set(ids.tone_mapping_amount, ui)
     {
-        *left_panel_tab = LeftPanelTab::Midi;
-        shader_left_dropdown.is_open = false;
-        shader_right_dropdown.is_open = false;
-    }
-
-    match *left_panel_tab {
-        LeftPanelTab::Live => {
-            let audio_anchor =
-                set_live_sidebar_widgets(ui, ids, global_config, since_start, shader_activity);
-            crate::audio_widgets::set_widgets(
-                ui,
-                ids,
-                audio_input,
-                &mut global_config.audio_input_device,
-                smoothing_speed,
-                &mut global_config.master_speed,
-                smoothed_master_speed,
-                &mut global_config.phase_offset,
-                &mut global_config.phase_offset_mod_amount,
-                smoothed_phase_offset,
-                audio_anchor,
-            );
-            set_presets_widgets(
-                ui,
-                ids,
-                global_config,
-                presets,
-                preset_list_drag,
-                last_preset_change,
-                led_colors,
-                assets,
-                hover_preview_request,
-                hover_preview_state,
-            );
-        }
-        LeftPanelTab::Output => {
-            set_output_sidebar_widgets(
-                ui,
-                ids,
-                global_config,
-                sacn_error,
-                sacn_output_monitor,
-                mad_project,
-                resolved_layout,
-                pending_file_dialog,
-                assets,
-            );
-            set_output_monitor_widgets(
-                ui,
-                ids,
-                global_config,
-                sacn_output_monitor,
-                sacn_error,
-                sacn_transport_label,
-            );
-        }
-        LeftPanelTab::Midi => {
-            set_midi_tab_widgets(ui, ids, midi_mapping, midi_learn, midi_values, assets);
-        }
+        preset.tone_mapping_amount = value;
     }
 
-    // Now that preset selection is done, get easier access to the selected preset.
-    let preset = presets.selected_mut();
-
-    //---------------------- LED SHADER LEFT
+    //---------------------- LED SHADER RIGHT
 
-    text("LED Shader Left")
-        .top_left_of(ids.column_3_id)
+    text("LED Shader Right")
+        .top_left_of(ids.column_4_id)
         .color(color::WHITE)
-        .set(ids.led_shader_left_text, ui);
+        .set(ids.led_shader_right_text, ui);
 
-    if let Some(image_id) = preview_left_image_id {
+    if let Some(image_id) = preview_right_image_id {
         widget::Image::new(image_id)
             .w(COLUMN_W)
             .h(COLUMN_W * 0.3)
             .down(10.0)
-            .set(ids.led_preview_left_image, ui);
+            .set(ids.led_preview_right_image, ui);
     }
 
-    let shader_names: Vec<_> = shader_shared::ALL_SHADERS
-        .iter()
-        .map(|s| s.name())
-        .collect();
-
-    let (selected, left_btn_id) = shader_dropdown(
+    let (selected, right_btn_id) = shader_dropdown(
         ui,
-        ids.shader_left_button,
-        ids.shader_left_list,
-        shader_left_dropdown,
-        preset.shader_left,
+        ids.shader_right_button,
+        ids.shader_right_list,
+        shader_right_dropdown,
+        preset.shader_right,
         &shader_names,
         hover_preview_request,
         hover_preview_state,
     );
     if let Some(shader) = selected {
-        preset.shader_left = shader;
+        preset.shader_right = shader;
     }
     widget::Rectangle::fill([0.0, 0.0])
-        .down_from(left_btn_id, 0.0)
-        .set(ids.shader_left_anchor, ui);
-    if shader_left_dropdown.is_open {
-        shader_right_dropdown.is_open = false;
+        .down_from(right_btn_id, 0.0)
+        .set(ids.shader_right_anchor, ui);
+    if shader_right_dropdown.is_open {
+        shader_left_dropdown.is_open = false;
     }
 
-    let smoothed_left_values = shader_param_f32_values(
-        smoothed_preset.shader_left,
-        smoothed_preset.shader_params_left,
-    );
-    let smoothed_colourise_values = shader_param_f32_values(
-        smoothed_preset.colourise,
-        smoothed_preset.shader_params_colourise,
-    );
-    let smoothed_right_values = shader_param_f32_values(
-        smoothed_preset.shader_right,
-        smoothed_preset.shader_params_right,
-    );
-
-    let mut mod_slider_ix = 0;
-    let mut int_slider_ix = 0;
-    let mut dropdown_ix = 0;
-    let mut button_ix = 0;
-
     {
         let mod_start = mod_slider_ix;
-        let params = shader_params(preset.shader_left, &mut preset.shader_params_left);
+        let params = preset.shader_params_right.params_mut(preset.shader_right);
         set_shader_widgets(
             ui,
             ids,
@@ -1856,170 +761,22 @@ pub fn update(ui: &mut UiCell, ctx: UpdateContext<'_>) {
                 int_slider_ix: &mut int_slider_ix,
                 dropdown_ix: &mut dropdown_ix,
                 button_ix: &mut button_ix,
-                mod_amounts: &mut preset.shader_mod_amounts_left,
-                smoothed_values: &smoothed_left_values,
+                mod_amounts: &mut preset.shader_mod_amounts_right,
+                smoothed_values: &smoothed_right_values,
                 mod_amounts_offset: mod_start,
                 envelope: audio_input.envelope,
             },
         );
         preset
-            .shader_mod_amounts_left
+            .shader_mod_amounts_right
             .truncate(mod_slider_ix - mod_start);
     }
 
-    //---------------------- COLOUR POST PROCESS SHADER
-    text("Colour Post Process")
+    //---------------------- BLEND MODES
+    text("LED Blend Mode")
         .down(20.0)
         .color(color::WHITE)
-        .set(ids.colour_post_process_text, ui);
-
-    if let Some(image_id) = preview_colourise_image_id {
-        widget::Image::new(image_id)
-            .w(COLUMN_W)
-            .h(COLUMN_W * 0.3)
-            .down(10.0)
-            .set(ids.led_preview_colourise_image, ui);
-    }
-
-    let colour_names: Vec<_> = shader_shared::SOLID_COLOUR_SHADERS
-        .iter()
-        .map(|s| s.name())
-        .collect();
-    let colourise_idx = preset.colourise.to_index();
-
-    if let Some(selected_idx) = widget::DropDownList::new(&colour_names, Some(colourise_idx))
-        .w_h(COLUMN_W, PAD * 2.0)
-        .down(10.0)
-        .max_visible_items(15)
-        .rgb(0.176, 0.513, 0.639)
-        .label("Colour Shader")
-        .label_font_size(15)
-        .label_rgb(1.0, 1.0, 1.0)
-        .scrollbar_on_top()
-        .set(ids.colour_post_process_ddl, ui)
-    {
-        preset.colourise = Shader::from_index(selected_idx).unwrap();
-    }
-
-    {
-        let mod_start = mod_slider_ix;
-        let params = shader_params(preset.colourise, &mut preset.shader_params_colourise);
-        set_shader_widgets(
-            ui,
-            ids,
-            params,
-            ShaderWidgetState {
-                mod_slider_ix: &mut mod_slider_ix,
-                int_slider_ix: &mut int_slider_ix,
-                dropdown_ix: &mut dropdown_ix,
-                button_ix: &mut button_ix,
-                mod_amounts: &mut preset.shader_mod_amounts_colourise,
-                smoothed_values: &smoothed_colourise_values,
-                mod_amounts_offset: mod_start,
-                envelope: audio_input.envelope,
-            },
-        );
-        preset
-            .shader_mod_amounts_colourise
-            .truncate(mod_slider_ix - mod_start);
-    }
-
-    text("Tone Mapping")
-        .down(20.0)
-        .color(color::WHITE)
-        .set(ids.tone_mapping_text, ui);
-
-    let tone_mapping_names: Vec<_> = shader_shared::ALL_TONE_MAPPINGS
-        .iter()
-        .map(|tone_mapping| tone_mapping.name())
-        .collect();
-    let tone_mapping_idx = preset.tone_mapping.to_index();
-    if let Some(selected_idx) =
-        widget::DropDownList::new(&tone_mapping_names, Some(tone_mapping_idx))
-            .w_h(COLUMN_W, PAD * 2.0)
-            .down(10.0)
-            .max_visible_items(tone_mapping_names.len().min(12))
-            .rgb(0.176, 0.513, 0.639)
-            .label("Tone Mapping")
-            .label_font_size(15)
-            .label_rgb(1.0, 1.0, 1.0)
-            .scrollbar_on_top()
-            .set(ids.tone_mapping_ddl, ui)
-    {
-        preset.tone_mapping = ToneMapping::from_index(selected_idx).unwrap();
-    }
-
-    if let Some(value) = slider(preset.tone_mapping_amount, 0.0, 1.0)
-        .down(10.0)
-        .label("Tone Mapping Amount")
-        .set(ids.tone_mapping_amount, ui)
-    {
-        preset.tone_mapping_amount = value;
-    }
-
-    //---------------------- LED SHADER RIGHT
-
-    text("LED Shader Right")
-        .top_left_of(ids.column_4_id)
-        .color(color::WHITE)
-        .set(ids.led_shader_right_text, ui);
-
-    if let Some(image_id) = preview_right_image_id {
-        widget::Image::new(image_id)
-            .w(COLUMN_W)
-            .h(COLUMN_W * 0.3)
-            .down(10.0)
-            .set(ids.led_preview_right_image, ui);
-    }
-
-    let (selected, right_btn_id) = shader_dropdown(
-        ui,
-        ids.shader_right_button,
-        ids.shader_right_list,
-        shader_right_dropdown,
-        preset.shader_right,
-        &shader_names,
-        hover_preview_request,
-        hover_preview_state,
-    );
-    if let Some(shader) = selected {
-        preset.shader_right = shader;
-    }
-    widget::Rectangle::fill([0.0, 0.0])
-        .down_from(right_btn_id, 0.0)
-        .set(ids.shader_right_anchor, ui);
-    if shader_right_dropdown.is_open {
-        shader_left_dropdown.is_open = false;
-    }
-
-    {
-        let mod_start = mod_slider_ix;
-        let params = shader_params(preset.shader_right, &mut preset.shader_params_right);
-        set_shader_widgets(
-            ui,
-            ids,
-            params,
-            ShaderWidgetState {
-                mod_slider_ix: &mut mod_slider_ix,
-                int_slider_ix: &mut int_slider_ix,
-                dropdown_ix: &mut dropdown_ix,
-                button_ix: &mut button_ix,
-                mod_amounts: &mut preset.shader_mod_amounts_right,
-                smoothed_values: &smoothed_right_values,
-                mod_amounts_offset: mod_start,
-                envelope: audio_input.envelope,
-            },
-        );
-        preset
-            .shader_mod_amounts_right
-            .truncate(mod_slider_ix - mod_start);
-    }
-
-    //---------------------- BLEND MODES
-    text("LED Blend Mode")
-        .down(20.0)
-        .color(color::WHITE)
-        .set(ids.blend_mode_text, ui);
+        .set(ids.blend_mode_text, ui);
 
     let blend_mode_names: Vec<_> = shader_shared::ALL_BLEND_MODES
         .iter()
@@ -2056,6 +813,21 @@ pub fn update(ui: &mut UiCell, ctx: UpdateContext<'_>) {
         global_config.fade_to_black.led = value;
     }
 
+    if global_config.strobe_limit.enabled {
+        let (label, colour) = if strobe_intervening {
+            ("STROBE LIMITER ACTIVE", color::LIGHT_RED)
+        } else {
+            ("Strobe limiter armed", TEXT_COLOR)
+        };
+        widget::Text::new(label)
+            .down(10.0)
+            .w(COLUMN_W)
+            .font_size(12)
+            .color(colour)
+            .left_justify()
+            .set(ids.strobe_limit_indicator_text, ui);
+    }
+
     // Floating hover preview image at mouse position.
     if let Some(image_id) = preview_hover_image_id {
         if hover_preview_request.is_some() {
@@ -2111,6 +883,12 @@ fn set_live_sidebar_widgets(
             let c = ui::color::YELLOW.with_luminance(l as _);
             (s, c)
         }
+        shader::Activity::Panicked(panics) => {
+            let names: Vec<_> = panics.iter().map(|p| p.shader.name()).collect();
+            let s = format!("Panicked: {}", names.join(", "));
+            let c = ui::color::RED;
+            (s, c)
+        }
         shader::Activity::LastIncoming(last) => match last {
             shader::LastIncoming::Succeeded => {
                 let s = "Succeeded".into();
@@ -2122,485 +900,1753 @@ fn set_live_sidebar_widgets(
                 let c = ui::color::RED;
                 (s, c)
             }
+            shader::LastIncoming::Incompatible(_err) => {
+                let s = "Incompatible - Restart".to_string();
+                let c = ui::color::RED;
+                (s, c)
+            }
         },
     };
-    text(&string)
-        .color(color)
-        .down(PAD)
-        .set(ids.shader_state_text, ui);
+    text(&string)
+        .color(color)
+        .down(PAD)
+        .set(ids.shader_state_text, ui);
+
+    ids.shader_state_text
+}
+
+fn set_output_sidebar_widgets(
+    ui: &mut UiCell,
+    ids: &Ids,
+    global_config: &mut GlobalConfig,
+    sacn_error: Option<&str>,
+    sacn_output_monitor: &crate::SacnOutputMonitor,
+    mad_project: &mut Option<crate::mad_mapper::MadProject>,
+    resolved_layout: &mut Option<crate::layout::ResolvedLayout>,
+    pending_file_dialog: &mut Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
+    assets: &Path,
+    render_passes: &crate::RenderPassTimes,
+) {
+    let has_mad_project = mad_project.is_some();
+
+    text("LED Output FPS")
+        .mid_left_of(ids.column_1_id)
+        .down_from(ids.live_tab_button, PAD * 0.5)
+        .set(ids.output_fps_text, ui);
+
+    let output_fps_labels: Vec<_> = crate::conf::LedOutputFps::ALL
+        .iter()
+        .map(|mode| mode.label())
+        .collect();
+    let selected_output_fps = Some(global_config.led_output_fps.to_index());
+    if let Some(selected_idx) = widget::DropDownList::new(&output_fps_labels, selected_output_fps)
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .down(5.0)
+        .max_visible_items(output_fps_labels.len())
+        .rgb(0.176, 0.513, 0.639)
+        .label("LED Output")
+        .label_font_size(14)
+        .label_rgb(1.0, 1.0, 1.0)
+        .scrollbar_on_top()
+        .set(ids.output_fps_ddl, ui)
+    {
+        if let Some(output_fps) = crate::conf::LedOutputFps::from_index(selected_idx) {
+            global_config.led_output_fps = output_fps;
+        }
+    }
+
+    let measured_output_fps = if global_config.dmx_on {
+        format_measured_fps(
+            sacn_output_monitor.smoothed_frame_fps,
+            sacn_output_monitor.total_frames_sent,
+        )
+    } else {
+        "Disabled".to_string()
+    };
+    let output_fps_status = format!(
+        "LED Output: {} (Cap {})",
+        measured_output_fps,
+        global_config.led_output_fps.label()
+    );
+    widget::Text::new(&output_fps_status)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.output_fps_status_text, ui);
+
+    text("Shader Previews")
+        .mid_left_of(ids.column_1_id)
+        .down_from(ids.output_fps_status_text, COLUMN_ONE_SECTION_GAP)
+        .set(ids.shader_previews_text, ui);
+
+    let preview_rate_labels: Vec<_> = crate::conf::ShaderPreviewRate::ALL
+        .iter()
+        .map(|rate| rate.label())
+        .collect();
+    let selected_preview_rate = Some(global_config.shader_previews.rate.to_index());
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&preview_rate_labels, selected_preview_rate)
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .max_visible_items(preview_rate_labels.len())
+            .rgb(0.176, 0.513, 0.639)
+            .label("Preview Rate")
+            .label_font_size(14)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.shader_preview_rate_ddl, ui)
+    {
+        if let Some(rate) = crate::conf::ShaderPreviewRate::from_index(selected_idx) {
+            global_config.shader_previews.rate = rate;
+        }
+    }
+
+    let preview_resolution_labels: Vec<_> = crate::conf::ShaderPreviewResolution::ALL
+        .iter()
+        .map(|resolution| resolution.label())
+        .collect();
+    let selected_preview_resolution = Some(global_config.shader_previews.resolution.to_index());
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&preview_resolution_labels, selected_preview_resolution)
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .max_visible_items(preview_resolution_labels.len())
+            .rgb(0.176, 0.513, 0.639)
+            .label("Preview Resolution")
+            .label_font_size(14)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.shader_preview_resolution_ddl, ui)
+    {
+        if let Some(resolution) = crate::conf::ShaderPreviewResolution::from_index(selected_idx) {
+            global_config.shader_previews.resolution = resolution;
+        }
+    }
+
+    widget::Text::new(&render_passes.summary())
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.render_passes_text, ui);
+
+    text("DMX Protocol")
+        .mid_left_of(ids.column_1_id)
+        .down_from(ids.render_passes_text, COLUMN_ONE_SECTION_GAP)
+        .set(ids.dmx_protocol_text, ui);
+
+    let protocol_labels: Vec<_> = crate::conf::DmxProtocol::ALL
+        .iter()
+        .map(|protocol| protocol.label())
+        .collect();
+    let selected_protocol = Some(global_config.dmx_protocol.to_index());
+    if let Some(selected_idx) = widget::DropDownList::new(&protocol_labels, selected_protocol)
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .down(5.0)
+        .max_visible_items(protocol_labels.len())
+        .rgb(0.176, 0.513, 0.639)
+        .label("DMX Protocol")
+        .label_font_size(14)
+        .label_rgb(1.0, 1.0, 1.0)
+        .scrollbar_on_top()
+        .set(ids.dmx_protocol_ddl, ui)
+    {
+        if let Some(protocol) = crate::conf::DmxProtocol::from_index(selected_idx) {
+            global_config.dmx_protocol = protocol;
+        }
+    }
+
+    text("sACN Interface IP")
+        .mid_left_of(ids.column_1_id)
+        .down_from(ids.dmx_protocol_ddl, COLUMN_ONE_SECTION_GAP)
+        .set(ids.sacn_interface_ip_text, ui);
+
+    widget::Text::new(
+        "Use this computer's IP on the PixLite network, e.g. 10.0.0.100. Leave blank for Auto; it falls back to localhost preview if multicast is unavailable.",
+    )
+    .down(5.0)
+    .w(WIDGET_W)
+    .font_size(10)
+    .color(TEXT_COLOR)
+    .left_justify()
+    .set(ids.sacn_interface_ip_help_text, ui);
+
+    let color = if sacn_error.is_some() {
+        color::DARK_RED.with_luminance(0.1)
+    } else {
+        match crate::conf::parse_sacn_interface_ip(&global_config.sacn_interface_ip) {
+            Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
+            Ok(None) => color::BLACK,
+            Err(_) => color::DARK_RED.with_luminance(0.1),
+        }
+    };
+    for event in widget::TextBox::new(&global_config.sacn_interface_ip)
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .down(5.0)
+        .border(0.0)
+        .color(color)
+        .text_color(color::WHITE)
+        .font_size(14)
+        .set(ids.sacn_interface_ip_text_box, ui)
+    {
+        match event {
+            widget::text_box::Event::Update(string) => global_config.sacn_interface_ip = string,
+            widget::text_box::Event::Enter => {
+                global_config.sacn_interface_ip =
+                    global_config.sacn_interface_ip.trim().to_string();
+            }
+        }
+    }
+
+    if let Some(error) = sacn_error {
+        widget::Text::new(error)
+            .down(5.0)
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(color::LIGHT_RED)
+            .left_justify()
+            .set(ids.sacn_interface_ip_error_text, ui);
+    }
+
+    if !global_config.dmx_protocol.sends_pixel_frames() {
+        text("Mirror Interface IP")
+            .mid_left_of(ids.column_1_id)
+            .down(COLUMN_ONE_SECTION_GAP)
+            .set(ids.mirror_interface_ip_text, ui);
+
+        widget::Text::new(
+            "Repeat all output on a second interface for a redundant show network. Leave blank to disable.",
+        )
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.mirror_interface_ip_help_text, ui);
+
+        let color = match crate::conf::parse_sacn_interface_ip(&global_config.mirror_interface_ip) {
+            Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
+            Ok(None) => color::BLACK,
+            Err(_) => color::DARK_RED.with_luminance(0.1),
+        };
+        for event in widget::TextBox::new(&global_config.mirror_interface_ip)
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .border(0.0)
+            .color(color)
+            .text_color(color::WHITE)
+            .font_size(14)
+            .set(ids.mirror_interface_ip_text_box, ui)
+        {
+            apply_text_box_event(&mut global_config.mirror_interface_ip, event);
+        }
+    }
+
+    if global_config.dmx_protocol == crate::conf::DmxProtocol::ArtNet {
+        text("Art-Net Destination IP")
+            .mid_left_of(ids.column_1_id)
+            .down(COLUMN_ONE_SECTION_GAP)
+            .set(ids.artnet_destination_ip_text, ui);
+
+        widget::Text::new(
+            "Unicast to a single controller, e.g. 10.0.0.50. Leave blank to broadcast to 255.255.255.255.",
+        )
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.artnet_destination_ip_help_text, ui);
+
+        let color = match crate::conf::parse_sacn_interface_ip(&global_config.artnet_destination_ip)
+        {
+            Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
+            Ok(None) => color::BLACK,
+            Err(_) => color::DARK_RED.with_luminance(0.1),
+        };
+        for event in widget::TextBox::new(&global_config.artnet_destination_ip)
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .border(0.0)
+            .color(color)
+            .text_color(color::WHITE)
+            .font_size(14)
+            .set(ids.artnet_destination_ip_text_box, ui)
+        {
+            match event {
+                widget::text_box::Event::Update(string) => {
+                    global_config.artnet_destination_ip = string
+                }
+                widget::text_box::Event::Enter => {
+                    global_config.artnet_destination_ip =
+                        global_config.artnet_destination_ip.trim().to_string();
+                }
+            }
+        }
+
+        widget::Text::new(
+            "Subtracted from each universe to give its port-address, e.g. 1 sends universe 1 as 0:0:0.",
+        )
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.artnet_universe_offset_help_text, ui);
+
+        let offset = global_config.artnet_universe_offset;
+        if let Some(v) = widget::NumberDialer::new(offset as f32, 0.0, 63999.0, 0)
+            .border(0.0)
+            .label("Universe Offset")
+            .label_color(color::WHITE)
+            .label_font_size(14)
+            .down(5.0)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .color(color::DARK_CHARCOAL)
+            .set(ids.artnet_universe_offset_dialer, ui)
+        {
+            global_config.artnet_universe_offset = v as u16;
+        }
+    }
+
+    if global_config.dmx_protocol.sends_pixel_frames() {
+        text("Pixel Destination")
+            .mid_left_of(ids.column_1_id)
+            .down(COLUMN_ONE_SECTION_GAP)
+            .set(ids.pixel_destination_text, ui);
+
+        widget::Text::new(
+            "The controller for fixtures without their own destination, e.g. 10.0.0.50 or 10.0.0.50:7890. Set per fixture under Fixture Output.",
+        )
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.pixel_destination_help_text, ui);
+
+        let destination = &mut global_config.pixel_destinations.default;
+        for event in pixel_destination_text_box(destination)
+            .down(5.0)
+            .set(ids.pixel_destination_text_box, ui)
+        {
+            apply_text_box_event(destination, event);
+        }
+    }
+
+    if button()
+        .color(toggle_color(global_config.sacn_sync_universe.is_some()))
+        .label("Universe Sync")
+        .w(WIDGET_W)
+        .mid_left_of(ids.column_1_id)
+        .down(COLUMN_ONE_SECTION_GAP)
+        .set(ids.sacn_sync_button, ui)
+        .was_clicked()
+    {
+        global_config.sacn_sync_universe = match global_config.sacn_sync_universe {
+            Some(_) => None,
+            None => Some(crate::conf::default::sacn_sync_universe()),
+        };
+    }
+
+    if let Some(sync_universe) = global_config.sacn_sync_universe {
+        if let Some(v) = widget::NumberDialer::new(sync_universe as f32, 1.0, 63999.0, 0)
+            .border(0.0)
+            .label("Sync Universe")
+            .label_color(color::WHITE)
+            .label_font_size(14)
+            .down(5.0)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .color(color::DARK_CHARCOAL)
+            .set(ids.sacn_sync_universe_dialer, ui)
+        {
+            global_config.sacn_sync_universe = Some(v as u16);
+        }
+
+        if global_config.dmx_protocol == crate::conf::DmxProtocol::ArtNet {
+            widget::Text::new(
+                "ArtSync broadcast address, e.g. 192.168.0.255. Leave blank to guess it from the interface or destination.",
+            )
+            .down(5.0)
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.artnet_sync_ip_help_text, ui);
+
+            let color = match crate::conf::parse_sacn_interface_ip(&global_config.artnet_sync_ip) {
+                Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
+                Ok(None) => color::BLACK,
+                Err(_) => color::DARK_RED.with_luminance(0.1),
+            };
+            for event in widget::TextBox::new(&global_config.artnet_sync_ip)
+                .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+                .down(5.0)
+                .border(0.0)
+                .color(color)
+                .text_color(color::WHITE)
+                .font_size(14)
+                .set(ids.artnet_sync_ip_text_box, ui)
+            {
+                apply_text_box_event(&mut global_config.artnet_sync_ip, event);
+            }
+        }
+    }
+
+    if button()
+        .color(toggle_color(global_config.dmx_on))
+        .label("Enable DMX Output")
+        .w(WIDGET_W)
+        .mid_left_of(ids.column_1_id)
+        .down(COLUMN_ONE_SECTION_GAP)
+        .set(ids.dmx_button, ui)
+        .was_clicked()
+    {
+        global_config.dmx_on = !global_config.dmx_on;
+    }
+
+    let fade_frames = global_config.dmx_off_fade_frames as f32;
+    if let Some(v) = widget::NumberDialer::new(fade_frames, 0.0, 300.0, 0)
+        .border(0.0)
+        .label("DMX Off Fade Frames")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.dmx_off_fade_dialer, ui)
+    {
+        global_config.dmx_off_fade_frames = v as u32;
+    }
+
+    for _click in button()
+        .down(COLUMN_ONE_SECTION_GAP)
+        .label("Save Config")
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .color(BUTTON_COLOR)
+        .set(ids.save_config_button, ui)
+    {
+        super::save_global_config(assets, global_config);
+    }
+
+    // --- MadMapper Project section ---
+
+    text("MadMapper Project")
+        .mid_left_of(ids.column_1_id)
+        .down(COLUMN_ONE_SECTION_GAP)
+        .set(ids.madmapper_section_text, ui);
+
+    if has_mad_project {
+        let project = mad_project.as_ref().unwrap();
+        let filename = global_config
+            .madmapper_project_path
+            .as_deref()
+            .and_then(|p| std::path::Path::new(p).file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Unknown".into());
+
+        widget::Text::new(&filename)
+            .down(5.0)
+            .w(WIDGET_W)
+            .font_size(12)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.madmapper_filename_text, ui);
+
+        let (min_u, max_u) = project.universe_range();
+        let stats = format!(
+            "{} fixtures, {} pixels, {} universes (U{}-U{})",
+            project.fixtures.len(),
+            project.total_pixels(),
+            project.universe_count(),
+            min_u,
+            max_u,
+        );
+        widget::Text::new(&stats)
+            .down(3.0)
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.madmapper_stats_text, ui);
+
+        if button()
+            .color(color::DARK_RED.with_luminance(0.15))
+            .label("Remove")
+            .label_font_size(14)
+            .down(5.0)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .set(ids.madmapper_remove_button, ui)
+            .was_clicked()
+        {
+            *mad_project = None;
+            *resolved_layout = None;
+            global_config.madmapper_project_path = None;
+        }
+    } else {
+        let dialog_pending = pending_file_dialog.is_some();
+        let label = if dialog_pending {
+            "Waiting..."
+        } else {
+            "Load .mad File"
+        };
+        if button()
+            .color(BUTTON_COLOR)
+            .label(label)
+            .label_font_size(14)
+            .down(5.0)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .set(ids.madmapper_load_button, ui)
+            .was_clicked()
+            && !dialog_pending
+        {
+            let (tx, rx) = std::sync::mpsc::channel();
+            *pending_file_dialog = Some(rx);
+            std::thread::spawn(move || {
+                let result = rfd::FileDialog::new()
+                    .add_filter("MadMapper", &["mad"])
+                    .pick_file();
+                let _ = tx.send(result);
+            });
+        }
+
+        // Manual controls shown only when no MadMapper project is loaded.
+
+        text("Universes")
+            .mid_left_of(ids.column_1_id)
+            .down(COLUMN_ONE_SECTION_GAP)
+            .set(ids.universe_starts_text, ui);
+
+        let min_universe = 1.0;
+        let max_universe = 99.0;
+        let precision = 0;
+        let v = global_config.led_start_universe;
+        if let Some(v) = widget::NumberDialer::new(v as f32, min_universe, max_universe, precision)
+            .border(0.0)
+            .label("Start Universe")
+            .label_color(color::WHITE)
+            .label_font_size(14)
+            .down(PAD)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .color(color::DARK_CHARCOAL)
+            .set(ids.led_start_universe_dialer, ui)
+        {
+            global_config.led_start_universe = v as u16;
+        }
+
+        text("LED Layout")
+            .mid_left_of(ids.column_1_id)
+            .down(COLUMN_ONE_SECTION_GAP)
+            .set(ids.led_layout_text, ui);
+
+        if let Some(v) = widget::NumberDialer::new(
+            global_config.led_layout.leds_per_metre as f32,
+            1.0,
+            288.0,
+            precision,
+        )
+        .border(0.0)
+        .label("LEDs / Metre")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(PAD)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.led_pixels_per_metre_dialer, ui)
+        {
+            global_config.led_layout.leds_per_metre = v as usize;
+        }
+
+        if let Some(v) = widget::NumberDialer::new(
+            global_config.led_layout.metres_per_row as f32,
+            1.0,
+            32.0,
+            precision,
+        )
+        .border(0.0)
+        .label("Row Length (m)")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.led_metres_per_row_dialer, ui)
+        {
+            global_config.led_layout.metres_per_row = v as usize;
+        }
+
+        if let Some(v) = widget::NumberDialer::new(
+            global_config.led_layout.row_count as f32,
+            1.0,
+            32.0,
+            precision,
+        )
+        .border(0.0)
+        .label("Rows")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.led_row_count_dialer, ui)
+        {
+            global_config.led_layout.row_count = v as usize;
+        }
+
+        global_config.led_layout.normalise();
+
+        let total_leds = global_config.led_layout.led_count();
+        let slots_per_led = global_config.channel_layouts.default.channels().len()
+            * global_config.dmx_bit_depth.slots_per_channel();
+        let leds_per_universe = crate::DMX_ADDRS_PER_UNIVERSE as usize / slots_per_led;
+        let universe_count = ((total_leds.saturating_sub(1)) / leds_per_universe) + 1;
+        let start_universe = global_config.led_start_universe;
+        let end_universe = start_universe.saturating_add(universe_count.saturating_sub(1) as u16);
+        let layout_stats = format!(
+            "{} LEDs across {} universes (U{}-U{})",
+            total_leds, universe_count, start_universe, end_universe
+        );
+        widget::Text::new(&layout_stats)
+            .down(5.0)
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.led_layout_stats_text, ui);
+    }
+}
+
+fn set_output_monitor_widgets(
+    ui: &mut UiCell,
+    ids: &mut Ids,
+    global_config: &mut GlobalConfig,
+    sacn_output_monitor: &mut crate::SacnOutputMonitor,
+    sacn_error: Option<&str>,
+    sacn_transport_label: Option<&str>,
+    mad_project: Option<&crate::mad_mapper::MadProject>,
+    fixture_editor: &mut FixtureEditorState,
+    power_estimate: &crate::power::PowerEstimate,
+) {
+    widget::Text::new("sACN OUTPUT")
+        .top_left_of(ids.column_2_id)
+        .color(TEXT_COLOR)
+        .set(ids.sacn_output_title_text, ui);
+
+    let status_text = if !global_config.dmx_on {
+        "DMX output is disabled on the Live tab.".to_string()
+    } else if let Some(error) = sacn_error.or(sacn_output_monitor.last_send_error.as_deref()) {
+        format!(
+            "LED output: {} (Cap {})\nsACN error:\n{}",
+            format_measured_fps(
+                sacn_output_monitor.smoothed_frame_fps,
+                sacn_output_monitor.total_frames_sent
+            ),
+            global_config.led_output_fps.label(),
+            error
+        ) + &format_destination_status(sacn_output_monitor)
+    } else if let Some(last_sent_at) = sacn_output_monitor.last_sent_at {
+        format!(
+            "Route: {}\nLED output: {} (Cap {})\nLast send: {:.2}s ago\nFrames sent: {}\nPackets sent: {}\nPayload bytes: {}\n{}",
+            sacn_transport_label.unwrap_or("Unknown"),
+            format_measured_fps(
+                sacn_output_monitor.smoothed_frame_fps,
+                sacn_output_monitor.total_frames_sent
+            ),
+            global_config.led_output_fps.label(),
+            last_sent_at.elapsed().as_secs_f32(),
+            sacn_output_monitor.total_frames_sent,
+            sacn_output_monitor.total_packets_sent,
+            sacn_output_monitor.total_payload_bytes_sent,
+            format_sync_status(sacn_output_monitor)
+        ) + &format_destination_status(sacn_output_monitor)
+    } else {
+        format!(
+            "LED output: Waiting (Cap {})\nWaiting for the first successful sACN packet.",
+            global_config.led_output_fps.label()
+        )
+    };
+    widget::Text::new(&status_text)
+        .down(10.0)
+        .w(WIDGET_W)
+        .font_size(11)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_output_status_text, ui);
+
+    let power_status = format_power_status(&global_config.power_limit, power_estimate);
+    widget::Text::new(&power_status)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(11)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.power_status_text, ui);
+
+    widget::Text::new("Universe View")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.sacn_output_universe_text, ui);
+
+    let universe_labels = sacn_output_monitor.available_universe_labels();
+    let selected_universe = sacn_output_monitor.selected_universe_index();
+    if !universe_labels.is_empty() {
+        if let Some(selected_idx) = widget::DropDownList::new(&universe_labels, selected_universe)
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .max_visible_items(8)
+            .rgb(0.176, 0.513, 0.639)
+            .label("Last Sent Universe")
+            .label_font_size(14)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.sacn_output_universe_ddl, ui)
+        {
+            let _ = sacn_output_monitor.select_universe(selected_idx);
+        }
+
+        if let Some(universe) = sacn_output_monitor.selected_universe {
+            let priority = crate::universe_priority(&global_config.sacn_priorities, universe);
+            if let Some(v) = widget::NumberDialer::new(priority as f32, 0.0, 200.0, 0)
+                .border(0.0)
+                .label("Universe Priority")
+                .label_color(color::WHITE)
+                .label_font_size(14)
+                .down(5.0)
+                .w(WIDGET_W)
+                .h(DEFAULT_WIDGET_H)
+                .color(color::DARK_CHARCOAL)
+                .set(ids.sacn_output_priority_dialer, ui)
+            {
+                let priority = v as u8;
+                if priority == sacn::packet::E131_DEFAULT_PRIORITY {
+                    global_config.sacn_priorities.remove(&universe);
+                } else {
+                    global_config.sacn_priorities.insert(universe, priority);
+                }
+            }
+
+            if global_config.dmx_protocol == crate::conf::DmxProtocol::Sacn {
+                widget::Text::new("Unicast To (comma separated IPs, blank for multicast)")
+                    .down(5.0)
+                    .w(WIDGET_W)
+                    .font_size(10)
+                    .color(TEXT_COLOR)
+                    .left_justify()
+                    .set(ids.sacn_unicast_text, ui);
+
+                let destinations = global_config
+                    .sacn_unicast_destinations
+                    .get(&universe)
+                    .map(|destinations| destinations.join(","))
+                    .unwrap_or_default();
+                let color = match global_config.sacn_unicast_destinations.get(&universe) {
+                    None => color::BLACK,
+                    Some(destinations) => {
+                        match crate::conf::parse_unicast_destinations(destinations) {
+                            Ok(_) => color::DARK_GREEN.with_luminance(0.1),
+                            Err(_) => color::DARK_RED.with_luminance(0.1),
+                        }
+                    }
+                };
+                for event in widget::TextBox::new(&destinations)
+                    .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+                    .down(5.0)
+                    .border(0.0)
+                    .color(color)
+                    .text_color(color::WHITE)
+                    .font_size(14)
+                    .set(ids.sacn_unicast_text_box, ui)
+                {
+                    let mut destinations = destinations.clone();
+                    apply_text_box_event(&mut destinations, event);
+                    // Keep the entries as typed so partially entered addresses survive edits.
+                    let entries: Vec<String> =
+                        destinations.split(',').map(str::to_string).collect();
+                    if destinations.trim().is_empty() {
+                        global_config.sacn_unicast_destinations.remove(&universe);
+                    } else {
+                        global_config
+                            .sacn_unicast_destinations
+                            .insert(universe, entries);
+                    }
+                }
+            }
+        }
+    } else {
+        widget::Rectangle::fill([WIDGET_W, DEFAULT_WIDGET_H])
+            .down(5.0)
+            .color(color::DARK_CHARCOAL)
+            .set(ids.sacn_output_universe_placeholder, ui);
+    }
+
+    widget::Text::new("Channels 1-512 run left to right, top to bottom. Start code omitted.")
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_output_grid_help_text, ui);
+
+    widget::Rectangle::fill([WIDGET_W, 390.0])
+        .down(5.0)
+        .color(color::rgb(0.05, 0.05, 0.1))
+        .set(ids.sacn_output_grid_bg, ui);
+
+    let selected_snapshot = sacn_output_monitor.selected_universe_snapshot();
+    let data_slots = selected_snapshot
+        .map(|snapshot| snapshot.payload.get(1..).unwrap_or(&[]))
+        .unwrap_or(&[]);
+    draw_sacn_output_grid(ui, ids, data_slots);
+
+    let summary = match selected_snapshot {
+        Some(snapshot) => {
+            let data_slot_count = snapshot.payload.len().saturating_sub(1);
+            let non_zero_slots = data_slots.iter().filter(|&&value| value != 0).count();
+            format!(
+                "U{}: {} packets, {} slots, {} non-zero slots",
+                snapshot.universe, snapshot.packets_sent, data_slot_count, non_zero_slots,
+            )
+        }
+        None => "No successful sACN payload captured yet.".to_string(),
+    };
+    widget::Text::new(&summary)
+        .down_from(ids.sacn_output_grid_bg, 5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_output_grid_summary_text, ui);
 
-    ids.shader_state_text
+    let slot_preview = format_slot_preview(data_slots);
+    widget::Text::new(&slot_preview)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_output_slot_preview_text, ui);
+
+    set_fixture_output_widgets(ui, ids, global_config, mad_project, fixture_editor);
+    set_calibration_widgets(ui, ids, global_config, mad_project, fixture_editor);
+    set_power_limit_widgets(ui, ids, global_config, mad_project);
+    set_strobe_limit_widgets(ui, ids, global_config);
 }
 
-fn set_output_sidebar_widgets(
+fn set_dmx_capture_widgets(
     ui: &mut UiCell,
     ids: &Ids,
-    global_config: &mut GlobalConfig,
-    sacn_error: Option<&str>,
-    sacn_output_monitor: &crate::SacnOutputMonitor,
-    mad_project: &mut Option<crate::mad_mapper::MadProject>,
-    resolved_layout: &mut Option<crate::layout::ResolvedLayout>,
-    pending_file_dialog: &mut Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
     assets: &Path,
+    dmx_capture: &mut crate::dmx_recording::CaptureRequest,
+    dmx_capture_status: &crate::dmx_recording::CaptureStatus,
+    pending_replay_dialog: &mut Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
 ) {
-    let has_mad_project = mad_project.is_some();
-
-    text("LED Output FPS")
-        .mid_left_of(ids.column_1_id)
-        .down_from(ids.live_tab_button, PAD * 0.5)
-        .set(ids.output_fps_text, ui);
+    widget::Text::new("Record / Replay")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.dmx_capture_title_text, ui);
 
-    let output_fps_labels: Vec<_> = crate::conf::LedOutputFps::ALL
-        .iter()
-        .map(|mode| mode.label())
-        .collect();
-    let selected_output_fps = Some(global_config.led_output_fps.to_index());
-    if let Some(selected_idx) = widget::DropDownList::new(&output_fps_labels, selected_output_fps)
-        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
-        .down(5.0)
-        .max_visible_items(output_fps_labels.len())
-        .rgb(0.176, 0.513, 0.639)
-        .label("LED Output")
+    let recording = dmx_capture.record_path.is_some();
+    if button()
+        .color(toggle_color(recording))
+        .label(if recording {
+            "Stop Recording"
+        } else {
+            "Record Output"
+        })
         .label_font_size(14)
-        .label_rgb(1.0, 1.0, 1.0)
-        .scrollbar_on_top()
-        .set(ids.output_fps_ddl, ui)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.dmx_record_button, ui)
+        .was_clicked()
     {
-        if let Some(output_fps) = crate::conf::LedOutputFps::from_index(selected_idx) {
-            global_config.led_output_fps = output_fps;
-        }
+        dmx_capture.record_path = if recording {
+            None
+        } else {
+            let secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let file_name = format!("dmx-{}.{}", secs, crate::dmx_recording::FILE_EXTENSION);
+            Some(assets.join("recordings").join(file_name))
+        };
     }
 
-    let measured_output_fps = if global_config.dmx_on {
-        format_measured_fps(
-            sacn_output_monitor.smoothed_frame_fps,
-            sacn_output_monitor.total_frames_sent,
-        )
+    let dialog_pending = pending_replay_dialog.is_some();
+    let label = if dialog_pending {
+        "Waiting..."
     } else {
-        "Disabled".to_string()
+        "Replay File..."
     };
-    let output_fps_status = format!(
-        "LED Output: {} (Cap {})",
-        measured_output_fps,
-        global_config.led_output_fps.label()
-    );
-    widget::Text::new(&output_fps_status)
-        .down(5.0)
+    if button()
+        .color(BUTTON_COLOR)
+        .label(label)
+        .label_font_size(14)
         .w(WIDGET_W)
-        .font_size(10)
-        .color(TEXT_COLOR)
-        .left_justify()
-        .set(ids.output_fps_status_text, ui);
-
-    text("sACN Interface IP")
-        .mid_left_of(ids.column_1_id)
-        .down_from(ids.output_fps_status_text, COLUMN_ONE_SECTION_GAP)
-        .set(ids.sacn_interface_ip_text, ui);
-
-    widget::Text::new(
-        "Use this computer's IP on the PixLite network, e.g. 10.0.0.100. Leave blank for Auto; it falls back to localhost preview if multicast is unavailable.",
-    )
-    .down(5.0)
-    .w(WIDGET_W)
-    .font_size(10)
-    .color(TEXT_COLOR)
-    .left_justify()
-    .set(ids.sacn_interface_ip_help_text, ui);
-
-    let color = if sacn_error.is_some() {
-        color::DARK_RED.with_luminance(0.1)
-    } else {
-        match crate::conf::parse_sacn_interface_ip(&global_config.sacn_interface_ip) {
-            Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
-            Ok(None) => color::BLACK,
-            Err(_) => color::DARK_RED.with_luminance(0.1),
-        }
-    };
-    for event in widget::TextBox::new(&global_config.sacn_interface_ip)
-        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .h(DEFAULT_WIDGET_H)
         .down(5.0)
-        .border(0.0)
-        .color(color)
-        .text_color(color::WHITE)
-        .font_size(14)
-        .set(ids.sacn_interface_ip_text_box, ui)
+        .set(ids.dmx_replay_button, ui)
+        .was_clicked()
+        && !dialog_pending
     {
-        match event {
-            widget::text_box::Event::Update(string) => global_config.sacn_interface_ip = string,
-            widget::text_box::Event::Enter => {
-                global_config.sacn_interface_ip =
-                    global_config.sacn_interface_ip.trim().to_string();
-            }
-        }
-    }
-
-    if let Some(error) = sacn_error {
-        widget::Text::new(error)
-            .down(5.0)
-            .w(WIDGET_W)
-            .font_size(10)
-            .color(color::LIGHT_RED)
-            .left_justify()
-            .set(ids.sacn_interface_ip_error_text, ui);
+        let (tx, rx) = std::sync::mpsc::channel();
+        *pending_replay_dialog = Some(rx);
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("DMX Recording", &[crate::dmx_recording::FILE_EXTENSION])
+                .pick_file();
+            let _ = tx.send(result);
+        });
     }
 
     if button()
-        .color(toggle_color(global_config.dmx_on))
-        .label("Enable DMX Output")
+        .color(toggle_color(dmx_capture.replay_looping))
+        .label("Loop Replay")
+        .label_font_size(14)
         .w(WIDGET_W)
-        .mid_left_of(ids.column_1_id)
-        .down(COLUMN_ONE_SECTION_GAP)
-        .set(ids.dmx_button, ui)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.dmx_replay_loop_button, ui)
         .was_clicked()
     {
-        global_config.dmx_on = !global_config.dmx_on;
+        dmx_capture.replay_looping = !dmx_capture.replay_looping;
     }
 
-    for _click in button()
-        .down(COLUMN_ONE_SECTION_GAP)
-        .label("Save Config")
-        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+    if button()
         .color(BUTTON_COLOR)
-        .set(ids.save_config_button, ui)
+        .label("Stop Replay")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.dmx_replay_stop_button, ui)
+        .was_clicked()
     {
-        super::save_global_config(assets, global_config);
+        dmx_capture.replay_path = None;
     }
 
-    // --- MadMapper Project section ---
-
-    text("MadMapper Project")
-        .mid_left_of(ids.column_1_id)
-        .down(COLUMN_ONE_SECTION_GAP)
-        .set(ids.madmapper_section_text, ui);
+    let status = format_dmx_capture_status(dmx_capture, dmx_capture_status);
+    widget::Text::new(&status)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.dmx_capture_status_text, ui);
+}
 
-    if has_mad_project {
-        let project = mad_project.as_ref().unwrap();
-        let filename = global_config
-            .madmapper_project_path
-            .as_deref()
-            .and_then(|p| std::path::Path::new(p).file_name())
-            .map(|n| n.to_string_lossy().into_owned())
-            .unwrap_or_else(|| "Unknown".into());
+fn format_dmx_capture_status(
+    request: &crate::dmx_recording::CaptureRequest,
+    status: &crate::dmx_recording::CaptureStatus,
+) -> String {
+    let mut lines = Vec::new();
+    if let Some(path) = &request.record_path {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        lines.push(format!(
+            "Recording {}: {} frames",
+            file_name, status.recorded_frames
+        ));
+    }
+    if let Some((next, total)) = status.replay_progress {
+        lines.push(format!("Replaying frame {} of {}", next, total));
+    } else if let Some(total) = status.finished_replay {
+        lines.push(format!("Replay finished ({} frames)", total));
+    }
+    if let Some(error) = &status.error {
+        lines.push(error.clone());
+    }
+    if lines.is_empty() {
+        lines.push("Not recording.".to_string());
+    }
+    lines.join("\n")
+}
 
-        widget::Text::new(&filename)
-            .down(5.0)
-            .w(WIDGET_W)
-            .font_size(12)
-            .color(TEXT_COLOR)
-            .left_justify()
-            .set(ids.madmapper_filename_text, ui);
+fn set_sacn_input_widgets(
+    ui: &mut UiCell,
+    ids: &Ids,
+    global_config: &mut GlobalConfig,
+    sacn_input_status: &crate::SacnInputStatus,
+) {
+    let sacn_input = &mut global_config.sacn_input;
 
-        let (min_u, max_u) = project.universe_range();
-        let stats = format!(
-            "{} fixtures, {} pixels, {} universes (U{}-U{})",
-            project.fixtures.len(),
-            project.total_pixels(),
-            project.universe_count(),
-            min_u,
-            max_u,
-        );
-        widget::Text::new(&stats)
-            .down(3.0)
-            .w(WIDGET_W)
-            .font_size(10)
-            .color(TEXT_COLOR)
-            .left_justify()
-            .set(ids.madmapper_stats_text, ui);
+    widget::Text::new("sACN Input")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.sacn_input_title_text, ui);
 
-        if button()
-            .color(color::DARK_RED.with_luminance(0.15))
-            .label("Remove")
-            .label_font_size(14)
-            .down(5.0)
-            .w(WIDGET_W)
-            .h(DEFAULT_WIDGET_H)
-            .set(ids.madmapper_remove_button, ui)
-            .was_clicked()
-        {
-            *mad_project = None;
-            *resolved_layout = None;
-            global_config.madmapper_project_path = None;
-        }
-    } else {
-        let dialog_pending = pending_file_dialog.is_some();
-        let label = if dialog_pending {
-            "Waiting..."
-        } else {
-            "Load .mad File"
-        };
-        if button()
-            .color(BUTTON_COLOR)
-            .label(label)
-            .label_font_size(14)
-            .down(5.0)
-            .w(WIDGET_W)
-            .h(DEFAULT_WIDGET_H)
-            .set(ids.madmapper_load_button, ui)
-            .was_clicked()
-            && !dialog_pending
-        {
-            let (tx, rx) = std::sync::mpsc::channel();
-            *pending_file_dialog = Some(rx);
-            std::thread::spawn(move || {
-                let result = rfd::FileDialog::new()
-                    .add_filter("MadMapper", &["mad"])
-                    .pick_file();
-                let _ = tx.send(result);
-            });
+    if button()
+        .color(toggle_color(sacn_input.enabled))
+        .label("Merge sACN Input")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.sacn_input_button, ui)
+        .was_clicked()
+    {
+        sacn_input.enabled = !sacn_input.enabled;
+    }
+
+    let merge_labels: Vec<_> = crate::conf::MergeMode::ALL
+        .iter()
+        .map(|mode| mode.label())
+        .collect();
+    let selected_merge = Some(sacn_input.merge_mode.to_index());
+    if let Some(selected_idx) = widget::DropDownList::new(&merge_labels, selected_merge)
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .down(5.0)
+        .max_visible_items(merge_labels.len())
+        .rgb(0.176, 0.513, 0.639)
+        .label("Merge Mode")
+        .label_font_size(14)
+        .label_rgb(1.0, 1.0, 1.0)
+        .scrollbar_on_top()
+        .set(ids.sacn_input_merge_ddl, ui)
+    {
+        if let Some(mode) = crate::conf::MergeMode::from_index(selected_idx) {
+            sacn_input.merge_mode = mode;
         }
+    }
 
-        // Manual controls shown only when no MadMapper project is loaded.
+    // Universes are edited as a contiguous range.
+    let start = sacn_input.universes.iter().copied().min().unwrap_or(1);
+    let count = sacn_input.universes.len();
+    let mut range = None;
+    if let Some(v) = widget::NumberDialer::new(start as f32, 1.0, 63999.0, 0)
+        .border(0.0)
+        .label("First Input Universe")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.sacn_input_start_dialer, ui)
+    {
+        range = Some((v as u16, count.max(1)));
+    }
+    if let Some(v) = widget::NumberDialer::new(count as f32, 0.0, 64.0, 0)
+        .border(0.0)
+        .label("Input Universe Count")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.sacn_input_count_dialer, ui)
+    {
+        range = Some((start, v as usize));
+    }
+    if let Some((start, count)) = range {
+        sacn_input.universes = (0..count)
+            .filter_map(|offset| start.checked_add(offset as u16))
+            .collect();
+    }
 
-        text("Universes")
-            .mid_left_of(ids.column_1_id)
-            .down(COLUMN_ONE_SECTION_GAP)
-            .set(ids.universe_starts_text, ui);
+    if button()
+        .color(toggle_color(sacn_input.control_universe.is_some()))
+        .label("Console Control")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.sacn_control_button, ui)
+        .was_clicked()
+    {
+        sacn_input.control_universe = match sacn_input.control_universe {
+            Some(_) => None,
+            None => Some(start),
+        };
+    }
 
-        let min_universe = 1.0;
-        let max_universe = 99.0;
-        let precision = 0;
-        let v = global_config.led_start_universe;
-        if let Some(v) = widget::NumberDialer::new(v as f32, min_universe, max_universe, precision)
+    if let Some(control_universe) = sacn_input.control_universe {
+        if let Some(v) = widget::NumberDialer::new(control_universe as f32, 1.0, 63999.0, 0)
             .border(0.0)
-            .label("Start Universe")
+            .label("Control Universe")
             .label_color(color::WHITE)
             .label_font_size(14)
-            .down(PAD)
+            .down(5.0)
             .w(WIDGET_W)
             .h(DEFAULT_WIDGET_H)
             .color(color::DARK_CHARCOAL)
-            .set(ids.led_start_universe_dialer, ui)
+            .set(ids.sacn_control_universe_dialer, ui)
         {
-            global_config.led_start_universe = v as u16;
+            sacn_input.control_universe = Some(v as u16);
         }
+    }
 
-        text("LED Layout")
-            .mid_left_of(ids.column_1_id)
-            .down(COLUMN_ONE_SECTION_GAP)
-            .set(ids.led_layout_text, ui);
+    widget::Text::new(
+        "Incoming levels are merged into matching output universes. Control universe channels are mapped and learned on the MIDI tab. Listens on UDP 5568, so a localhost previs can't run on this machine at the same time.",
+    )
+    .down(5.0)
+    .w(WIDGET_W)
+    .font_size(10)
+    .color(TEXT_COLOR)
+    .left_justify()
+    .set(ids.sacn_input_help_text, ui);
+
+    let status = if let Some(error) = &sacn_input_status.error {
+        error.clone()
+    } else if !sacn_input_status.listening {
+        "Not listening.".to_string()
+    } else if sacn_input_status.active_universes.is_empty() {
+        "Listening, no sources.".to_string()
+    } else {
+        let universes: Vec<_> = sacn_input_status
+            .active_universes
+            .iter()
+            .map(|universe| format!("U{}", universe))
+            .collect();
+        format!("Receiving: {}", universes.join(", "))
+    };
+    widget::Text::new(&status)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_input_status_text, ui);
+}
 
-        if let Some(v) = widget::NumberDialer::new(
-            global_config.led_layout.leds_per_metre as f32,
-            1.0,
-            288.0,
-            precision,
-        )
-        .border(0.0)
-        .label("LEDs / Metre")
-        .label_color(color::WHITE)
+fn set_sacn_discovery_widgets(
+    ui: &mut UiCell,
+    ids: &Ids,
+    global_config: &mut GlobalConfig,
+    sacn_input_status: &crate::SacnInputStatus,
+    sacn_output_monitor: &crate::SacnOutputMonitor,
+) {
+    let sacn_input = &mut global_config.sacn_input;
+
+    widget::Text::new("sACN Sources")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.sacn_discovery_title_text, ui);
+
+    if button()
+        .color(toggle_color(sacn_input.discover_sources))
+        .label("Discover Sources")
         .label_font_size(14)
-        .down(PAD)
         .w(WIDGET_W)
         .h(DEFAULT_WIDGET_H)
-        .color(color::DARK_CHARCOAL)
-        .set(ids.led_pixels_per_metre_dialer, ui)
-        {
-            global_config.led_layout.leds_per_metre = v as usize;
-        }
+        .down(5.0)
+        .set(ids.sacn_discovery_button, ui)
+        .was_clicked()
+    {
+        sacn_input.discover_sources = !sacn_input.discover_sources;
+    }
 
-        if let Some(v) = widget::NumberDialer::new(
-            global_config.led_layout.metres_per_row as f32,
-            1.0,
-            32.0,
-            precision,
-        )
+    let status = if !sacn_input.discover_sources {
+        "Not browsing.".to_string()
+    } else if let Some(error) = &sacn_input_status.error {
+        error.clone()
+    } else {
+        let output_universes: Vec<u16> = sacn_output_monitor
+            .universes
+            .iter()
+            .map(|snapshot| snapshot.universe)
+            .collect();
+        format_discovered_sources(&sacn_input_status.discovered_sources, &output_universes)
+    };
+    widget::Text::new(&status)
+        .down(5.0)
+        .w(WIDGET_W)
+        .font_size(10)
+        .color(TEXT_COLOR)
+        .left_justify()
+        .set(ids.sacn_discovery_status_text, ui);
+}
+
+fn set_strobe_limit_widgets(ui: &mut UiCell, ids: &Ids, global_config: &mut GlobalConfig) {
+    let strobe_limit = &mut global_config.strobe_limit;
+
+    widget::Text::new("Strobe Safety")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.strobe_limit_title_text, ui);
+
+    if button()
+        .color(toggle_color(strobe_limit.enabled))
+        .label("Limit Flash Rate")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.strobe_limit_button, ui)
+        .was_clicked()
+    {
+        strobe_limit.enabled = !strobe_limit.enabled;
+    }
+
+    if let Some(v) = widget::NumberDialer::new(strobe_limit.max_flash_hz, 0.5, 10.0, 1)
         .border(0.0)
-        .label("Row Length (m)")
+        .label("Max Flashes / Sec")
         .label_color(color::WHITE)
         .label_font_size(14)
         .down(5.0)
         .w(WIDGET_W)
         .h(DEFAULT_WIDGET_H)
         .color(color::DARK_CHARCOAL)
-        .set(ids.led_metres_per_row_dialer, ui)
-        {
-            global_config.led_layout.metres_per_row = v as usize;
-        }
+        .set(ids.strobe_max_hz_dialer, ui)
+    {
+        strobe_limit.max_flash_hz = v;
+    }
 
-        if let Some(v) = widget::NumberDialer::new(
-            global_config.led_layout.row_count as f32,
-            1.0,
-            32.0,
-            precision,
-        )
+    if let Some(v) = widget::NumberDialer::new(strobe_limit.area_fraction * 100.0, 5.0, 100.0, 0)
         .border(0.0)
-        .label("Rows")
+        .label("Flash Area %")
         .label_color(color::WHITE)
         .label_font_size(14)
         .down(5.0)
         .w(WIDGET_W)
         .h(DEFAULT_WIDGET_H)
         .color(color::DARK_CHARCOAL)
-        .set(ids.led_row_count_dialer, ui)
-        {
-            global_config.led_layout.row_count = v as usize;
-        }
-
-        global_config.led_layout.normalise();
-
-        let total_leds = global_config.led_layout.led_count();
-        let leds_per_universe =
-            (crate::DMX_ADDRS_PER_UNIVERSE as usize - 2) / crate::DMX_ADDRS_PER_LED as usize;
-        let universe_count = ((total_leds.saturating_sub(1)) / leds_per_universe) + 1;
-        let start_universe = global_config.led_start_universe;
-        let end_universe = start_universe.saturating_add(universe_count.saturating_sub(1) as u16);
-        let layout_stats = format!(
-            "{} LEDs across {} universes (U{}-U{})",
-            total_leds, universe_count, start_universe, end_universe
-        );
-        widget::Text::new(&layout_stats)
-            .down(5.0)
-            .w(WIDGET_W)
-            .font_size(10)
-            .color(TEXT_COLOR)
-            .left_justify()
-            .set(ids.led_layout_stats_text, ui);
+        .set(ids.strobe_area_dialer, ui)
+    {
+        strobe_limit.area_fraction = v / 100.0;
     }
 }
 
-fn set_output_monitor_widgets(
+fn set_power_limit_widgets(
     ui: &mut UiCell,
     ids: &mut Ids,
-    global_config: &GlobalConfig,
-    sacn_output_monitor: &mut crate::SacnOutputMonitor,
-    sacn_error: Option<&str>,
-    sacn_transport_label: Option<&str>,
+    global_config: &mut GlobalConfig,
+    mad_project: Option<&crate::mad_mapper::MadProject>,
 ) {
-    widget::Text::new("sACN OUTPUT")
-        .top_left_of(ids.column_2_id)
+    let power_limit = &mut global_config.power_limit;
+
+    widget::Text::new("Power Limit")
+        .down(10.0)
         .color(TEXT_COLOR)
-        .set(ids.sacn_output_title_text, ui);
+        .font_size(12)
+        .set(ids.power_limit_title_text, ui);
 
-    let status_text = if !global_config.dmx_on {
-        "DMX output is disabled on the Live tab.".to_string()
-    } else if let Some(error) = sacn_error.or(sacn_output_monitor.last_send_error.as_deref()) {
-        format!(
-            "LED output: {} (Cap {})\nsACN error:\n{}",
-            format_measured_fps(
-                sacn_output_monitor.smoothed_frame_fps,
-                sacn_output_monitor.total_frames_sent
-            ),
-            global_config.led_output_fps.label(),
-            error
-        )
-    } else if let Some(last_sent_at) = sacn_output_monitor.last_sent_at {
-        format!(
-            "Route: {}\nLED output: {} (Cap {})\nLast send: {:.2}s ago\nFrames sent: {}\nPackets sent: {}\nPayload bytes: {}",
-            sacn_transport_label.unwrap_or("Unknown"),
-            format_measured_fps(
-                sacn_output_monitor.smoothed_frame_fps,
-                sacn_output_monitor.total_frames_sent
+    if button()
+        .color(toggle_color(power_limit.enabled))
+        .label("Limit Brightness To Budget")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.power_limit_button, ui)
+        .was_clicked()
+    {
+        power_limit.enabled = !power_limit.enabled;
+    }
+
+    if let Some(v) = widget::NumberDialer::new(power_limit.milliamps_per_channel, 1.0, 100.0, 1)
+        .border(0.0)
+        .label("mA / Channel")
+        .label_color(color::WHITE)
+        .label_font_size(14)
+        .down(5.0)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .color(color::DARK_CHARCOAL)
+        .set(ids.power_milliamps_dialer, ui)
+    {
+        power_limit.milliamps_per_channel = v;
+    }
+
+    let group_count = power_limit.groups.len();
+    if ids.power_group_budget_dialers.len() < group_count {
+        ids.power_group_budget_dialers
+            .resize(group_count, &mut ui.widget_id_generator());
+    }
+    for (group, &id) in power_limit
+        .groups
+        .iter_mut()
+        .zip(ids.power_group_budget_dialers.iter())
+    {
+        let label = format!("{} Budget (A)", group.name);
+        if let Some(v) = widget::NumberDialer::new(group.budget_amps, 0.0, 200.0, 1)
+            .border(0.0)
+            .label(&label)
+            .label_color(color::WHITE)
+            .label_font_size(14)
+            .down(5.0)
+            .w(WIDGET_W)
+            .h(DEFAULT_WIDGET_H)
+            .color(color::DARK_CHARCOAL)
+            .set(id, ui)
+        {
+            group.budget_amps = v;
+        }
+    }
+
+    // New groups cover the whole current layout. Split them up in the config file.
+    if button()
+        .color(BUTTON_COLOR)
+        .label("Add PSU Group")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.power_add_group_button, ui)
+        .was_clicked()
+    {
+        let (fixtures, rows) = match mad_project {
+            Some(project) => (
+                project.fixtures.iter().map(|f| f.name.clone()).collect(),
+                Vec::new(),
             ),
-            global_config.led_output_fps.label(),
-            last_sent_at.elapsed().as_secs_f32(),
-            sacn_output_monitor.total_frames_sent,
-            sacn_output_monitor.total_packets_sent,
-            sacn_output_monitor.total_payload_bytes_sent
-        )
-    } else {
-        format!(
-            "LED output: Waiting (Cap {})\nWaiting for the first successful sACN packet.",
-            global_config.led_output_fps.label()
-        )
-    };
-    widget::Text::new(&status_text)
-        .down(10.0)
+            None => (
+                Vec::new(),
+                (0..global_config.led_layout.row_count).collect(),
+            ),
+        };
+        let power_limit = &mut global_config.power_limit;
+        power_limit.groups.push(crate::conf::PsuGroup {
+            name: format!("PSU {}", power_limit.groups.len() + 1),
+            budget_amps: crate::conf::default::power_limit::budget_amps(),
+            fixtures,
+            rows,
+        });
+    }
+
+    let help = "Group membership (fixture names or row indices) is edited in the config file.";
+    widget::Text::new(help)
+        .down(5.0)
         .w(WIDGET_W)
-        .font_size(11)
+        .font_size(10)
         .color(TEXT_COLOR)
         .left_justify()
-        .set(ids.sacn_output_status_text, ui);
+        .set(ids.power_limit_help_text, ui);
+}
 
-    widget::Text::new("Universe View")
+/// A text box for an OPC or DDP address, tinted by whether it parses.
+fn pixel_destination_text_box(destination: &str) -> widget::TextBox {
+    let color = match crate::conf::parse_pixel_destination(destination, 0) {
+        Ok(Some(_)) => color::DARK_GREEN.with_luminance(0.1),
+        Ok(None) => color::BLACK,
+        Err(_) => color::DARK_RED.with_luminance(0.1),
+    };
+    widget::TextBox::new(destination)
+        .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+        .border(0.0)
+        .color(color)
+        .text_color(color::WHITE)
+        .font_size(14)
+}
+
+fn apply_text_box_event(value: &mut String, event: widget::text_box::Event) {
+    match event {
+        widget::text_box::Event::Update(string) => *value = string,
+        widget::text_box::Event::Enter => *value = value.trim().to_string(),
+    }
+}
+
+fn set_fixture_output_widgets(
+    ui: &mut UiCell,
+    ids: &Ids,
+    global_config: &mut GlobalConfig,
+    mad_project: Option<&crate::mad_mapper::MadProject>,
+    fixture_editor: &mut FixtureEditorState,
+) {
+    widget::Text::new("Fixture Output")
         .down(10.0)
         .color(TEXT_COLOR)
         .font_size(12)
-        .set(ids.sacn_output_universe_text, ui);
+        .set(ids.fixture_output_title_text, ui);
 
-    let universe_labels = sacn_output_monitor.available_universe_labels();
-    let selected_universe = sacn_output_monitor.selected_universe_index();
-    if !universe_labels.is_empty() {
-        if let Some(selected_idx) = widget::DropDownList::new(&universe_labels, selected_universe)
+    let order_labels: Vec<_> = crate::conf::ChannelOrder::ALL
+        .iter()
+        .map(|order| order.label())
+        .collect();
+    let default_order = global_config.channel_layouts.default;
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&order_labels, Some(default_order.to_index()))
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .max_visible_items(6)
+            .rgb(0.176, 0.513, 0.639)
+            .label("Default Channel Order")
+            .label_font_size(14)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.default_channel_order_ddl, ui)
+    {
+        if let Some(order) = crate::conf::ChannelOrder::from_index(selected_idx) {
+            global_config.channel_layouts.default = order;
+        }
+    }
+
+    let bit_depth_labels: Vec<_> = crate::conf::DmxBitDepth::ALL
+        .iter()
+        .map(|depth| depth.label())
+        .collect();
+    if let Some(selected_idx) = widget::DropDownList::new(
+        &bit_depth_labels,
+        Some(global_config.dmx_bit_depth.to_index()),
+    )
+    .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+    .down(5.0)
+    .rgb(0.176, 0.513, 0.639)
+    .label("Bit Depth")
+    .label_font_size(14)
+    .label_rgb(1.0, 1.0, 1.0)
+    .set(ids.dmx_bit_depth_ddl, ui)
+    {
+        if let Some(depth) = crate::conf::DmxBitDepth::from_index(selected_idx) {
+            global_config.dmx_bit_depth = depth;
+        }
+    }
+
+    if button()
+        .color(toggle_color(global_config.led_dither))
+        .label("Temporal Dither")
+        .label_font_size(14)
+        .w(WIDGET_W)
+        .h(DEFAULT_WIDGET_H)
+        .down(5.0)
+        .set(ids.led_dither_button, ui)
+        .was_clicked()
+    {
+        global_config.led_dither = !global_config.led_dither;
+    }
+
+    let Some(project) = mad_project.filter(|project| !project.fixtures.is_empty()) else {
+        return;
+    };
+
+    fixture_editor.selected_fixture = fixture_editor
+        .selected_fixture
+        .min(project.fixtures.len() - 1);
+    let fixture_labels: Vec<_> = project
+        .fixtures
+        .iter()
+        .map(|fixture| fixture.name.as_str())
+        .collect();
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&fixture_labels, Some(fixture_editor.selected_fixture))
             .w_h(WIDGET_W, DEFAULT_WIDGET_H)
             .down(5.0)
             .max_visible_items(8)
             .rgb(0.176, 0.513, 0.639)
-            .label("Last Sent Universe")
+            .label("Fixture")
             .label_font_size(14)
             .label_rgb(1.0, 1.0, 1.0)
             .scrollbar_on_top()
-            .set(ids.sacn_output_universe_ddl, ui)
-        {
-            let _ = sacn_output_monitor.select_universe(selected_idx);
+            .set(ids.fixture_output_ddl, ui)
+    {
+        fixture_editor.selected_fixture = selected_idx;
+    }
+
+    let fixture = &project.fixtures[fixture_editor.selected_fixture];
+    let fixture_order = global_config.channel_layouts.for_fixture(&fixture.name);
+    if let Some(selected_idx) =
+        widget::DropDownList::new(&order_labels, Some(fixture_order.to_index()))
+            .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+            .down(5.0)
+            .max_visible_items(6)
+            .rgb(0.176, 0.513, 0.639)
+            .label("Fixture Channel Order")
+            .label_font_size(14)
+            .label_rgb(1.0, 1.0, 1.0)
+            .scrollbar_on_top()
+            .set(ids.fixture_channel_order_ddl, ui)
+    {
+        if let Some(order) = crate::conf::ChannelOrder::from_index(selected_idx) {
+            // Only store overrides that differ from the default to keep the config tidy.
+            if order == global_config.channel_layouts.default {
+                global_config.channel_layouts.fixtures.remove(&fixture.name);
+            } else {
+                global_config
+                    .channel_layouts
+                    .fixtures
+                    .insert(fixture.name.clone(), order);
+            }
         }
-    } else {
-        widget::Rectangle::fill([WIDGET_W, DEFAULT_WIDGET_H])
+    }
+
+    if global_config.dmx_protocol.sends_pixel_frames() {
+        widget::Text::new("Fixture Destination (blank uses the default)")
             .down(5.0)
-            .color(color::DARK_CHARCOAL)
-            .set(ids.sacn_output_universe_placeholder, ui);
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.fixture_pixel_destination_text, ui);
+
+        let destinations = &mut global_config.pixel_destinations.fixtures;
+        let mut destination = destinations.get(&fixture.name).cloned().unwrap_or_default();
+        for event in pixel_destination_text_box(&destination)
+            .down(5.0)
+            .set(ids.fixture_pixel_destination_text_box, ui)
+        {
+            apply_text_box_event(&mut destination, event);
+            if destination.is_empty() {
+                destinations.remove(&fixture.name);
+            } else {
+                destinations.insert(fixture.name.clone(), destination.clone());
+            }
+        }
     }
 
-    widget::Text::new("Channels 1-512 run left to right, top to bottom. Start code omitted.")
+    let profile_labels = calibration_assignment_labels(&global_config.calibration);
+    let assigned_profile = global_config.calibration.fixtures.get(&fixture.name);
+    if let Some(selected_idx) = widget::DropDownList::new(
+        &profile_labels,
+        Some(calibration_assignment_index(
+            &profile_labels,
+            assigned_profile,
+        )),
+    )
+    .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+    .down(5.0)
+    .max_visible_items(6)
+    .rgb(0.176, 0.513, 0.639)
+    .label("Fixture Calibration")
+    .label_font_size(14)
+    .label_rgb(1.0, 1.0, 1.0)
+    .scrollbar_on_top()
+    .set(ids.fixture_calibration_ddl, ui)
+    {
+        match selected_idx {
+            0 => {
+                global_config.calibration.fixtures.remove(&fixture.name);
+            }
+            ix => {
+                global_config
+                    .calibration
+                    .fixtures
+                    .insert(fixture.name.clone(), profile_labels[ix].clone());
+            }
+        }
+    }
+
+    let channels_per_pixel = (fixture.channels_per_pixel as usize)
+        .max(fixture_order.channels().len() * global_config.dmx_bit_depth.slots_per_channel());
+    let pixels_per_universe = crate::DMX_ADDRS_PER_UNIVERSE as usize / channels_per_pixel;
+    let stats = format!(
+        "U{} ch{}: {} pixels, {} ch/pixel, {} pixels per universe",
+        fixture.universe,
+        fixture.start_channel,
+        fixture.pixel_count,
+        channels_per_pixel,
+        pixels_per_universe,
+    );
+    widget::Text::new(&stats)
         .down(5.0)
         .w(WIDGET_W)
         .font_size(10)
         .color(TEXT_COLOR)
         .left_justify()
-        .set(ids.sacn_output_grid_help_text, ui);
+        .set(ids.fixture_output_stats_text, ui);
+}
 
-    widget::Rectangle::fill([WIDGET_W, 390.0])
-        .down(5.0)
-        .color(color::rgb(0.05, 0.05, 0.1))
-        .set(ids.sacn_output_grid_bg, ui);
+fn set_calibration_widgets(
+    ui: &mut UiCell,
+    ids: &Ids,
+    global_config: &mut GlobalConfig,
+    mad_project: Option<&crate::mad_mapper::MadProject>,
+    fixture_editor: &mut FixtureEditorState,
+) {
+    widget::Text::new("Calibration")
+        .down(10.0)
+        .color(TEXT_COLOR)
+        .font_size(12)
+        .set(ids.calibration_title_text, ui);
+
+    let calibration = &mut global_config.calibration;
+    let profile_names: Vec<String> = calibration.profiles.keys().cloned().collect();
+    if !profile_names.is_empty() {
+        fixture_editor.selected_profile =
+            fixture_editor.selected_profile.min(profile_names.len() - 1);
+        if let Some(selected_idx) =
+            widget::DropDownList::new(&profile_names, Some(fixture_editor.selected_profile))
+                .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+                .down(5.0)
+                .max_visible_items(6)
+                .rgb(0.176, 0.513, 0.639)
+                .label("Profile")
+                .label_font_size(14)
+                .label_rgb(1.0, 1.0, 1.0)
+                .scrollbar_on_top()
+                .set(ids.calibration_profile_ddl, ui)
+        {
+            fixture_editor.selected_profile = selected_idx;
+        }
+    }
 
-    let selected_snapshot = sacn_output_monitor.selected_universe_snapshot();
-    let data_slots = selected_snapshot
-        .map(|snapshot| snapshot.payload.get(1..).unwrap_or(&[]))
-        .unwrap_or(&[]);
-    draw_sacn_output_grid(ui, ids, data_slots);
+    let half_w = (WIDGET_W - PAD * 0.25) * 0.5;
+    let new_button = button()
+        .label("New Profile")
+        .label_font_size(14)
+        .w(half_w)
+        .h(DEFAULT_WIDGET_H)
+        .color(BUTTON_COLOR);
+    let new_button = if profile_names.is_empty() {
+        new_button.down(5.0)
+    } else {
+        new_button.down_from(ids.calibration_profile_ddl, 5.0)
+    };
+    if new_button.set(ids.calibration_new_button, ui).was_clicked() {
+        let name = calibration.add_profile();
+        if let Some(ix) = calibration.profiles.keys().position(|n| *n == name) {
+            fixture_editor.selected_profile = ix;
+        }
+    }
 
-    let summary = match selected_snapshot {
-        Some(snapshot) => {
-            let data_slot_count = snapshot.payload.len().saturating_sub(1);
-            let pad_bytes = if data_slot_count == crate::DMX_ADDRS_PER_UNIVERSE as usize {
-                2
-            } else {
-                0
-            };
-            let rgb_pixels = data_slot_count.saturating_sub(pad_bytes) / 3;
-            let non_zero_slots = data_slots.iter().filter(|&&value| value != 0).count();
+    let selected_name = profile_names.get(fixture_editor.selected_profile);
+    if button()
+        .label("Delete")
+        .label_font_size(14)
+        .w(half_w)
+        .h(DEFAULT_WIDGET_H)
+        .right(PAD * 0.25)
+        .color(Color::Rgba(0.5, 0.15, 0.15, 1.0))
+        .set(ids.calibration_delete_button, ui)
+        .was_clicked()
+    {
+        if let Some(name) = selected_name {
+            calibration.remove_profile(name);
+        }
+    }
+
+    if let Some(profile) = selected_name.and_then(|name| calibration.profiles.get_mut(name)) {
+        if let Some(value) = slider(profile.gamma, 0.5, 3.0)
+            .w(WIDGET_W)
+            .mid_left_of(ids.column_2_id)
+            .down_from(ids.calibration_new_button, 5.0)
+            .label(&format!("Gamma {:.2}", profile.gamma))
+            .set(ids.calibration_gamma_slider, ui)
+        {
+            profile.gamma = value;
+        }
+
+        let gain_sliders = [
+            ("Red Gain", ids.calibration_red_gain_slider),
+            ("Green Gain", ids.calibration_green_gain_slider),
+            ("Blue Gain", ids.calibration_blue_gain_slider),
+        ];
+        for (channel, (name, id)) in gain_sliders.iter().enumerate() {
+            let gain = profile.white_balance[channel];
+            if let Some(value) = slider(gain, 0.0, 1.0)
+                .w(WIDGET_W)
+                .down(5.0)
+                .label(&format!("{} {:.2}", name, gain))
+                .set(*id, ui)
+            {
+                profile.white_balance[channel] = value;
+            }
+        }
+
+        if let Some(value) = slider(profile.max_level, 0.0, 1.0)
+            .w(WIDGET_W)
+            .down(5.0)
+            .label(&format!("Max Level {:.0}%", profile.max_level * 100.0))
+            .set(ids.calibration_max_level_slider, ui)
+        {
+            profile.max_level = value;
+        }
+
+        let lut_text = if profile.lut.len() >= 2 {
             format!(
-                "U{}: {} packets, {} slots, {} RGB pixels, {} non-zero slots{}",
-                snapshot.universe,
-                snapshot.packets_sent,
-                data_slot_count,
-                rgb_pixels,
-                non_zero_slots,
-                if pad_bytes > 0 {
-                    ", 2 pad bytes reserved for RGB alignment"
-                } else {
-                    ""
-                }
+                "Curve: {}-point LUT from the config file (gamma ignored)",
+                profile.lut.len()
             )
+        } else {
+            "Curve: gamma. Add a `lut` in the config file to use a lookup table.".to_string()
+        };
+        widget::Text::new(&lut_text)
+            .down(5.0)
+            .w(WIDGET_W)
+            .font_size(10)
+            .color(TEXT_COLOR)
+            .left_justify()
+            .set(ids.calibration_lut_text, ui);
+    }
+
+    // MadMapper fixtures are assigned in the Fixture Output section above.
+    if mad_project.is_some() {
+        return;
+    }
+
+    let row_count = global_config.led_layout.row_count.max(1);
+    fixture_editor.selected_row = fixture_editor.selected_row.min(row_count - 1);
+    if let Some(v) = widget::NumberDialer::new(
+        (fixture_editor.selected_row + 1) as f32,
+        1.0,
+        row_count as f32,
+        0,
+    )
+    .border(0.0)
+    .label("Row")
+    .label_color(color::WHITE)
+    .label_font_size(14)
+    .w(WIDGET_W)
+    .h(DEFAULT_WIDGET_H)
+    .mid_left_of(ids.column_2_id)
+    .down(5.0)
+    .color(color::DARK_CHARCOAL)
+    .set(ids.calibration_row_dialer, ui)
+    {
+        fixture_editor.selected_row = (v as usize).saturating_sub(1);
+    }
+
+    let calibration = &mut global_config.calibration;
+    let profile_labels = calibration_assignment_labels(calibration);
+    let row = fixture_editor.selected_row;
+    if let Some(selected_idx) = widget::DropDownList::new(
+        &profile_labels,
+        Some(calibration_assignment_index(
+            &profile_labels,
+            calibration.rows.get(&row),
+        )),
+    )
+    .w_h(WIDGET_W, DEFAULT_WIDGET_H)
+    .down(5.0)
+    .max_visible_items(6)
+    .rgb(0.176, 0.513, 0.639)
+    .label("Row Calibration")
+    .label_font_size(14)
+    .label_rgb(1.0, 1.0, 1.0)
+    .scrollbar_on_top()
+    .set(ids.calibration_row_ddl, ui)
+    {
+        match selected_idx {
+            0 => {
+                calibration.rows.remove(&row);
+            }
+            ix => {
+                calibration.rows.insert(row, profile_labels[ix].clone());
+            }
         }
-        None => "No successful sACN payload captured yet.".to_string(),
-    };
-    widget::Text::new(&summary)
-        .down_from(ids.sacn_output_grid_bg, 5.0)
-        .w(WIDGET_W)
-        .font_size(10)
-        .color(TEXT_COLOR)
-        .left_justify()
-        .set(ids.sacn_output_grid_summary_text, ui);
+    }
+}
 
-    let slot_preview = format_slot_preview(data_slots);
-    widget::Text::new(&slot_preview)
-        .down(5.0)
-        .w(WIDGET_W)
-        .font_size(10)
-        .color(TEXT_COLOR)
-        .left_justify()
-        .set(ids.sacn_output_slot_preview_text, ui);
+/// "None" followed by every profile name, for profile assignment dropdowns.
+fn calibration_assignment_labels(calibration: &crate::conf::Calibration) -> Vec<String> {
+    std::iter::once("None".to_string())
+        .chain(calibration.profiles.keys().cloned())
+        .collect()
+}
+
+fn calibration_assignment_index(labels: &[String], assigned: Option<&String>) -> usize {
+    assigned
+        .and_then(|name| labels.iter().skip(1).position(|label| label == name))
+        .map_or(0, |ix| ix + 1)
 }
 
 fn draw_sacn_output_grid(ui: &mut UiCell, ids: &mut Ids, data_slots: &[u8]) {
@@ -2681,6 +2727,132 @@ fn format_slot_preview(data_slots: &[u8]) -> String {
     format!("Slots 1-8: {}\nSlots 9-16: {}", first_eight, second_eight)
 }
 
+fn format_power_status(
+    power_limit: &crate::conf::PowerLimit,
+    estimate: &crate::power::PowerEstimate,
+) -> String {
+    let mut status = format!("Power: {:.1} A estimated", estimate.total_amps);
+    if power_limit.groups.is_empty() {
+        status.push_str(" (no PSU groups)");
+    }
+    for group in &estimate.groups {
+        status.push_str(&format!(
+            "\n{}: {:.1} A / {:.1} A",
+            group.name, group.amps, group.budget_amps
+        ));
+        if group.scale < 0.995 {
+            status.push_str(&format!(" (limited to {:.0}%)", group.scale * 100.0));
+        } else if group.amps > group.budget_amps {
+            status.push_str(" (over budget)");
+        }
+    }
+    if !estimate.fixtures.is_empty() {
+        status.push_str("\nPer fixture:");
+    }
+    for fixture in &estimate.fixtures {
+        status.push_str(&format!("\n  {}: {:.2} A", fixture.name, fixture.amps));
+    }
+    status
+}
+
+fn format_sync_status(sacn_output_monitor: &crate::SacnOutputMonitor) -> String {
+    match (
+        sacn_output_monitor.sync_universe,
+        sacn_output_monitor.rejected_sync_universe,
+    ) {
+        (Some(universe), _) => format!(
+            "Sync: Active on U{} ({} sync packets)",
+            universe, sacn_output_monitor.total_sync_packets_sent
+        ),
+        (None, Some(universe)) => format!(
+            "Sync: Off, U{} is also sent as a data universe. Pick another sync universe.",
+            universe
+        ),
+        (None, None) => "Sync: Off".to_string(),
+    }
+}
+
+fn format_destination_status(sacn_output_monitor: &crate::SacnOutputMonitor) -> String {
+    // A single destination is already covered by the route.
+    if sacn_output_monitor.destinations.len() < 2
+        && sacn_output_monitor
+            .destinations
+            .iter()
+            .all(|destination| destination.last_error.is_none())
+    {
+        return String::new();
+    }
+    let mut status = String::from("\nDestinations:");
+    for destination in &sacn_output_monitor.destinations {
+        status.push_str(&format!(
+            "\n{}: {} packets",
+            destination.label, destination.packets_sent
+        ));
+        if let Some(error) = &destination.last_error {
+            status.push_str(&format!("\n  {}", error));
+        }
+    }
+    status
+}
+
+/// One line per source, flagging any universes that we are also sending.
+fn format_discovered_sources(
+    sources: &[crate::sacn_discovery::DiscoveredSource],
+    output_universes: &[u16],
+) -> String {
+    if sources.is_empty() {
+        return "Listening, no other sources.".to_string();
+    }
+    let mut lines = Vec::new();
+    for source in sources {
+        let cid = source.cid.simple().to_string();
+        lines.push(format!(
+            "{} ({}): {}",
+            source.name,
+            &cid[..8],
+            format_universe_ranges(&source.universes)
+        ));
+        let collisions: Vec<u16> = source
+            .universes
+            .iter()
+            .copied()
+            .filter(|universe| output_universes.contains(universe))
+            .collect();
+        if !collisions.is_empty() {
+            lines.push(format!(
+                "  COLLISION on {}",
+                format_universe_ranges(&collisions)
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Format sorted universes as ranges, e.g. "U1-4, U7".
+fn format_universe_ranges(universes: &[u16]) -> String {
+    if universes.is_empty() {
+        return "no universes".to_string();
+    }
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for &universe in universes {
+        match ranges.last_mut() {
+            Some((_, end)) if end.checked_add(1) == Some(universe) => *end = universe,
+            _ => ranges.push((universe, universe)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                format!("U{}", start)
+            } else {
+                format!("U{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn format_measured_fps(smoothed_fps: f32, total_frames: u64) -> String {
     match total_frames {
         0 => "Waiting".to_string(),
@@ -2856,11 +3028,14 @@ fn set_midi_tab_widgets(
             .color(color::WHITE)
             .set(ids.midi_mapping_param_labels[i], ui);
 
-        // Port name — to the right of label.
+        // Port name — to the right of label. DMX mappings show their universe instead.
         let entry = midi_mapping.entry_for(*target);
-        let port_label = entry
-            .map(|e| truncate_port_name(&e.port_name))
-            .unwrap_or_else(|| "—".to_string());
+        let dmx_entry = midi_mapping.dmx_entry_for(*target);
+        let port_label = match (entry, dmx_entry) {
+            (Some(e), _) => truncate_port_name(&e.port_name),
+            (None, Some(e)) => format!("DMX U{}", e.universe),
+            (None, None) => "—".to_string(),
+        };
         text(&port_label)
             .right(2.0)
             .w(port_w)
@@ -2868,10 +3043,12 @@ fn set_midi_tab_widgets(
             .color(Color::Rgba(0.6, 0.6, 0.6, 1.0))
             .set(ids.midi_mapping_port_texts[i], ui);
 
-        // CC number — to the right of port.
-        let cc_label = entry
-            .map(|e| format!("{}", e.cc))
-            .unwrap_or_else(|| "—".to_string());
+        // CC number or DMX channel — to the right of port.
+        let cc_label = match (entry, dmx_entry) {
+            (Some(e), _) => format!("{}", e.cc),
+            (None, Some(e)) => format!("{}", e.channel),
+            (None, None) => "—".to_string(),
+        };
         text(&cc_label)
             .right(2.0)
             .w(cc_w)
@@ -3490,38 +3667,6 @@ fn column_canvas(background: widget::Id) -> widget::Canvas<'static> {
         .scroll_kids_vertically()
 }
 
-/// Apply envelope modulation to shader params, matching the same iteration
-/// order as set_shader_widgets so mod_slider_ix lines up with mod_amounts.
-pub fn apply_shader_modulation(
-    shader: Shader,
-    params: &mut ShaderParams,
-    mod_slider_ix: &mut usize,
-    mod_amounts: &[f32],
-    envelope: f32,
-) {
-    let p: &mut dyn Params = shader_params(shader, params);
-    for ix in 0..p.param_count() {
-        let ParamMut { kind, .. } = p.param_mut(ix);
-        match kind {
-            ParamKindMut::F32 { value, max } => {
-                if let Some(&mod_amt) = mod_amounts.get(*mod_slider_ix) {
-                    let offset = (envelope * mod_amt) - (mod_amt / 2.0);
-                    *value = (*value + offset).max(0.0).min(max);
-                }
-                *mod_slider_ix += 1;
-            }
-            ParamKindMut::F32Range { value, min, max } => {
-                if let Some(&mod_amt) = mod_amounts.get(*mod_slider_ix) {
-                    let offset = (envelope * mod_amt) - (mod_amt / 2.0);
-                    *value = (*value + offset).max(min).min(max);
-                }
-                *mod_slider_ix += 1;
-            }
-            ParamKindMut::Usize { .. } | ParamKindMut::Bool(_) | ParamKindMut::Select { .. } => {}
-        }
-    }
-}
-
 pub fn normalise_preset_shader_mod_amounts(preset: &mut crate::conf::Preset) {
     let left_count =
         shader_modulation_slot_count(preset.shader_left, &mut preset.shader_params_left);
@@ -3537,7 +3682,7 @@ pub fn normalise_preset_shader_mod_amounts(preset: &mut crate::conf::Preset) {
 }
 
 pub fn shader_modulation_slot_count(shader: Shader, params: &mut ShaderParams) -> usize {
-    let p: &mut dyn Params = shader_params(shader, params);
+    let p = params.params_mut(shader);
     let mut count = 0;
     for ix in 0..p.param_count() {
         let ParamMut { kind, .. } = p.param_mut(ix);
@@ -3550,7 +3695,7 @@ pub fn shader_modulation_slot_count(shader: Shader, params: &mut ShaderParams) -
 }
 
 pub fn shader_param_f32_values(shader: Shader, mut params: ShaderParams) -> Vec<f32> {
-    let p: &mut dyn Params = shader_params(shader, &mut params);
+    let p = params.params_mut(shader);
     let mut values = Vec::new();
     for ix in 0..p.param_count() {
         let ParamMut { kind, .. } = p.param_mut(ix);
@@ -3563,39 +3708,3 @@ pub fn shader_param_f32_values(shader: Shader, mut params: ShaderParams) -> Vec<
     }
     values
 }
-
-pub fn shader_params(shader: Shader, params: &mut ShaderParams) -> &mut dyn Params {
-    match shader {
-        Shader::AcidGradient => &mut params.acid_gradient,
-        Shader::BlinkyCircles => &mut params.blinky_circles,
-        Shader::BwGradient => &mut params.bw_gradient,
-        Shader::ColourGrid => &mut params.colour_grid,
-        Shader::EscherTilings => &mut params.escher_tilings,
-        Shader::GilmoreAcid => &mut params.gilmore_acid,
-        Shader::GradientBars => &mut params.gradient_bars,
-        Shader::HoopLoop => &mut params.hoop_loop,
-        Shader::ImitationRiley => &mut params.imitation_riley,
-        Shader::JustRelax => &mut params.just_relax,
-        Shader::LifeLedWall => &mut params.life_led_wall,
-        Shader::LightPatternGenerator => &mut params.light_pattern_generator,
-        Shader::LineGradient => &mut params.line_gradient,
-        Shader::Metafall => &mut params.metafall,
-        Shader::ParticleZoom => &mut params.particle_zoom,
-        Shader::RadialLines => &mut params.radial_lines,
-        Shader::SatisSpiraling => &mut params.satis_spiraling,
-        Shader::SpiralIntersect => &mut params.spiral_intersect,
-        Shader::SquareTunnel => &mut params.square_tunnel,
-        Shader::ThePulse => &mut params.the_pulse,
-        Shader::TunnelProjection => &mut params.tunnel_projection,
-        Shader::VertColourGradient => &mut params.vert_colour_gradient,
-        Shader::SolidHsvColour => &mut params.solid_hsv_colour,
-        Shader::SolidRgbColour => &mut params.solid_rgb_colour,
-        Shader::ColourPalettes => &mut params.colour_palettes,
-        Shader::MitchWash => &mut params.mitch_wash,
-        Shader::ShapeEnvelopes => &mut params.shape_envelopes,
-        Shader::RowTest => &mut params.row_test,
-        Shader::BarTest => &mut params.bar_test,
-        Shader::TwoDTiles => &mut params.two_d_tiles,
-        Shader::RadialKeta => &mut params.radial_keta,
-    }
-}
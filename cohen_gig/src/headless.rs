@@ -0,0 +1,272 @@
+//! Runs the show without the GUI or previs windows, e.g. on a rack machine with no display.
+//!
+//! The LED worker and DMX output run just as they do with the GUI. The show is controlled by the
+//! saved MIDI mapping, the sACN control universe and commands typed on stdin, one per line.
+//! Typing `quit`, pressing Ctrl-C or sending SIGTERM all fade out and terminate the output before
+//! exiting.
+
+use crate::conf::GlobalConfig;
+use crate::Show;
+use std::io::BufRead;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the show is updated, matching the GUI's frame rate.
+const UPDATE_FPS: f32 = 60.0;
+
+const HELP: &str = "\
+Commands:
+  next | prev          select the next or previous preset
+  preset <n | name>    select a preset by number (from 1) or name
+  dmx <on | off>       enable or disable DMX output
+  fade <0..1>          fade to black, where 1 is fully black
+  speed <0..1>         set the master speed
+  cycle                press the cycle button, as space does in the GUI
+  status               print the current preset and output state
+  save                 save the config
+  quit                 fade out the output and exit";
+
+/// A single line of stdin.
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    NextPreset,
+    PrevPreset,
+    Preset(PresetRef),
+    Dmx(bool),
+    FadeToBlack(f32),
+    MasterSpeed(f32),
+    Cycle,
+    Status,
+    Save,
+    Help,
+    Quit,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PresetRef {
+    /// The 1-based position in the preset list, as shown in the GUI.
+    Number(usize),
+    Name(String),
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (command, arg) = match line.split_once(char::is_whitespace) {
+            Some((command, arg)) => (command, arg.trim()),
+            None => (line, ""),
+        };
+        let unit = |arg: &str| match arg.parse::<f32>() {
+            Ok(v) if (0.0..=1.0).contains(&v) => Ok(v),
+            _ => Err(format!("`{}` needs a value from 0 to 1", command)),
+        };
+        let command = match command {
+            "next" => Command::NextPreset,
+            "prev" => Command::PrevPreset,
+            "preset" if arg.is_empty() => return Err("`preset` needs a number or name".into()),
+            "preset" => match arg.parse() {
+                Ok(number) => Command::Preset(PresetRef::Number(number)),
+                Err(_) => Command::Preset(PresetRef::Name(arg.to_string())),
+            },
+            "dmx" => match arg {
+                "on" => Command::Dmx(true),
+                "off" => Command::Dmx(false),
+                _ => return Err("`dmx` needs `on` or `off`".into()),
+            },
+            "fade" => Command::FadeToBlack(unit(arg)?),
+            "speed" => Command::MasterSpeed(unit(arg)?),
+            "cycle" => Command::Cycle,
+            "status" => Command::Status,
+            "save" => Command::Save,
+            "help" => Command::Help,
+            "quit" | "exit" => Command::Quit,
+            _ => return Err(format!("Unknown command `{}`", line)),
+        };
+        Ok(command)
+    }
+}
+
+/// Load the show and run it until `quit` is entered.
+pub fn run() {
+    let assets =
+        nannou::app::find_assets_path().expect("failed to find project `assets` directory");
+    let mut show = Show::load(&assets);
+    let (tx, commands) = mpsc::channel();
+    spawn_stdin_reader(tx.clone());
+    // Signals are handled as `quit`, so that the output still fades out and terminates.
+    if let Err(err) = ctrlc::set_handler(move || {
+        let _ = tx.send("quit".to_string());
+    }) {
+        eprintln!("Couldn't handle interrupts, use `quit` to stop: {}", err);
+    }
+    eprintln!("Running headless. Type `help` for commands.");
+    print_status(&show);
+
+    let interval = Duration::from_secs_f32(1.0 / UPDATE_FPS);
+    let mut last_update = Instant::now();
+    loop {
+        let now = Instant::now();
+        crate::update_show_inputs(&mut show);
+        for line in commands.try_iter() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match Command::parse(&line) {
+                Ok(Command::Quit) => {
+                    eprintln!("Stopping output...");
+                    show.shutdown();
+                    return;
+                }
+                Ok(command) => apply_command(&mut show, &assets, command),
+                Err(err) => eprintln!("{}. Type `help` for commands.", err),
+            }
+        }
+        crate::update_show(
+            &mut show,
+            now.duration_since(last_update),
+            crate::GuiShowing::default(),
+        );
+        last_update = now;
+        thread::sleep(interval.saturating_sub(now.elapsed()));
+    }
+}
+
+/// Forward stdin lines to the show loop. Reaching the end of stdin, e.g. when run as a service,
+/// leaves the show running.
+fn spawn_stdin_reader(tx: mpsc::Sender<String>) {
+    thread::Builder::new()
+        .name("stdin".into())
+        .spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn stdin thread");
+}
+
+fn apply_command(show: &mut Show, assets: &std::path::Path, command: Command) {
+    let count = show.presets.list.len();
+    match command {
+        Command::NextPreset if count > 0 => {
+            let index = (show.presets.selected_preset_idx + 1) % count;
+            crate::select_preset(show, index);
+            print_status(show);
+        }
+        Command::PrevPreset if count > 0 => {
+            let index = (show.presets.selected_preset_idx + count - 1) % count;
+            crate::select_preset(show, index);
+            print_status(show);
+        }
+        Command::NextPreset | Command::PrevPreset => eprintln!("There are no presets"),
+        Command::Preset(preset) => {
+            let index = match &preset {
+                PresetRef::Number(number) => number.checked_sub(1).filter(|&ix| ix < count),
+                PresetRef::Name(name) => show.presets.list.iter().position(|p| &p.name == name),
+            };
+            match index {
+                Some(index) => {
+                    crate::select_preset(show, index);
+                    print_status(show);
+                }
+                None => eprintln!("No preset {:?}", preset),
+            }
+        }
+        Command::Dmx(on) => {
+            show.global_config.dmx_on = on;
+            print_status(show);
+        }
+        Command::FadeToBlack(_) | Command::MasterSpeed(_) => {
+            apply_config_command(&mut show.global_config, &command)
+        }
+        Command::Cycle => crate::update_button(
+            &mut show.buttons,
+            shader_shared::Button::Cycle,
+            shader_shared::State::On,
+        ),
+        Command::Status => print_status(show),
+        Command::Save => {
+            crate::save_global_config(assets, &show.global_config);
+            eprintln!("Saved config");
+        }
+        Command::Help => eprintln!("{}", HELP),
+        Command::Quit => unreachable!("handled by the show loop"),
+    }
+}
+
+/// Apply a command that only changes the global config.
+fn apply_config_command(config: &mut GlobalConfig, command: &Command) {
+    match *command {
+        // The config holds the brightness left, where the command gives how far to fade.
+        Command::FadeToBlack(amount) => config.fade_to_black.led = 1.0 - amount,
+        Command::MasterSpeed(speed) => config.master_speed = speed,
+        _ => {}
+    }
+}
+
+fn print_status(show: &Show) {
+    let monitor = &show.dmx.monitor;
+    let output = match (&show.dmx.error, &monitor.last_send_error) {
+        _ if !show.global_config.dmx_on => "DMX off".to_string(),
+        (Some(error), _) | (None, Some(error)) => format!("DMX error: {}", error),
+        (None, None) => format!(
+            "DMX on via {}, {:.1} FPS",
+            show.dmx
+                .last_send_route
+                .map_or("-", crate::dmx_send_route_label),
+            monitor.smoothed_frame_fps
+        ),
+    };
+    eprintln!(
+        "Preset {}/{}: {} | {} | {}",
+        show.presets.selected_preset_idx + 1,
+        show.presets.list.len(),
+        show.presets.selected().name,
+        output,
+        show.runtime_stats.render_passes.summary()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_config_command, Command, PresetRef};
+    use crate::conf::GlobalConfig;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(Command::parse(" next "), Ok(Command::NextPreset));
+        assert_eq!(
+            Command::parse("preset 3"),
+            Ok(Command::Preset(PresetRef::Number(3)))
+        );
+        assert_eq!(
+            Command::parse("preset Slow Wash"),
+            Ok(Command::Preset(PresetRef::Name("Slow Wash".into())))
+        );
+        assert_eq!(Command::parse("dmx off"), Ok(Command::Dmx(false)));
+        assert_eq!(Command::parse("fade 0.5"), Ok(Command::FadeToBlack(0.5)));
+        assert_eq!(Command::parse("quit"), Ok(Command::Quit));
+    }
+
+    #[test]
+    fn fade_one_is_fully_black() {
+        let mut config = GlobalConfig::default();
+        apply_config_command(&mut config, &Command::parse("fade 1").unwrap());
+        assert_eq!(config.fade_to_black.led, 0.0);
+        apply_config_command(&mut config, &Command::parse("fade 0").unwrap());
+        assert_eq!(config.fade_to_black.led, 1.0);
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        assert!(Command::parse("preset").is_err());
+        assert!(Command::parse("dmx maybe").is_err());
+        assert!(Command::parse("fade 2").is_err());
+        assert!(Command::parse("dance").is_err());
+    }
+}
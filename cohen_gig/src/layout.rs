@@ -91,9 +91,11 @@ pub fn shader_resolution(led_layout: &LedLayout) -> Vec2 {
 // Resolved layout abstraction
 // ---------------------------------------------------------------------------
 
+use crate::conf::{parse_pixel_destination, PixelDestinations};
 use crate::mad_mapper;
-use crate::CachedLedShaderInput;
-use shader_shared::Light;
+use shader_shared::{CachedLedShaderInput, Light};
+use std::net::SocketAddr;
+use std::ops::Range;
 
 /// Everything the LED worker needs to know about the physical layout.
 #[derive(Clone)]
@@ -114,16 +116,67 @@ pub enum DmxMap {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FixtureDmxEntry {
+    /// The MadMapper fixture name, used to look up per-fixture output settings.
+    pub fixture_name: String,
     pub led_offset: usize,
     pub led_count: usize,
     pub start_universe: u16,
     pub start_channel: u16,
     pub channels_per_pixel: u8,
+    /// Where the fixture's pixels are sent by the OPC and DDP protocols. `None` if the fixture
+    /// has no valid destination.
+    pub pixel_destination: Option<SocketAddr>,
+}
+
+/// A contiguous run of LEDs sent as part of a single OPC or DDP frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PixelFrameLayout {
+    pub destination: SocketAddr,
+    /// LED index ranges, concatenated in order to form the frame.
+    pub led_ranges: Vec<PixelFrameRange>,
+}
+
+/// The LEDs of a single fixture within a pixel frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PixelFrameRange {
+    pub leds: Range<usize>,
+    /// The fixture the LEDs belong to, used to find its calibration. `None` for the manual
+    /// layout, which is calibrated per row.
+    pub fixture_name: Option<String>,
+}
+
+fn normalised_led_coord(index: usize, count: usize) -> f32 {
+    if count <= 1 {
+        0.0
+    } else {
+        (index as f32 / (count - 1) as f32) * 2.0 - 1.0
+    }
+}
+
+/// The shader inputs for every LED of the manual layout, starting from the top left.
+pub fn manual_shader_inputs(led_layout: &LedLayout) -> Vec<CachedLedShaderInput> {
+    led_positions_metres(led_layout)
+        .enumerate()
+        .map(|(led_ix, (row, x, h))| {
+            let position =
+                topdown_metres_to_shader_coords(pt2(x, SHADER_ORIGIN_METRES[1]), h, led_layout);
+            let col = led_ix % led_layout.leds_per_row();
+            let light = Light::Led {
+                index: led_ix,
+                col_row: [col, row],
+                normalised_coords: vec2(
+                    normalised_led_coord(col, led_layout.leds_per_row()),
+                    normalised_led_coord(row, led_layout.row_count),
+                ),
+            };
+            CachedLedShaderInput { position, light }
+        })
+        .collect()
 }
 
 /// Build a resolved layout from the manual config.
 pub fn resolve_from_manual(led_layout: &LedLayout, start_universe: u16) -> ResolvedLayout {
-    let shader_inputs = crate::rebuild_led_shader_inputs(led_layout);
+    let shader_inputs = manual_shader_inputs(led_layout);
     let led_count = shader_inputs.len();
     ResolvedLayout {
         shader_inputs,
@@ -169,11 +222,13 @@ pub fn resolve_from_mad_project(project: &mad_mapper::MadProject) -> ResolvedLay
         }
 
         dmx_entries.push(FixtureDmxEntry {
+            fixture_name: fixture.name.clone(),
             led_offset,
             led_count: fixture.pixel_count,
             start_universe: fixture.universe,
             start_channel: fixture.start_channel,
             channels_per_pixel: fixture.channels_per_pixel,
+            pixel_destination: None,
         });
 
         led_offset += fixture.pixel_count;
@@ -185,3 +240,158 @@ pub fn resolve_from_mad_project(project: &mad_mapper::MadProject) -> ResolvedLay
         dmx_map: DmxMap::PerFixture(dmx_entries),
     }
 }
+
+/// Resolve each fixture's OPC/DDP destination from the config.
+///
+/// Addresses without a port use `default_port`. Invalid addresses leave the fixture unsent.
+pub fn assign_pixel_destinations(
+    layout: &mut ResolvedLayout,
+    destinations: &PixelDestinations,
+    default_port: u16,
+) {
+    let DmxMap::PerFixture(entries) = &mut layout.dmx_map else {
+        return;
+    };
+    for entry in entries {
+        let address = destinations.for_fixture(&entry.fixture_name);
+        entry.pixel_destination = parse_pixel_destination(address, default_port)
+            .ok()
+            .flatten();
+    }
+}
+
+/// Group the LEDs into one frame per pixel destination, in the order destinations first appear.
+///
+/// The manual layout sends every LED to the default destination.
+pub fn pixel_frame_layouts(
+    dmx_map: Option<&DmxMap>,
+    led_count: usize,
+    destinations: &PixelDestinations,
+    default_port: u16,
+) -> Vec<PixelFrameLayout> {
+    let Some(DmxMap::PerFixture(entries)) = dmx_map else {
+        let default = parse_pixel_destination(&destinations.default, default_port);
+        return match default {
+            Ok(Some(destination)) if led_count > 0 => vec![PixelFrameLayout {
+                destination,
+                led_ranges: vec![PixelFrameRange {
+                    leds: 0..led_count,
+                    fixture_name: None,
+                }],
+            }],
+            _ => Vec::new(),
+        };
+    };
+
+    let mut frames: Vec<PixelFrameLayout> = Vec::new();
+    for entry in entries {
+        let Some(destination) = entry.pixel_destination else {
+            continue;
+        };
+        let start = entry.led_offset.min(led_count);
+        let end = (entry.led_offset + entry.led_count).min(led_count);
+        let range = PixelFrameRange {
+            leds: start..end,
+            fixture_name: Some(entry.fixture_name.clone()),
+        };
+        match frames
+            .iter_mut()
+            .find(|frame| frame.destination == destination)
+        {
+            Some(frame) => frame.led_ranges.push(range),
+            None => frames.push(PixelFrameLayout {
+                destination,
+                led_ranges: vec![range],
+            }),
+        }
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assign_pixel_destinations, pixel_frame_layouts, DmxMap, FixtureDmxEntry};
+    use super::{PixelFrameLayout, PixelFrameRange, ResolvedLayout};
+    use crate::conf::PixelDestinations;
+    use std::net::SocketAddr;
+
+    fn fixture(name: &str, led_offset: usize, led_count: usize) -> FixtureDmxEntry {
+        FixtureDmxEntry {
+            fixture_name: name.to_string(),
+            led_offset,
+            led_count,
+            start_universe: 1,
+            start_channel: 1,
+            channels_per_pixel: 3,
+            pixel_destination: None,
+        }
+    }
+
+    fn range(name: &str, leds: std::ops::Range<usize>) -> PixelFrameRange {
+        PixelFrameRange {
+            leds,
+            fixture_name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn fixtures_sharing_a_destination_form_one_frame() {
+        let mut layout = ResolvedLayout {
+            shader_inputs: Vec::new(),
+            dmx_map: DmxMap::PerFixture(vec![
+                fixture("A", 0, 10),
+                fixture("B", 10, 5),
+                fixture("C", 15, 5),
+                fixture("D", 20, 5),
+            ]),
+            led_count: 25,
+        };
+        let mut destinations = PixelDestinations {
+            default: "10.0.0.1".to_string(),
+            ..Default::default()
+        };
+        destinations
+            .fixtures
+            .insert("B".to_string(), "10.0.0.2:7000".to_string());
+        destinations
+            .fixtures
+            .insert("D".to_string(), "not an address".to_string());
+        assign_pixel_destinations(&mut layout, &destinations, 4048);
+
+        let frames = pixel_frame_layouts(Some(&layout.dmx_map), 25, &destinations, 4048);
+        assert_eq!(
+            frames,
+            vec![
+                PixelFrameLayout {
+                    destination: SocketAddr::from(([10, 0, 0, 1], 4048)),
+                    led_ranges: vec![range("A", 0..10), range("C", 15..20)],
+                },
+                PixelFrameLayout {
+                    destination: SocketAddr::from(([10, 0, 0, 2], 7000)),
+                    led_ranges: vec![range("B", 10..15)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn manual_layout_sends_everything_to_the_default() {
+        let destinations = PixelDestinations {
+            default: "127.0.0.1".to_string(),
+            ..Default::default()
+        };
+        let map = DmxMap::Sequential { start_universe: 1 };
+        let frames = pixel_frame_layouts(Some(&map), 30, &destinations, 7890);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].led_ranges,
+            vec![PixelFrameRange {
+                leds: 0..30,
+                fixture_name: None,
+            }]
+        );
+
+        let frames = pixel_frame_layouts(None, 30, &PixelDestinations::default(), 7890);
+        assert!(frames.is_empty());
+    }
+}
@@ -5,29 +5,43 @@ use nannou_conrod::Ui;
 use rayon::prelude::*;
 use sacn::packet::{ACN_SDT_MULTICAST_PORT, E131_DEFAULT_PRIORITY, UNIVERSE_CHANNEL_CAPACITY};
 use sacn::source::SacnSource;
-use shader_shared::{Light, MixingInfo, ShaderParams, Uniforms, Vertex};
-use std::collections::HashMap;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use shader_shared::{CachedLedShaderInput, MixingInfo, ShaderParams, Uniforms};
+use std::collections::{BTreeMap, HashMap};
+use std::net::{AddrParseError, IpAddr, Ipv4Addr, SocketAddr};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
+use uuid::Uuid;
 
+mod artnet_sender;
 mod audio_input;
 mod audio_widgets;
 mod conf;
+mod ddp_sender;
+mod dmx_merge;
+mod dmx_recording;
 mod gui;
+mod headless;
 pub mod knob;
 mod layout;
 mod lerp;
 mod mad_mapper;
 mod midi;
 pub mod mod_slider;
+mod opc_sender;
+mod power;
+mod preview;
+mod render;
+mod sacn_discovery;
+mod sacn_receiver;
 mod sacn_sender;
 mod shader;
+mod strobe_limit;
 
 use crate::conf::GlobalConfig;
-use crate::shader::{Shader, ShaderFnPtr, ShaderReceiver};
+use crate::render::render_preset_graph;
+use crate::shader::{Shader, ShaderFns, ShaderReceiver};
 
 const WINDOW_PAD: i32 = 20;
 const GUI_WINDOW_X: i32 = WINDOW_PAD;
@@ -44,6 +58,15 @@ pub const RIGHT_X: f32 = 1.0;
 pub const FLOOR_Y: f32 = -1.0;
 pub const ROOF_Y: f32 = 1.0;
 
+/// The source name sent with our output. Incoming sACN with this name is ignored.
+const SACN_SOURCE_NAME: &str = "Cohen Pre-vis";
+/// The highest source priority permitted by E1.31.
+const MAX_SACN_PRIORITY: u8 = 200;
+/// The frame rate of the fade to black on exit when the output rate is uncapped.
+const STOP_FADE_FALLBACK_FPS: f32 = 60.0;
+/// How long to wait after the mirror interface fails before trying it again.
+const MIRROR_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 pub const DMX_ADDRS_PER_LED: u8 = 3;
 pub const DMX_ADDRS_PER_UNIVERSE: u16 = 512;
 
@@ -66,12 +89,29 @@ struct Model {
     _gui_window: window::Id,
     led_strip_window: window::Id,
     preview_window_visible: bool,
+    show: Show,
+    ui: Ui,
+    ids: gui::Ids,
+    left_panel_tab: gui::LeftPanelTab,
+    preset_list_drag: gui::PresetListDragState,
+    shader_left_dropdown: gui::ShaderDropdownState,
+    shader_right_dropdown: gui::ShaderDropdownState,
+    hover_preview_state: gui::HoverPreviewState,
+    fixture_editor: gui::FixtureEditorState,
+    pending_file_dialog: Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
+    pending_replay_dialog: Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
+    preview_images: Option<PreviewImages>,
+}
+
+/// Everything needed to run the show, shared by the GUI and headless modes.
+struct Show {
     led_worker: LedWorker,
     dmx: Dmx,
     midi_manager: midi::MidiManager,
     midi_mapping: midi::mapping::MidiMapping,
     midi_learn: midi::learn::LearnState,
     midi_values: HashMap<midi::mapping::MidiTarget, MidiTargetState>,
+    dmx_control: midi::dmx::DmxControlInput,
     shader_rx: ShaderReceiver,
     global_config: GlobalConfig,
     presets: conf::Presets,
@@ -88,21 +128,16 @@ struct Model {
     led_colors_colourise: Vec<LinSrgb>,
     led_colors_hover: Vec<LinSrgb>,
     led_outputs: Vec<LinSrgb>,
+    power_estimate: power::PowerEstimate,
+    strobe_intervening: bool,
+    dmx_capture: dmx_recording::CaptureRequest,
+    dmx_capture_status: dmx_recording::CaptureStatus,
     hover_preview_request: Option<HoverPreviewRequest>,
     last_preset_change: Option<LastPresetChange>,
-    ui: Ui,
-    ids: gui::Ids,
-    left_panel_tab: gui::LeftPanelTab,
-    preset_list_drag: gui::PresetListDragState,
-    shader_left_dropdown: gui::ShaderDropdownState,
-    shader_right_dropdown: gui::ShaderDropdownState,
-    hover_preview_state: gui::HoverPreviewState,
     audio_input: audio_input::AudioInput,
-    runtime_stats: RuntimeStats,
     mad_project: Option<mad_mapper::MadProject>,
     resolved_layout: Option<layout::ResolvedLayout>,
-    pending_file_dialog: Option<std::sync::mpsc::Receiver<Option<std::path::PathBuf>>>,
-    preview_images: Option<PreviewImages>,
+    runtime_stats: RuntimeStats,
 }
 
 #[derive(Clone)]
@@ -126,12 +161,6 @@ struct PresetTransitionState {
     lerp_amt: f32,
 }
 
-#[derive(Copy, Clone)]
-pub struct CachedLedShaderInput {
-    pub position: Point3,
-    pub light: Light,
-}
-
 #[derive(Clone)]
 struct ButtonState {
     pub last_pressed: std::time::Instant,
@@ -142,19 +171,136 @@ struct Dmx {
     error: Option<String>,
     last_send_route: Option<DmxSendRoute>,
     monitor: SacnOutputMonitor,
+    input: SacnInputStatus,
 }
 
 struct DmxRuntime {
     source: Option<DmxOutputTransport>,
-    requested_interface_ip: Option<Ipv4Addr>,
+    requested_source: Option<DmxSourceRequest>,
+    /// Repeats all output on a second interface, when one is set.
+    mirror: Option<DmxOutputTransport>,
+    requested_mirror: Option<DmxSourceRequest>,
+    mirror_error: Option<String>,
+    /// When the mirror last failed to bind or send, so that it isn't retried every frame.
+    mirror_failed_at: Option<Instant>,
+    /// Announces our sACN universes, created alongside an sACN source.
+    discovery: Option<sacn_discovery::DiscoverySender>,
+    last_sent: LastSentOutput,
+    /// Set while the output fades out after DMX is switched off.
+    stopping: Option<OutputFade>,
     error: Option<String>,
     last_send_route: Option<DmxSendRoute>,
     last_send_attempt_at: Option<Instant>,
     monitor: SacnOutputMonitor,
+    input: SacnInputRuntime,
+}
+
+/// The universes and pixel frames most recently sent, kept so that they can be faded out and
+/// terminated when output stops.
+#[derive(Clone, Debug, Default)]
+struct LastSentOutput {
+    payloads: BTreeMap<u16, Vec<u8>>,
+    pixel_frames: Vec<(SocketAddr, Vec<u8>)>,
+}
+
+/// A fade to black from the last sent output, run before the streams are terminated.
+struct OutputFade {
+    frame: u32,
+    frames: u32,
+    /// The bit depth of the universe slots, so 16-bit channels fade as a whole.
+    bit_depth: conf::DmxBitDepth,
+    from: LastSentOutput,
+}
+
+impl OutputFade {
+    /// The faded universes and pixel frames for the current frame.
+    fn next_output(&self) -> (Vec<(u16, Vec<u8>)>, Vec<(SocketAddr, Vec<u8>)>) {
+        let payloads = self
+            .from
+            .payloads
+            .iter()
+            .map(|(&universe, payload)| {
+                let mut payload = payload.clone();
+                // Leave the start code alone.
+                if let Some(levels) = payload.get_mut(1..) {
+                    fade_levels(levels, self.bit_depth, self.frame, self.frames);
+                }
+                (universe, payload)
+            })
+            .collect();
+        let pixel_frames = self
+            .from
+            .pixel_frames
+            .iter()
+            .map(|(destination, rgb)| {
+                let mut rgb = rgb.clone();
+                // OPC and DDP frames are always 8-bit.
+                fade_levels(&mut rgb, conf::DmxBitDepth::Eight, self.frame, self.frames);
+                (*destination, rgb)
+            })
+            .collect();
+        (payloads, pixel_frames)
+    }
+}
+
+/// The sACN receiver used to merge a lighting console into our output.
+#[derive(Default)]
+struct SacnInputRuntime {
+    receiver: Option<sacn_receiver::SacnReceiver>,
+    requested: Option<SacnInputRequest>,
+    merger: dmx_merge::DmxMerger,
+    control_universe: Option<u16>,
+    error: Option<String>,
+}
+
+/// The settings that the sACN receiver was created from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SacnInputRequest {
+    interface_ip: Option<Ipv4Addr>,
+    universes: Vec<u16>,
+    discover_sources: bool,
+}
+
+/// The state of the sACN input, reported back to the GUI.
+#[derive(Clone, Debug, Default)]
+pub struct SacnInputStatus {
+    pub listening: bool,
+    /// Universes with a live incoming source.
+    pub active_universes: Vec<u16>,
+    /// The latest payload received on the control universe.
+    pub control_levels: Option<Vec<u8>>,
+    /// Other sources announcing their universes, when discovery is enabled.
+    pub discovered_sources: Vec<sacn_discovery::DiscoveredSource>,
+    pub error: Option<String>,
+}
+
+/// What the GUI is currently showing that the LED worker would otherwise render or capture for
+/// nothing. Headless mode shows nothing.
+#[derive(Clone, Copy, Debug, Default)]
+struct GuiShowing {
+    output_monitor: bool,
+    shader_previews: bool,
 }
 
+#[derive(Default)]
 struct RuntimeStats {
     app_fps: f32,
+    /// Smoothed time the LED worker spends on each render pass per frame.
+    render_passes: RenderPassTimes,
+}
+
+/// Milliseconds spent on each pass of `render_led_worker_frame`.
+///
+/// Passes that are skipped on a frame count as zero, so the preview passes show their cost
+/// amortised over the frames between renders.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct RenderPassTimes {
+    main: f32,
+    left: f32,
+    right: f32,
+    colourise: f32,
+    hover: f32,
+    transitions: f32,
 }
 
 struct LedWorker {
@@ -180,9 +326,15 @@ struct LedWorkerSharedOutput {
     led_colors_colourise: Vec<LinSrgb>,
     led_colors_hover: Vec<LinSrgb>,
     led_outputs: Vec<LinSrgb>,
+    power_estimate: power::PowerEstimate,
+    strobe_intervening: bool,
+    capture_status: dmx_recording::CaptureStatus,
     monitor: LedWorkerMonitorSnapshot,
     dmx_error: Option<String>,
     last_send_route: Option<DmxSendRoute>,
+    sacn_input: SacnInputStatus,
+    shader_panics: Vec<shader::ShaderPanic>,
+    render_passes: RenderPassTimes,
 }
 
 #[derive(Clone)]
@@ -194,13 +346,28 @@ struct LedWorkerInputState {
     audio_envelope: f32,
     buttons: HashMap<shader_shared::Button, ButtonState>,
     capture_output_monitor: bool,
+    /// Whether the GUI is showing the isolated shader previews.
+    render_shader_previews: bool,
+    /// Where to record sent DMX frames to, or replay them from.
+    dmx_capture: dmx_recording::CaptureRequest,
 }
 
 #[derive(Clone)]
 struct LedWorkerConfig {
     dmx_on: bool,
+    dmx_off_fade_frames: u32,
     sacn_interface_ip: String,
+    mirror_interface_ip: String,
+    dmx_protocol: conf::DmxProtocol,
+    artnet_destination_ip: String,
+    artnet_universe_offset: u16,
+    artnet_sync_ip: String,
+    sacn_sync_universe: Option<u16>,
+    sacn_priorities: BTreeMap<u16, u8>,
+    sacn_unicast_destinations: BTreeMap<u16, Vec<String>>,
+    sacn_input: conf::SacnInput,
     led_output_fps: conf::LedOutputFps,
+    shader_previews: conf::ShaderPreviews,
     led_start_universe: u16,
     fade_to_black_led: f32,
     preset_lerp_secs: f32,
@@ -208,17 +375,29 @@ struct LedWorkerConfig {
     phase_offset: f32,
     phase_offset_mod_amount: f32,
     led_layout: conf::LedLayout,
+    channel_layouts: conf::ChannelLayouts,
+    calibration: conf::Calibration,
+    dmx_bit_depth: conf::DmxBitDepth,
+    led_dither: bool,
+    power_limit: conf::PowerLimit,
+    strobe_limit: conf::StrobeLimit,
     preset: conf::Preset,
     /// Resolved layout from MadMapper, if active.
     resolved_layout: Option<layout::ResolvedLayout>,
+    /// The LEDs sent in each OPC or DDP frame. Empty unless the protocol sends pixel frames.
+    pixel_frame_layouts: Vec<layout::PixelFrameLayout>,
 }
 
 #[derive(Clone, Default)]
 struct LedWorkerMonitorSnapshot {
     universes: Vec<SacnUniverseSnapshot>,
+    destinations: Vec<SacnDestinationStats>,
     total_frames_sent: u64,
     total_packets_sent: u64,
     total_payload_bytes_sent: u64,
+    sync_universe: Option<u16>,
+    rejected_sync_universe: Option<u16>,
+    total_sync_packets_sent: u64,
     smoothed_frame_fps: f32,
     last_sent_at: Option<Instant>,
     last_send_error: Option<String>,
@@ -231,20 +410,75 @@ enum DmxOutputTransport {
         multicast: Option<SacnSource>,
         localhost: sacn_sender::LocalhostSacnSender,
     },
+    ArtNet(artnet_sender::ArtNetSender),
+    Opc(opc_sender::OpcSender),
+    Ddp(ddp_sender::DdpSender),
+}
+
+/// The settings that a `DmxOutputTransport` was created from.
+///
+/// The transport is recreated whenever the requested settings change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DmxSourceRequest {
+    protocol: conf::DmxProtocol,
+    interface_ip: Option<Ipv4Addr>,
+    artnet_destination_ip: Option<Ipv4Addr>,
+    artnet_sync_ip: Option<Ipv4Addr>,
+    artnet_universe_offset: u16,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum DmxSendRoute {
     Multicast,
+    Unicast,
     Localhost,
+    ArtNet,
+    Opc,
+    Ddp,
+}
+
+impl SacnInputRuntime {
+    fn status(&self) -> SacnInputStatus {
+        SacnInputStatus {
+            listening: self.receiver.is_some(),
+            active_universes: self
+                .receiver
+                .as_ref()
+                .map(sacn_receiver::SacnReceiver::active_universes)
+                .unwrap_or_default(),
+            control_levels: self
+                .receiver
+                .as_ref()
+                .zip(self.control_universe)
+                .and_then(|(receiver, universe)| receiver.universe(universe))
+                .map(|received| received.payload.clone()),
+            discovered_sources: self
+                .receiver
+                .as_ref()
+                .map(sacn_receiver::SacnReceiver::discovered_sources)
+                .unwrap_or_default(),
+            error: self.error.clone(),
+        }
+    }
 }
 
 impl DmxOutputTransport {
-    fn send(&mut self, universe: u16, payload: &[u8]) -> Result<DmxSendRoute, String> {
+    /// Send a single universe. When `sync_universe` is set, receivers hold the data until the
+    /// matching `send_sync` call.
+    fn send(
+        &mut self,
+        universe: u16,
+        payload: &[u8],
+        priority: u8,
+        sync_universe: Option<u16>,
+    ) -> Result<DmxSendRoute, String> {
+        let synchronization_address = sync_universe.unwrap_or(0);
         match self {
-            Self::Network(source) => send_multicast_payload(source, universe, payload),
+            Self::Network(source) => {
+                send_multicast_payload(source, universe, payload, priority, sync_universe)
+            }
             Self::Localhost(sender) => sender
-                .send_property_values(universe, payload)
+                .send_property_values(universe, payload, priority, synchronization_address)
                 .map(|()| DmxSendRoute::Localhost)
                 .map_err(|err| {
                     format!(
@@ -257,7 +491,8 @@ impl DmxOutputTransport {
                 localhost,
             } => {
                 if let Some(source) = multicast.as_mut() {
-                    match send_multicast_payload(source, universe, payload) {
+                    match send_multicast_payload(source, universe, payload, priority, sync_universe)
+                    {
                         Ok(route) => return Ok(route),
                         Err(_) => {
                             *multicast = None;
@@ -266,7 +501,7 @@ impl DmxOutputTransport {
                 }
 
                 localhost
-                    .send_property_values(universe, payload)
+                    .send_property_values(universe, payload, priority, synchronization_address)
                     .map(|()| DmxSendRoute::Localhost)
                     .map_err(|err| {
                         format!(
@@ -275,6 +510,215 @@ impl DmxOutputTransport {
                         )
                     })
             }
+            Self::ArtNet(sender) => sender
+                .send_property_values(universe, payload)
+                .map(|()| DmxSendRoute::ArtNet)
+                .map_err(|err| format!("Couldn't send Art-Net universe {}: {}", universe, err)),
+            Self::Opc(_) | Self::Ddp(_) => Err(format!(
+                "Couldn't send universe {}: OPC and DDP send whole pixel frames",
+                universe
+            )),
+        }
+    }
+
+    /// The route that `send` normally takes, used to label its errors.
+    fn route(&self) -> DmxSendRoute {
+        match self {
+            Self::Network(_)
+            | Self::Auto {
+                multicast: Some(_), ..
+            } => DmxSendRoute::Multicast,
+            Self::Localhost(_)
+            | Self::Auto {
+                multicast: None, ..
+            } => DmxSendRoute::Localhost,
+            Self::ArtNet(_) => DmxSendRoute::ArtNet,
+            Self::Opc(_) => DmxSendRoute::Opc,
+            Self::Ddp(_) => DmxSendRoute::Ddp,
+        }
+    }
+
+    /// Whether universes can be unicast to individual receivers.
+    fn supports_unicast(&self) -> bool {
+        matches!(
+            self,
+            Self::Network(_)
+                | Self::Auto {
+                    multicast: Some(_),
+                    ..
+                }
+        )
+    }
+
+    /// Send a single universe to one sACN receiver rather than to the universe's multicast group.
+    fn send_unicast(
+        &mut self,
+        destination: SocketAddr,
+        universe: u16,
+        payload: &[u8],
+        priority: u8,
+        sync_universe: Option<u16>,
+    ) -> Result<DmxSendRoute, String> {
+        match self {
+            Self::Network(source)
+            | Self::Auto {
+                multicast: Some(source),
+                ..
+            } => {
+                source.register_universe(universe).map_err(|err| {
+                    format!("Couldn't register sACN universe {}: {}", universe, err)
+                })?;
+                source
+                    .send(
+                        &[universe],
+                        payload,
+                        Some(priority),
+                        Some(destination),
+                        sync_universe,
+                    )
+                    .map(|()| DmxSendRoute::Unicast)
+                    .map_err(|err| {
+                        format!(
+                            "Couldn't send sACN universe {} to {}: {}",
+                            universe, destination, err
+                        )
+                    })
+            }
+            _ => Err(format!(
+                "Couldn't send sACN universe {} to {}: unicast needs a network interface",
+                universe, destination
+            )),
+        }
+    }
+
+    /// Release the universes unicast to `destination` with the given `sync_universe`.
+    fn send_sync_unicast(
+        &mut self,
+        destination: SocketAddr,
+        sync_universe: u16,
+    ) -> Result<(), String> {
+        match self {
+            Self::Network(source)
+            | Self::Auto {
+                multicast: Some(source),
+                ..
+            } => source
+                .send_sync_packet(sync_universe, Some(destination))
+                .map_err(|err| {
+                    format!(
+                        "Couldn't send sACN sync universe {} to {}: {}",
+                        sync_universe, destination, err
+                    )
+                }),
+            _ => Err(format!(
+                "Couldn't send sACN sync universe {} to {}: unicast needs a network interface",
+                sync_universe, destination
+            )),
+        }
+    }
+
+    /// End the universe's stream so that receivers release its last frame.
+    ///
+    /// Unicast receivers and Art-Net nodes are sent a black frame instead, as sACN stream
+    /// termination is only sent to the multicast group and Art-Net has no equivalent. A failure to
+    /// reach one destination doesn't stop the others; every failure is reported together.
+    fn terminate(
+        &mut self,
+        universe: u16,
+        payload_len: usize,
+        priority: u8,
+        unicast: &[SocketAddr],
+    ) -> Result<(), String> {
+        let black = vec![0; payload_len.max(1)];
+        let mut errors: Vec<String> = unicast
+            .iter()
+            .filter_map(|&destination| {
+                self.send_unicast(destination, universe, &black, priority, None)
+                    .err()
+            })
+            .collect();
+        errors.extend(self.terminate_stream(universe, priority, &black).err());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// End the universe's stream on the transport's own route.
+    fn terminate_stream(
+        &mut self,
+        universe: u16,
+        priority: u8,
+        black: &[u8],
+    ) -> Result<(), String> {
+        match self {
+            Self::Network(source)
+            | Self::Auto {
+                multicast: Some(source),
+                ..
+            } => source
+                .terminate_stream(universe, 0)
+                .map_err(|err| format!("Couldn't terminate sACN universe {}: {}", universe, err)),
+            Self::Localhost(sender)
+            | Self::Auto {
+                multicast: None,
+                localhost: sender,
+            } => sender.terminate_stream(universe, priority).map_err(|err| {
+                format!(
+                    "Couldn't terminate localhost sACN universe {}: {}",
+                    universe, err
+                )
+            }),
+            Self::ArtNet(sender) => sender.send_property_values(universe, black).map_err(|err| {
+                format!("Couldn't black out Art-Net universe {}: {}", universe, err)
+            }),
+            Self::Opc(_) | Self::Ddp(_) => Ok(()),
+        }
+    }
+
+    /// Send a whole frame of 8-bit RGB pixel data to a single OPC or DDP destination.
+    fn send_pixels(&mut self, destination: SocketAddr, rgb: &[u8]) -> Result<DmxSendRoute, String> {
+        match self {
+            Self::Opc(sender) => sender
+                .send_pixels(destination, rgb)
+                .map(|()| DmxSendRoute::Opc)
+                .map_err(|err| format!("Couldn't send OPC frame to {}: {}", destination, err)),
+            Self::Ddp(sender) => sender
+                .send_pixels(destination, rgb)
+                .map(|()| DmxSendRoute::Ddp)
+                .map_err(|err| format!("Couldn't send DDP frame to {}: {}", destination, err)),
+            _ => Err("Only OPC and DDP send whole pixel frames".to_string()),
+        }
+    }
+
+    /// Release all universes sent with the given `sync_universe` since the last sync.
+    fn send_sync(&mut self, sync_universe: u16) -> Result<(), String> {
+        let localhost_err = |err: std::io::Error| {
+            format!(
+                "Couldn't send localhost sACN sync universe {}: {}",
+                sync_universe, err
+            )
+        };
+        match self {
+            Self::Network(source) => send_multicast_sync(source, sync_universe),
+            Self::Localhost(sender) => sender
+                .send_sync_packet(sync_universe)
+                .map_err(localhost_err),
+            Self::Auto {
+                multicast,
+                localhost,
+            } => match multicast.as_mut() {
+                Some(source) => send_multicast_sync(source, sync_universe),
+                None => localhost
+                    .send_sync_packet(sync_universe)
+                    .map_err(localhost_err),
+            },
+            Self::ArtNet(sender) => sender
+                .send_sync()
+                .map_err(|err| format!("Couldn't send ArtSync: {}", err)),
+            // Neither protocol has a separate sync; DDP pushes each frame with its last packet.
+            Self::Opc(_) | Self::Ddp(_) => Ok(()),
         }
     }
 }
@@ -287,13 +731,29 @@ pub struct SacnUniverseSnapshot {
     pub last_sent_at: Option<Instant>,
 }
 
+/// Packets sent to, and the latest error from, a single output destination.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SacnDestinationStats {
+    pub label: String,
+    pub packets_sent: u64,
+    /// The last error of the most recent frame, or `None` if every send succeeded.
+    pub last_error: Option<String>,
+}
+
 #[derive(Default)]
 pub struct SacnOutputMonitor {
     pub universes: Vec<SacnUniverseSnapshot>,
     pub selected_universe: Option<u16>,
+    /// Each destination sent to in the last frame, in the order they were sent.
+    pub destinations: Vec<SacnDestinationStats>,
     pub total_frames_sent: u64,
     pub total_packets_sent: u64,
     pub total_payload_bytes_sent: u64,
+    /// The synchronization universe used for the last sent frame, if any.
+    pub sync_universe: Option<u16>,
+    /// The configured sync universe, if it wasn't used because it's also a data universe.
+    pub rejected_sync_universe: Option<u16>,
+    pub total_sync_packets_sent: u64,
     pub smoothed_frame_fps: f32,
     pub last_sent_at: Option<Instant>,
     pub last_send_error: Option<String>,
@@ -388,18 +848,49 @@ impl SacnOutputMonitor {
         }
     }
 
+    fn record_sync(&mut self, sync_universe: Option<u16>, rejected_sync_universe: Option<u16>) {
+        self.sync_universe = sync_universe;
+        self.rejected_sync_universe = rejected_sync_universe;
+        if sync_universe.is_some() {
+            self.total_sync_packets_sent += 1;
+        }
+    }
+
     fn record_send_error(&mut self, error: String) {
         self.last_send_error = Some(error);
     }
+
+    /// Add one frame's per-destination results, dropping destinations that weren't sent to.
+    fn record_destinations(&mut self, frame: &[SacnDestinationStats]) {
+        let previous = std::mem::take(&mut self.destinations);
+        self.destinations = frame
+            .iter()
+            .map(|sent| {
+                let packets_sent = previous
+                    .iter()
+                    .find(|destination| destination.label == sent.label)
+                    .map_or(0, |destination| destination.packets_sent);
+                SacnDestinationStats {
+                    label: sent.label.clone(),
+                    packets_sent: packets_sent + sent.packets_sent,
+                    last_error: sent.last_error.clone(),
+                }
+            })
+            .collect();
+    }
 }
 
 impl LedWorkerMonitorSnapshot {
     fn from_monitor(monitor: &SacnOutputMonitor) -> Self {
         Self {
             universes: monitor.universes.clone(),
+            destinations: monitor.destinations.clone(),
             total_frames_sent: monitor.total_frames_sent,
             total_packets_sent: monitor.total_packets_sent,
             total_payload_bytes_sent: monitor.total_payload_bytes_sent,
+            sync_universe: monitor.sync_universe,
+            rejected_sync_universe: monitor.rejected_sync_universe,
+            total_sync_packets_sent: monitor.total_sync_packets_sent,
             smoothed_frame_fps: monitor.smoothed_frame_fps,
             last_sent_at: monitor.last_sent_at,
             last_send_error: monitor.last_send_error.clone(),
@@ -424,9 +915,15 @@ impl LedWorker {
             led_colors_colourise: Vec::new(),
             led_colors_hover: Vec::new(),
             led_outputs: Vec::new(),
+            power_estimate: power::PowerEstimate::default(),
+            strobe_intervening: false,
+            capture_status: dmx_recording::CaptureStatus::default(),
             monitor: LedWorkerMonitorSnapshot::default(),
             dmx_error: None,
             last_send_route: None,
+            sacn_input: SacnInputStatus::default(),
+            shader_panics: Vec::new(),
+            render_passes: RenderPassTimes::default(),
         }));
 
         let worker_input = Arc::clone(&shared_input);
@@ -450,22 +947,60 @@ impl RuntimeStats {
     }
 }
 
+impl RenderPassTimes {
+    const SMOOTHING_FACTOR: f32 = 0.05;
+
+    /// Ease each pass time towards the times measured for the latest frame.
+    fn smoothed_towards(self, sample: Self) -> Self {
+        let ease =
+            |current: f32, sample: f32| current + (sample - current) * Self::SMOOTHING_FACTOR;
+        Self {
+            main: ease(self.main, sample.main),
+            left: ease(self.left, sample.left),
+            right: ease(self.right, sample.right),
+            colourise: ease(self.colourise, sample.colourise),
+            hover: ease(self.hover, sample.hover),
+            transitions: ease(self.transitions, sample.transitions),
+        }
+    }
+
+    fn total(&self) -> f32 {
+        self.main + self.left + self.right + self.colourise + self.hover + self.transitions
+    }
+
+    /// A single line summary, e.g. for the GUI or the headless status line.
+    fn summary(&self) -> String {
+        format!(
+            "Render {:.2} ms: main {:.2}, left {:.2}, right {:.2}, colourise {:.2}, \
+             hover {:.2}, transitions {:.2}",
+            self.total(),
+            self.main,
+            self.left,
+            self.right,
+            self.colourise,
+            self.hover,
+            self.transitions,
+        )
+    }
+}
+
+fn elapsed_ms(since: Instant) -> f32 {
+    since.elapsed().as_secs_f32() * 1_000.0
+}
+
 fn main() {
-    nannou::app(model).update(update).exit(exit).run();
+    if std::env::args().skip(1).any(|arg| arg == "--headless") {
+        headless::run();
+    } else {
+        nannou::app(model).update(update).exit(exit).run();
+    }
 }
 
 fn model(app: &App) -> Model {
     let assets = app
         .assets_path()
         .expect("failed to find project `assets` directory");
-
-    let (mut global_config, mut presets) = conf::load(&assets);
-    global_config.led_layout.normalise();
-    for preset in &mut presets.list {
-        preset.migrate_legacy();
-        gui::normalise_preset_shader_mod_amounts(preset);
-    }
-    presets.sync_global_config(&mut global_config);
+    let show = Show::load(&assets);
 
     let gui_window = app
         .new_window()
@@ -509,105 +1044,14 @@ fn model(app: &App) -> Model {
             .window(led_strip_window)
             .expect("visualisation window closed unexpectedly");
         w.set_outer_position_pixels(LED_STRIP_WINDOW_X, LED_STRIP_WINDOW_Y);
-        w.set_visible(global_config.preview_window_on);
+        w.set_visible(show.global_config.preview_window_on);
     }
 
-    let dmx = Dmx {
-        error: None,
-        last_send_route: None,
-        monitor: SacnOutputMonitor::default(),
-    };
-
-    let shader_rx = shader::spawn_watch();
-
-    let mad_project = global_config
-        .madmapper_project_path
-        .as_ref()
-        .and_then(|path| match mad_mapper::parse(path) {
-            Ok(project) => {
-                eprintln!(
-                    "Loaded MadMapper project: {} fixtures, {} pixels",
-                    project.fixtures.len(),
-                    project.total_pixels()
-                );
-                Some(project)
-            }
-            Err(e) => {
-                eprintln!("Failed to parse MadMapper project: {}", e);
-                None
-            }
-        });
-
-    let initial_led_count = mad_project
-        .as_ref()
-        .map(|p| p.total_pixels())
-        .unwrap_or_else(|| global_config.led_layout.led_count());
-    let led_colors = black_led_buffer(initial_led_count);
-    let led_outputs = black_led_buffer(initial_led_count);
-
-    let midi_manager = midi::MidiManager::new();
-
-    // Load MIDI mapping preset (or default empty).
-    let midi_mappings_dir = assets.join("midi_mappings");
-    let midi_mapping = midi::mapping::MidiMappingPreset::list_presets(&midi_mappings_dir)
-        .into_iter()
-        .next()
-        .and_then(|name| {
-            let path = midi_mappings_dir.join(format!("{name}.json"));
-            midi::mapping::MidiMappingPreset::load(&path).ok()
-        })
-        .map(midi::mapping::MidiMapping::new)
-        .unwrap_or_default();
-
-    let audio_input = audio_input::AudioInput::new(128, global_config.audio_input_device.clone());
-    let colour_channels = [1.0, 0.0, 1.0]; // R/H, G/S, B/V defaults
-    let smoothed_preset = presets.selected().clone();
-    let smoothed_master_speed = global_config.master_speed;
-    let smoothed_phase_offset = global_config.phase_offset;
-
-    let resolved_layout = mad_project.as_ref().map(layout::resolve_from_mad_project);
-
-    let last_preset_change = None;
-    let led_worker = LedWorker::new(build_led_worker_input_state(
-        0.0,
-        smoothed_master_speed,
-        smoothed_phase_offset,
-        &global_config,
-        &smoothed_preset,
-        &audio_input,
-        colour_channels,
-        gui::LeftPanelTab::Live,
-        &resolved_layout,
-    ));
-
     Model {
         _gui_window: gui_window,
         led_strip_window,
-        preview_window_visible: global_config.preview_window_on,
-        led_worker,
-        dmx,
-        midi_manager,
-        midi_mapping,
-        midi_learn: midi::learn::LearnState::default(),
-        midi_values: HashMap::new(),
-        shader_rx,
-        global_config,
-        presets,
-        smoothed_preset,
-        smoothing_speed: 0.05,
-        smoothed_master_speed,
-        smoothed_phase_offset,
-        master_phase: 0.0,
-        colour_channels,
-        buttons: Default::default(),
-        led_colors,
-        led_colors_left: black_led_buffer(initial_led_count),
-        led_colors_right: black_led_buffer(initial_led_count),
-        led_colors_colourise: black_led_buffer(initial_led_count),
-        led_colors_hover: black_led_buffer(initial_led_count),
-        led_outputs,
-        hover_preview_request: None,
-        last_preset_change,
+        preview_window_visible: show.global_config.preview_window_on,
+        show,
         ui,
         ids,
         left_panel_tab: gui::LeftPanelTab::Live,
@@ -615,15 +1059,142 @@ fn model(app: &App) -> Model {
         shader_left_dropdown: gui::ShaderDropdownState::default(),
         shader_right_dropdown: gui::ShaderDropdownState::default(),
         hover_preview_state: gui::HoverPreviewState::default(),
-        audio_input,
-        runtime_stats: RuntimeStats { app_fps: 0.0 },
-        resolved_layout,
-        mad_project,
+        fixture_editor: gui::FixtureEditorState::default(),
         pending_file_dialog: None,
+        pending_replay_dialog: None,
         preview_images: None,
     }
 }
 
+impl Show {
+    /// Load the config, presets and MIDI mapping and start the LED worker.
+    fn load(assets: &Path) -> Self {
+        let (mut global_config, mut presets) = conf::load(assets);
+        global_config.led_layout.normalise();
+        for preset in &mut presets.list {
+            preset.migrate_legacy();
+            gui::normalise_preset_shader_mod_amounts(preset);
+        }
+        presets.sync_global_config(&mut global_config);
+
+        let dmx = Dmx {
+            error: None,
+            last_send_route: None,
+            monitor: SacnOutputMonitor::default(),
+            input: SacnInputStatus::default(),
+        };
+
+        let shader_rx = shader::spawn_watch();
+
+        let mad_project = global_config
+            .madmapper_project_path
+            .as_ref()
+            .and_then(|path| match mad_mapper::parse(path) {
+                Ok(project) => {
+                    eprintln!(
+                        "Loaded MadMapper project: {} fixtures, {} pixels",
+                        project.fixtures.len(),
+                        project.total_pixels()
+                    );
+                    Some(project)
+                }
+                Err(e) => {
+                    eprintln!("Failed to parse MadMapper project: {}", e);
+                    None
+                }
+            });
+
+        let initial_led_count = mad_project
+            .as_ref()
+            .map(|p| p.total_pixels())
+            .unwrap_or_else(|| global_config.led_layout.led_count());
+        let led_colors = black_led_buffer(initial_led_count);
+        let led_outputs = black_led_buffer(initial_led_count);
+
+        let midi_manager = midi::MidiManager::new();
+
+        // Load MIDI mapping preset (or default empty).
+        let midi_mappings_dir = assets.join("midi_mappings");
+        let midi_mapping = midi::mapping::MidiMappingPreset::list_presets(&midi_mappings_dir)
+            .into_iter()
+            .next()
+            .and_then(|name| {
+                let path = midi_mappings_dir.join(format!("{name}.json"));
+                midi::mapping::MidiMappingPreset::load(&path).ok()
+            })
+            .map(midi::mapping::MidiMapping::new)
+            .unwrap_or_default();
+
+        let audio_input =
+            audio_input::AudioInput::new(128, global_config.audio_input_device.clone());
+        let colour_channels = [1.0, 0.0, 1.0]; // R/H, G/S, B/V defaults
+        let smoothed_preset = presets.selected().clone();
+        let smoothed_master_speed = global_config.master_speed;
+        let smoothed_phase_offset = global_config.phase_offset;
+
+        let resolved_layout = mad_project.as_ref().map(layout::resolve_from_mad_project);
+
+        let last_preset_change = None;
+        let led_worker = LedWorker::new(build_led_worker_input_state(
+            0.0,
+            smoothed_master_speed,
+            smoothed_phase_offset,
+            &global_config,
+            &smoothed_preset,
+            &audio_input,
+            colour_channels,
+            GuiShowing::default(),
+            &resolved_layout,
+        ));
+
+        Show {
+            led_worker,
+            dmx,
+            midi_manager,
+            midi_mapping,
+            midi_learn: midi::learn::LearnState::default(),
+            midi_values: HashMap::new(),
+            dmx_control: midi::dmx::DmxControlInput::default(),
+            shader_rx,
+            global_config,
+            presets,
+            smoothed_preset,
+            smoothing_speed: 0.05,
+            smoothed_master_speed,
+            smoothed_phase_offset,
+            master_phase: 0.0,
+            colour_channels,
+            buttons: Default::default(),
+            led_colors,
+            led_colors_left: black_led_buffer(initial_led_count),
+            led_colors_right: black_led_buffer(initial_led_count),
+            led_colors_colourise: black_led_buffer(initial_led_count),
+            led_colors_hover: black_led_buffer(initial_led_count),
+            led_outputs,
+            power_estimate: power::PowerEstimate::default(),
+            strobe_intervening: false,
+            dmx_capture: dmx_recording::CaptureRequest::default(),
+            dmx_capture_status: dmx_recording::CaptureStatus::default(),
+            hover_preview_request: None,
+            last_preset_change,
+            audio_input,
+            mad_project,
+            resolved_layout,
+            runtime_stats: RuntimeStats::default(),
+        }
+    }
+
+    /// Stop the LED worker, which fades out and terminates any DMX output first.
+    fn shutdown(&mut self) {
+        if let Ok(mut shared_input) = self.led_worker.shared_input.lock() {
+            shared_input.shutdown = true;
+        }
+        if let Some(thread) = self.led_worker.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
 fn black_led_buffer(led_count: usize) -> Vec<LinSrgb> {
     vec![lin_srgb(0.0, 0.0, 0.0); led_count]
 }
@@ -663,8 +1234,10 @@ fn preview_dimensions(
 }
 
 fn update_preview_textures(app: &App, model: &mut Model) {
-    let (width, height) =
-        preview_dimensions(&model.global_config.led_layout, model.mad_project.as_ref());
+    let (width, height) = preview_dimensions(
+        &model.show.global_config.led_layout,
+        model.show.mad_project.as_ref(),
+    );
     if width == 0 || height == 0 {
         return;
     }
@@ -763,53 +1336,57 @@ fn update_preview_textures(app: &App, model: &mut Model) {
             rows_per_image: std::num::NonZeroU32::new(pi.height),
         };
 
-        let left_rgba = led_colors_to_rgba(&model.led_colors_left, pi.width, pi.height);
-        if let Some(img) = model.ui.image_map.get(&pi.left_id) {
-            queue.write_texture(
-                nannou::wgpu::ImageCopyTexture {
-                    texture: &img.texture,
-                    mip_level: 0,
-                    origin: nannou::wgpu::Origin3d::ZERO,
-                    aspect: nannou::wgpu::TextureAspect::All,
-                },
-                &left_rgba,
-                layout,
-                size,
-            );
-        }
+        // The isolated previews aren't shown while their rendering is turned off.
+        if model.show.global_config.shader_previews.is_on() {
+            let left_rgba = led_colors_to_rgba(&model.show.led_colors_left, pi.width, pi.height);
+            if let Some(img) = model.ui.image_map.get(&pi.left_id) {
+                queue.write_texture(
+                    nannou::wgpu::ImageCopyTexture {
+                        texture: &img.texture,
+                        mip_level: 0,
+                        origin: nannou::wgpu::Origin3d::ZERO,
+                        aspect: nannou::wgpu::TextureAspect::All,
+                    },
+                    &left_rgba,
+                    layout,
+                    size,
+                );
+            }
 
-        let right_rgba = led_colors_to_rgba(&model.led_colors_right, pi.width, pi.height);
-        if let Some(img) = model.ui.image_map.get(&pi.right_id) {
-            queue.write_texture(
-                nannou::wgpu::ImageCopyTexture {
-                    texture: &img.texture,
-                    mip_level: 0,
-                    origin: nannou::wgpu::Origin3d::ZERO,
-                    aspect: nannou::wgpu::TextureAspect::All,
-                },
-                &right_rgba,
-                layout,
-                size,
-            );
-        }
+            let right_rgba = led_colors_to_rgba(&model.show.led_colors_right, pi.width, pi.height);
+            if let Some(img) = model.ui.image_map.get(&pi.right_id) {
+                queue.write_texture(
+                    nannou::wgpu::ImageCopyTexture {
+                        texture: &img.texture,
+                        mip_level: 0,
+                        origin: nannou::wgpu::Origin3d::ZERO,
+                        aspect: nannou::wgpu::TextureAspect::All,
+                    },
+                    &right_rgba,
+                    layout,
+                    size,
+                );
+            }
 
-        let colourise_rgba = led_colors_to_rgba(&model.led_colors_colourise, pi.width, pi.height);
-        if let Some(img) = model.ui.image_map.get(&pi.colourise_id) {
-            queue.write_texture(
-                nannou::wgpu::ImageCopyTexture {
-                    texture: &img.texture,
-                    mip_level: 0,
-                    origin: nannou::wgpu::Origin3d::ZERO,
-                    aspect: nannou::wgpu::TextureAspect::All,
-                },
-                &colourise_rgba,
-                layout,
-                size,
-            );
+            let colourise_rgba =
+                led_colors_to_rgba(&model.show.led_colors_colourise, pi.width, pi.height);
+            if let Some(img) = model.ui.image_map.get(&pi.colourise_id) {
+                queue.write_texture(
+                    nannou::wgpu::ImageCopyTexture {
+                        texture: &img.texture,
+                        mip_level: 0,
+                        origin: nannou::wgpu::Origin3d::ZERO,
+                        aspect: nannou::wgpu::TextureAspect::All,
+                    },
+                    &colourise_rgba,
+                    layout,
+                    size,
+                );
+            }
         }
 
-        if !model.led_colors_hover.is_empty() {
-            let hover_rgba = led_colors_to_rgba(&model.led_colors_hover, pi.width, pi.height);
+        if !model.show.led_colors_hover.is_empty() {
+            let hover_rgba = led_colors_to_rgba(&model.show.led_colors_hover, pi.width, pi.height);
             if let Some(img) = model.ui.image_map.get(&pi.hover_id) {
                 queue.write_texture(
                     nannou::wgpu::ImageCopyTexture {
@@ -827,119 +1404,421 @@ fn update_preview_textures(app: &App, model: &mut Model) {
     }
 }
 
-fn convert_channel(f: f32) -> u8 {
-    (f.clamp(0.0, 1.0) * 255.0) as u8
+/// Quantise a channel in `0..=1` to `0..=max`.
+///
+/// With an `error` accumulator the rounding error is carried into the next frame (temporal
+/// error diffusion) so that values between two steps average out over time.
+fn quantise_channel(value: f32, max: f32, error: Option<&mut f32>) -> u16 {
+    let scaled = value.clamp(0.0, 1.0) * max;
+    let Some(error) = error else {
+        return scaled as u16;
+    };
+    // Keep true black black rather than letting leftover error flicker.
+    if scaled <= 0.0 {
+        *error = 0.0;
+        return 0;
+    }
+    let target = scaled + *error;
+    let quantised = target.round().clamp(0.0, max);
+    *error = target - quantised;
+    quantised as u16
 }
 
-// Convert the floating point f32 representation to bytes.
-fn lin_srgb_f32_to_bytes(lin_srgb: &LinSrgb) -> [u8; 3] {
-    let r = convert_channel(lin_srgb.red);
-    let g = convert_channel(lin_srgb.green);
-    let b = convert_channel(lin_srgb.blue);
-    [r, g, b]
-}
+/// The maximum number of DMX channels a single pixel may occupy.
+const MAX_CHANNELS_PER_PIXEL: usize = 16;
 
-fn build_led_sacn_payloads(
-    start_universe: u16,
-    rgb_triplets: impl IntoIterator<Item = [u8; 3]>,
-) -> Vec<(u16, Vec<u8>)> {
-    let mut payloads = Vec::new();
-    let mut universe = start_universe;
-    let mut payload = vec![0];
+/// The number of `PixelChannel` kinds, each of which gets its own dither accumulator.
+const PIXEL_CHANNEL_KINDS: usize = 5;
 
-    for rgb in rgb_triplets {
-        payload.extend(rgb);
+/// The quantisation error carried between frames for a single LED, indexed by `PixelChannel`.
+type DitherError = [f32; PIXEL_CHANNEL_KINDS];
 
-        // Full universes carry 170 RGB pixels = 510 DMX slots. Reserve the
-        // last 2 slots as zeros so the next RGB triplet always starts on a new
-        // universe boundary instead of being split 2/1 across universes.
-        if payload.len() >= (UNIVERSE_CHANNEL_CAPACITY - 2) {
-            payload.push(0);
-            payload.push(0);
-            payloads.push((universe, payload));
-            universe += 1;
-            payload = vec![0];
-        }
-    }
+/// The level each channel of a pixel is driven at, indexed by `PixelChannel`.
+type PixelLevels = [f32; PIXEL_CHANNEL_KINDS];
 
-    // Intentionally do not emit a trailing empty universe when the LED data
-    // lands exactly on a 170-pixel boundary. The old loop did that
-    // accidentally by always sending after draining the last full packet.
-    if payload.len() > 1 {
-        payloads.push((universe, payload));
-    }
+/// How a fixture expects its pixels to be laid out in DMX.
+#[derive(Clone, Copy, Debug)]
+struct PixelFormat {
+    order: conf::ChannelOrder,
+    channels_per_pixel: usize,
+    bit_depth: conf::DmxBitDepth,
+}
 
-    payloads
+/// The DMX channel values for a single pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PixelChannels {
+    values: [u8; MAX_CHANNELS_PER_PIXEL],
+    len: usize,
 }
 
+impl AsRef<[u8]> for PixelChannels {
+    fn as_ref(&self) -> &[u8] {
+        &self.values[..self.len]
+    }
+}
+
+/// Apply a calibration profile's white balance, transfer curve and brightness ceiling.
+fn calibrate_colour(colour: &LinSrgb, profile: &conf::CalibrationProfile) -> LinSrgb {
+    let [r_gain, g_gain, b_gain] = profile.white_balance;
+    let max_level = profile.max_level.clamp(0.0, 1.0);
+    let correct = |value: f32, gain: f32| {
+        calibration_curve((value * gain).clamp(0.0, 1.0), profile) * max_level
+    };
+    lin_srgb(
+        correct(colour.red, r_gain),
+        correct(colour.green, g_gain),
+        correct(colour.blue, b_gain),
+    )
+}
+
+fn calibration_curve(value: f32, profile: &conf::CalibrationProfile) -> f32 {
+    let lut = &profile.lut;
+    if lut.len() < 2 {
+        return value.powf(profile.gamma.max(f32::EPSILON));
+    }
+    let position = value * (lut.len() - 1) as f32;
+    let ix = (position as usize).min(lut.len() - 2);
+    let fract = position - ix as f32;
+    let out = lut[ix] + (lut[ix + 1] - lut[ix]) * fract;
+    out.clamp(0.0, 1.0)
+}
+
+/// The level of each channel of a fixture with the given channel order for a linear colour.
+///
+/// White and amber channels are extracted from the part of the colour they can reproduce, so
+/// RGBW and RGBA fixtures show the same colour as RGB ones. Channels not named by `order` stay at
+/// zero.
+fn pixel_levels(
+    colour: &LinSrgb,
+    order: conf::ChannelOrder,
+    profile: Option<&conf::CalibrationProfile>,
+) -> PixelLevels {
+    use conf::PixelChannel;
+
+    let colour = match profile {
+        Some(profile) => calibrate_colour(colour, profile),
+        None => *colour,
+    };
+    let channels = order.channels();
+    let mut r = colour.red.clamp(0.0, 1.0);
+    let mut g = colour.green.clamp(0.0, 1.0);
+    let mut b = colour.blue.clamp(0.0, 1.0);
+    let mut w = 0.0;
+    let mut a = 0.0;
+    if channels.contains(&PixelChannel::White) {
+        w = r.min(g).min(b);
+        r -= w;
+        g -= w;
+        b -= w;
+    }
+    if channels.contains(&PixelChannel::Amber) {
+        // Treat amber as roughly full red plus half green.
+        a = r.min(g * 2.0);
+        r -= a;
+        g -= a * 0.5;
+    }
+
+    let mut levels = [0.0; PIXEL_CHANNEL_KINDS];
+    for &channel in channels {
+        levels[channel as usize] = match channel {
+            PixelChannel::Red => r,
+            PixelChannel::Green => g,
+            PixelChannel::Blue => b,
+            PixelChannel::White => w,
+            PixelChannel::Amber => a,
+        };
+    }
+    levels
+}
+
+/// Pack channel levels into the channel order of a fixture.
+///
+/// Any channels beyond those named by the order (e.g. a 4 channel pixel driven as RGB) are left
+/// at zero. In 16-bit mode each channel occupies two slots, coarse then fine.
+fn pack_pixel_levels(
+    levels: &PixelLevels,
+    format: PixelFormat,
+    mut dither: Option<&mut DitherError>,
+) -> PixelChannels {
+    use conf::DmxBitDepth;
+
+    let channels = format.order.channels();
+    let slots_per_channel = format.bit_depth.slots_per_channel();
+    let mut values = [0; MAX_CHANNELS_PER_PIXEL];
+    for (slots, &channel) in values.chunks_exact_mut(slots_per_channel).zip(channels) {
+        let value = levels[channel as usize];
+        let error = dither
+            .as_deref_mut()
+            .map(|error| &mut error[channel as usize]);
+        match format.bit_depth {
+            DmxBitDepth::Eight => {
+                slots[0] = quantise_channel(value, u8::MAX as f32, error) as u8;
+            }
+            DmxBitDepth::Sixteen => {
+                let value = quantise_channel(value, u16::MAX as f32, error);
+                slots.copy_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+    let len = format
+        .channels_per_pixel
+        .max(channels.len() * slots_per_channel)
+        .min(MAX_CHANNELS_PER_PIXEL);
+    PixelChannels { values, len }
+}
+
+fn build_led_sacn_payloads<P: AsRef<[u8]>>(
+    start_universe: u16,
+    pixels: impl IntoIterator<Item = P>,
+) -> Vec<(u16, Vec<u8>)> {
+    let mut payloads = Vec::new();
+    let mut universe = start_universe;
+    let mut payload = vec![0];
+
+    for pixel in pixels {
+        let pixel = pixel.as_ref();
+        payload.extend_from_slice(pixel);
+
+        // Never split a pixel across universes. Once the next pixel would no
+        // longer fit, pad the rest of the universe with zeros so it starts on a
+        // new universe boundary. For RGB, full universes carry 170 pixels = 510
+        // DMX slots with the last 2 slots reserved as zeros.
+        if payload.len() + pixel.len() > UNIVERSE_CHANNEL_CAPACITY {
+            payload.resize(UNIVERSE_CHANNEL_CAPACITY, 0);
+            payloads.push((universe, payload));
+            universe += 1;
+            payload = vec![0];
+        }
+    }
+
+    // Intentionally do not emit a trailing empty universe when the LED data
+    // lands exactly on a 170-pixel boundary. The old loop did that
+    // accidentally by always sending after draining the last full packet.
+    if payload.len() > 1 {
+        payloads.push((universe, payload));
+    }
+
+    payloads
+}
+
+/// Settings that shape how LED colours are packed into DMX slots.
+struct PixelOutputConfig<'a> {
+    channel_layouts: &'a conf::ChannelLayouts,
+    calibration: &'a conf::Calibration,
+    bit_depth: conf::DmxBitDepth,
+    /// Used to find the manual layout row of each LED for row calibration.
+    leds_per_row: usize,
+    /// The power limit scale of each LED. LEDs beyond the end are unscaled.
+    power_scales: &'a [f32],
+}
+
+impl PixelOutputConfig<'_> {
+    /// Pack the LED at `led_ix`, with its channel levels scaled by its power limit.
+    fn pack_led(
+        &self,
+        led_ix: usize,
+        colour: &LinSrgb,
+        format: PixelFormat,
+        profile: Option<&conf::CalibrationProfile>,
+        dither: Option<&mut DitherError>,
+    ) -> PixelChannels {
+        let scale = self.power_scales.get(led_ix).copied().unwrap_or(1.0);
+        let levels = pixel_levels(colour, format.order, profile).map(|level| level * scale);
+        pack_pixel_levels(&levels, format, dither)
+    }
+}
+
+fn pixel_output_config<'a>(
+    config: &'a LedWorkerConfig,
+    power_scales: &'a [f32],
+) -> PixelOutputConfig<'a> {
+    PixelOutputConfig {
+        channel_layouts: &config.channel_layouts,
+        calibration: &config.calibration,
+        bit_depth: config.dmx_bit_depth,
+        leds_per_row: config.led_layout.leds_per_row(),
+        power_scales,
+    }
+}
+
+/// The summed channel levels each LED is driven at, for estimating its power draw.
+///
+/// These follow the same channel orders and calibration as the packed output, so white and amber
+/// extraction and calibration curves are accounted for. `rgb_only` matches the RGB frames sent by
+/// OPC and DDP. LEDs that aren't output by a per-fixture layout are left at zero.
+fn led_drive_levels(
+    dmx_map: Option<&layout::DmxMap>,
+    output: &PixelOutputConfig,
+    rgb_only: bool,
+    led_outputs: &[LinSrgb],
+    levels: &mut Vec<f32>,
+) {
+    let drive = |colour: &LinSrgb, order, profile| -> f32 {
+        pixel_levels(colour, order, profile).iter().sum()
+    };
+    levels.clear();
+    levels.resize(led_outputs.len(), 0.0);
+    match dmx_map {
+        Some(layout::DmxMap::PerFixture(entries)) => {
+            for entry in entries {
+                let order = if rgb_only {
+                    conf::ChannelOrder::Rgb
+                } else {
+                    output.channel_layouts.for_fixture(&entry.fixture_name)
+                };
+                let profile = output.calibration.for_fixture(&entry.fixture_name);
+                let end = (entry.led_offset + entry.led_count).min(led_outputs.len());
+                for led_ix in entry.led_offset.min(end)..end {
+                    levels[led_ix] = drive(&led_outputs[led_ix], order, profile);
+                }
+            }
+        }
+        Some(layout::DmxMap::Sequential { .. }) | None => {
+            let order = if rgb_only {
+                conf::ChannelOrder::Rgb
+            } else {
+                output.channel_layouts.default
+            };
+            let leds_per_row = output.leds_per_row.max(1);
+            for (led_ix, (level, colour)) in levels.iter_mut().zip(led_outputs).enumerate() {
+                let profile = output.calibration.for_row(led_ix / leds_per_row);
+                *level = drive(colour, order, profile);
+            }
+        }
+    }
+}
+
+/// One dither accumulator per LED when dithering is enabled.
+fn led_dither_errors(
+    errors: &mut Vec<DitherError>,
+    led_count: usize,
+    enabled: bool,
+) -> Option<&mut [DitherError]> {
+    if enabled {
+        errors.resize(led_count, [0.0; PIXEL_CHANNEL_KINDS]);
+        Some(&mut errors[..])
+    } else {
+        None
+    }
+}
+
+/// Pack `led_outputs` into one frame of 8-bit RGB data per OPC or DDP destination.
+///
+/// Both protocols define RGB pixel data, so fixture channel orders and bit depth don't apply.
+fn build_pixel_frames(
+    frame_layouts: &[layout::PixelFrameLayout],
+    output: &PixelOutputConfig,
+    led_outputs: &[LinSrgb],
+    mut dither_errors: Option<&mut [DitherError]>,
+) -> Vec<(SocketAddr, Vec<u8>)> {
+    let format = PixelFormat {
+        order: conf::ChannelOrder::Rgb,
+        channels_per_pixel: 3,
+        bit_depth: conf::DmxBitDepth::Eight,
+    };
+    let leds_per_row = output.leds_per_row.max(1);
+    frame_layouts
+        .iter()
+        .map(|frame| {
+            let mut rgb = Vec::new();
+            for range in &frame.led_ranges {
+                let fixture_profile = range
+                    .fixture_name
+                    .as_ref()
+                    .map(|name| output.calibration.for_fixture(name));
+                let end = range.leds.end.min(led_outputs.len());
+                for led_ix in range.leds.start.min(end)..end {
+                    let profile = fixture_profile
+                        .unwrap_or_else(|| output.calibration.for_row(led_ix / leds_per_row));
+                    let dither = dither_errors
+                        .as_deref_mut()
+                        .and_then(|errors| errors.get_mut(led_ix));
+                    let pixel =
+                        output.pack_led(led_ix, &led_outputs[led_ix], format, profile, dither);
+                    rgb.extend_from_slice(pixel.as_ref());
+                }
+            }
+            (frame.destination, rgb)
+        })
+        .collect()
+}
+
+/// The port used for OPC and DDP destinations that don't give one.
+fn pixel_destination_port(protocol: conf::DmxProtocol) -> u16 {
+    match protocol {
+        conf::DmxProtocol::Ddp => ddp_sender::DDP_PORT,
+        _ => opc_sender::OPC_PORT,
+    }
+}
+
+/// Pack `led_outputs` into per-universe payloads.
+///
+/// When `dither_errors` is given it must hold one accumulator per LED and is updated in place.
 fn build_sacn_payloads(
     dmx_map: Option<&layout::DmxMap>,
     fallback_start_universe: u16,
+    output: &PixelOutputConfig,
     led_outputs: &[LinSrgb],
+    mut dither_errors: Option<&mut [DitherError]>,
 ) -> Vec<(u16, Vec<u8>)> {
-    match dmx_map {
+    let start_universe = match dmx_map {
         Some(layout::DmxMap::PerFixture(entries)) => {
-            build_per_fixture_payloads(entries, led_outputs)
+            return build_per_fixture_payloads(entries, output, led_outputs, dither_errors);
         }
-        Some(layout::DmxMap::Sequential { start_universe }) => build_led_sacn_payloads(
-            *start_universe,
-            led_outputs.iter().map(lin_srgb_f32_to_bytes),
-        ),
-        None => build_led_sacn_payloads(
-            fallback_start_universe,
-            led_outputs.iter().map(lin_srgb_f32_to_bytes),
-        ),
-    }
+        Some(layout::DmxMap::Sequential { start_universe }) => *start_universe,
+        None => fallback_start_universe,
+    };
+    let format = PixelFormat {
+        order: output.channel_layouts.default,
+        channels_per_pixel: DMX_ADDRS_PER_LED as usize,
+        bit_depth: output.bit_depth,
+    };
+    let leds_per_row = output.leds_per_row.max(1);
+    build_led_sacn_payloads(
+        start_universe,
+        led_outputs.iter().enumerate().map(|(led_ix, colour)| {
+            let profile = output.calibration.for_row(led_ix / leds_per_row);
+            let dither = dither_errors
+                .as_deref_mut()
+                .and_then(|errors| errors.get_mut(led_ix));
+            output.pack_led(led_ix, colour, format, profile, dither)
+        }),
+    )
 }
 
 fn build_per_fixture_payloads(
     entries: &[layout::FixtureDmxEntry],
+    output: &PixelOutputConfig,
     led_outputs: &[LinSrgb],
+    mut dither_errors: Option<&mut [DitherError]>,
 ) -> Vec<(u16, Vec<u8>)> {
     let mut all_payloads: Vec<(u16, Vec<u8>)> = Vec::new();
     for entry in entries {
         let end = (entry.led_offset + entry.led_count).min(led_outputs.len());
         let fixture_leds = &led_outputs[entry.led_offset..end];
+        let format = PixelFormat {
+            order: output.channel_layouts.for_fixture(&entry.fixture_name),
+            channels_per_pixel: entry.channels_per_pixel as usize,
+            bit_depth: output.bit_depth,
+        };
+        let profile = output.calibration.for_fixture(&entry.fixture_name);
+        let mut fixture_errors = dither_errors
+            .as_deref_mut()
+            .and_then(|errors| errors.get_mut(entry.led_offset..end));
         let fixture_payloads = build_led_sacn_payloads(
             entry.start_universe,
-            fixture_leds.iter().map(lin_srgb_f32_to_bytes),
+            fixture_leds.iter().enumerate().map(|(ix, colour)| {
+                let dither = fixture_errors
+                    .as_deref_mut()
+                    .and_then(|errors| errors.get_mut(ix));
+                output.pack_led(entry.led_offset + ix, colour, format, profile, dither)
+            }),
         );
         all_payloads.extend(fixture_payloads);
     }
     all_payloads
 }
 
-fn normalised_led_coord(index: usize, count: usize) -> f32 {
-    if count <= 1 {
-        0.0
-    } else {
-        (index as f32 / (count - 1) as f32) * 2.0 - 1.0
-    }
-}
-
-pub fn rebuild_led_shader_inputs(led_layout: &conf::LedLayout) -> Vec<CachedLedShaderInput> {
-    layout::led_positions_metres(led_layout)
-        .enumerate()
-        .map(|(led_ix, (row, x, h))| {
-            let position = layout::topdown_metres_to_shader_coords(
-                pt2(x, layout::SHADER_ORIGIN_METRES[1]),
-                h,
-                led_layout,
-            );
-            let col = led_ix % led_layout.leds_per_row();
-            let light = Light::Led {
-                index: led_ix,
-                col_row: [col, row],
-                normalised_coords: vec2(
-                    normalised_led_coord(col, led_layout.leds_per_row()),
-                    normalised_led_coord(row, led_layout.row_count),
-                ),
-            };
-            CachedLedShaderInput { position, light }
-        })
-        .collect()
-}
-
 fn build_led_worker_input_state(
     app_time: f32,
     master_speed: f32,
@@ -948,77 +1827,170 @@ fn build_led_worker_input_state(
     preset: &conf::Preset,
     audio_input: &audio_input::AudioInput,
     colour_channels: [f32; 3],
-    left_panel_tab: gui::LeftPanelTab,
+    showing: GuiShowing,
     resolved_layout: &Option<layout::ResolvedLayout>,
 ) -> LedWorkerInputState {
-    let resolved_layout = resolved_layout.clone();
     LedWorkerInputState {
         app_time,
         snapshot_at: Instant::now(),
-        config: LedWorkerConfig {
-            dmx_on: global_config.dmx_on,
-            sacn_interface_ip: global_config.sacn_interface_ip.clone(),
-            led_output_fps: global_config.led_output_fps,
-            led_start_universe: global_config.led_start_universe,
-            fade_to_black_led: global_config.fade_to_black.led,
-            preset_lerp_secs: global_config.preset_lerp_secs,
+        config: led_worker_config(
+            global_config,
+            preset,
             master_speed,
             phase_offset,
-            phase_offset_mod_amount: global_config.phase_offset_mod_amount,
-            led_layout: global_config.led_layout.clone(),
-            preset: preset.clone(),
             resolved_layout,
-        },
+        ),
         colour_channels,
         audio_envelope: audio_input.envelope,
         buttons: Default::default(),
-        capture_output_monitor: left_panel_tab == gui::LeftPanelTab::Output,
+        capture_output_monitor: showing.output_monitor,
+        render_shader_previews: showing.shader_previews,
+        dmx_capture: dmx_recording::CaptureRequest::default(),
+    }
+}
+
+/// The parts of the global config and preset that the LED worker renders and sends with.
+fn led_worker_config(
+    global_config: &GlobalConfig,
+    preset: &conf::Preset,
+    master_speed: f32,
+    phase_offset: f32,
+    resolved_layout: &Option<layout::ResolvedLayout>,
+) -> LedWorkerConfig {
+    let mut resolved_layout = resolved_layout.clone();
+    if let Some(resolved_layout) = resolved_layout.as_mut() {
+        layout::assign_pixel_destinations(
+            resolved_layout,
+            &global_config.pixel_destinations,
+            pixel_destination_port(global_config.dmx_protocol),
+        );
+    }
+    let pixel_frame_layouts = if global_config.dmx_protocol.sends_pixel_frames() {
+        let led_count = resolved_layout
+            .as_ref()
+            .map_or_else(|| global_config.led_layout.led_count(), |rl| rl.led_count);
+        layout::pixel_frame_layouts(
+            resolved_layout.as_ref().map(|rl| &rl.dmx_map),
+            led_count,
+            &global_config.pixel_destinations,
+            pixel_destination_port(global_config.dmx_protocol),
+        )
+    } else {
+        Vec::new()
+    };
+    LedWorkerConfig {
+        dmx_on: global_config.dmx_on,
+        dmx_off_fade_frames: global_config.dmx_off_fade_frames,
+        sacn_interface_ip: global_config.sacn_interface_ip.clone(),
+        mirror_interface_ip: global_config.mirror_interface_ip.clone(),
+        dmx_protocol: global_config.dmx_protocol,
+        artnet_destination_ip: global_config.artnet_destination_ip.clone(),
+        artnet_universe_offset: global_config.artnet_universe_offset,
+        artnet_sync_ip: global_config.artnet_sync_ip.clone(),
+        sacn_sync_universe: global_config.sacn_sync_universe,
+        sacn_priorities: global_config.sacn_priorities.clone(),
+        sacn_unicast_destinations: global_config.sacn_unicast_destinations.clone(),
+        sacn_input: global_config.sacn_input.clone(),
+        led_output_fps: global_config.led_output_fps,
+        shader_previews: global_config.shader_previews,
+        led_start_universe: global_config.led_start_universe,
+        fade_to_black_led: global_config.fade_to_black.led,
+        preset_lerp_secs: global_config.preset_lerp_secs,
+        master_speed,
+        phase_offset,
+        phase_offset_mod_amount: global_config.phase_offset_mod_amount,
+        led_layout: global_config.led_layout.clone(),
+        channel_layouts: global_config.channel_layouts.clone(),
+        calibration: global_config.calibration.clone(),
+        dmx_bit_depth: global_config.dmx_bit_depth,
+        led_dither: global_config.led_dither,
+        power_limit: global_config.power_limit.clone(),
+        strobe_limit: global_config.strobe_limit.clone(),
+        preset: preset.clone(),
+        resolved_layout,
+        pixel_frame_layouts,
     }
 }
 
-fn sync_led_buffers(model: &mut Model) {
-    let led_count = model
+fn sync_led_buffers(show: &mut Show) {
+    let led_count = show
         .mad_project
         .as_ref()
         .map(|p| p.total_pixels())
-        .unwrap_or_else(|| model.global_config.led_layout.led_count());
-    if model.led_colors.len() != led_count {
-        model.led_colors.resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model
-            .led_colors_left
+        .unwrap_or_else(|| show.global_config.led_layout.led_count());
+    if show.led_colors.len() != led_count {
+        show.led_colors.resize(led_count, lin_srgb(0.0, 0.0, 0.0));
+        show.led_colors_left
             .resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model
-            .led_colors_right
+        show.led_colors_right
             .resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model
-            .led_colors_colourise
+        show.led_colors_colourise
             .resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model
-            .led_colors_hover
+        show.led_colors_hover
             .resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model.led_outputs.resize(led_count, lin_srgb(0.0, 0.0, 0.0));
-        model.last_preset_change = None;
+        show.led_outputs.resize(led_count, lin_srgb(0.0, 0.0, 0.0));
+        show.last_preset_change = None;
     }
 }
 
 /// Copy the shader-specific fields from `src` into `dst` for the given shader type.
 
-fn apply_midi_values(model: &mut Model) {
+/// Set the target value of a control from an incoming MIDI or DMX value in `0..=1`.
+fn set_control_target(show: &mut Show, target: midi::mapping::MidiTarget, normalized: f32) {
+    if target == midi::mapping::MidiTarget::PresetSelect {
+        select_preset_from_control(show, normalized);
+    }
+    let state = show.midi_values.entry(target).or_insert(MidiTargetState {
+        target: normalized,
+        smoothed: normalized,
+        active: false,
+    });
+    state.target = normalized;
+    state.active = true;
+}
+
+/// Select the preset at the given position through the preset list, as the GUI would.
+fn select_preset_from_control(show: &mut Show, normalized: f32) {
+    let count = show.presets.list.len();
+    if count == 0 {
+        return;
+    }
+    let index = (normalized.clamp(0.0, 1.0) * (count - 1) as f32).round() as usize;
+    select_preset(show, index);
+}
+
+/// Select the preset at `index`, starting a transition from the current one.
+fn select_preset(show: &mut Show, index: usize) {
+    let count = show.presets.list.len();
+    if count == 0 || index == show.presets.selected_preset_idx {
+        return;
+    }
+    let outgoing_preset = show.presets.selected().clone();
+    show.presets.selected_preset_idx = index.min(count - 1);
+    show.presets.selected_preset_name = show.presets.selected().name.clone();
+    show.last_preset_change = Some(LastPresetChange {
+        started_at: Instant::now(),
+        preset: outgoing_preset,
+        led_colors: show.led_colors.clone(),
+    });
+}
+
+fn apply_midi_values(show: &mut Show) {
     use midi::mapping::MidiTarget;
-    for (&target, state) in &model.midi_values {
+    for (&target, state) in &show.midi_values {
         if !state.active {
             continue;
         }
         let v = state.smoothed;
         match target {
             MidiTarget::SmoothingSpeed => {
-                model.smoothing_speed = map_range(v, 0.0, 1.0, 0.0008, 0.08);
+                show.smoothing_speed = map_range(v, 0.0, 1.0, 0.0008, 0.08);
             }
             MidiTarget::MasterSpeed => {
-                model.global_config.master_speed = v;
+                show.global_config.master_speed = v;
             }
             MidiTarget::PhaseOffset => {
-                model.global_config.phase_offset = map_range(
+                show.global_config.phase_offset = map_range(
                     v,
                     0.0,
                     1.0,
@@ -1027,52 +1999,52 @@ fn apply_midi_values(model: &mut Model) {
                 );
             }
             MidiTarget::PhaseOffsetMod => {
-                model.global_config.phase_offset_mod_amount = v;
+                show.global_config.phase_offset_mod_amount = v;
             }
+            // Applied as values arrive so that the GUI can still change preset.
+            MidiTarget::PresetSelect => (),
             MidiTarget::FadeToBlack => {
-                model.global_config.fade_to_black.led = v;
+                show.global_config.fade_to_black.led = v;
             }
             MidiTarget::LeftRightMix => {
-                model.presets.selected_mut().left_right_mix = map_range(v, 0.0, 1.0, -1.0, 1.0);
+                show.presets.selected_mut().left_right_mix = map_range(v, 0.0, 1.0, -1.0, 1.0);
             }
             MidiTarget::AudioGain => {
-                model.audio_input.gain_db =
+                show.audio_input.gain_db =
                     map_range(v, 0.0, 1.0, 0.0, audio_input::MAX_INPUT_GAIN_DB);
             }
             MidiTarget::AudioThreshold => {
-                model.audio_input.threshold = v;
+                show.audio_input.threshold = v;
             }
             MidiTarget::AudioAttack => {
-                model.audio_input.attack = map_range(v, 0.0, 1.0, 0.001, 1.0);
+                show.audio_input.attack = map_range(v, 0.0, 1.0, 0.001, 1.0);
             }
             MidiTarget::AudioHold => {
-                model.audio_input.hold = map_range(v, 0.0, 1.0, 0.0, 2.0);
+                show.audio_input.hold = map_range(v, 0.0, 1.0, 0.0, 2.0);
             }
             MidiTarget::AudioRelease => {
-                model.audio_input.release = map_range(v, 0.0, 1.0, 0.01, 2.0);
+                show.audio_input.release = map_range(v, 0.0, 1.0, 0.01, 2.0);
             }
             MidiTarget::ColourChannel1 => {
-                model.colour_channels[0] = v;
+                show.colour_channels[0] = v;
             }
             MidiTarget::ColourChannel2 => {
-                model.colour_channels[1] = v;
+                show.colour_channels[1] = v;
             }
             MidiTarget::ColourChannel3 => {
-                model.colour_channels[2] = v;
+                show.colour_channels[2] = v;
             }
             MidiTarget::ColourPalette => {
-                model
-                    .presets
+                show.presets
                     .selected_mut()
                     .shader_params_colourise
                     .colour_palettes
                     .interval = map_range(v, 0.0, 1.0, 0.0, 1.0);
             }
             MidiTarget::ShaderLeftParam(n) => {
-                let preset = model.presets.selected_mut();
+                let preset = show.presets.selected_mut();
                 let shader = preset.shader_left;
-                let params: &mut dyn gui::Params =
-                    gui::shader_params(shader, &mut preset.shader_params_left);
+                let params = preset.shader_params_left.params_mut(shader);
                 if (n as usize) < params.param_count() {
                     let p = params.param_mut(n as usize);
                     if let gui::ParamKindMut::F32 { value, max } = p.kind {
@@ -1081,10 +2053,9 @@ fn apply_midi_values(model: &mut Model) {
                 }
             }
             MidiTarget::ShaderRightParam(n) => {
-                let preset = model.presets.selected_mut();
+                let preset = show.presets.selected_mut();
                 let shader = preset.shader_right;
-                let params: &mut dyn gui::Params =
-                    gui::shader_params(shader, &mut preset.shader_params_right);
+                let params = preset.shader_params_right.params_mut(shader);
                 if (n as usize) < params.param_count() {
                     let p = params.param_mut(n as usize);
                     if let gui::ParamKindMut::F32 { value, max } = p.kind {
@@ -1093,14 +2064,14 @@ fn apply_midi_values(model: &mut Model) {
                 }
             }
             MidiTarget::ShaderLeftMod(n) => {
-                let preset = model.presets.selected_mut();
+                let preset = show.presets.selected_mut();
                 let idx = n as usize;
                 if idx < preset.shader_mod_amounts_left.len() {
                     preset.shader_mod_amounts_left[idx] = v;
                 }
             }
             MidiTarget::ShaderRightMod(n) => {
-                let preset = model.presets.selected_mut();
+                let preset = show.presets.selected_mut();
                 let idx = n as usize;
                 if idx < preset.shader_mod_amounts_right.len() {
                     preset.shader_mod_amounts_right[idx] = v;
@@ -1110,24 +2081,25 @@ fn apply_midi_values(model: &mut Model) {
     }
 }
 
-fn queue_led_worker_update(_app: &App, model: &mut Model) {
-    if let Ok(mut shared_input) = model.led_worker.shared_input.lock() {
+fn queue_led_worker_update(show: &mut Show, showing: GuiShowing) {
+    if let Ok(mut shared_input) = show.led_worker.shared_input.lock() {
         shared_input.latest_state = build_led_worker_input_state(
-            model.master_phase,
-            model.smoothed_master_speed,
-            model.smoothed_phase_offset,
-            &model.global_config,
-            &model.smoothed_preset,
-            &model.audio_input,
-            model.colour_channels,
-            model.left_panel_tab,
-            &model.resolved_layout,
+            show.master_phase,
+            show.smoothed_master_speed,
+            show.smoothed_phase_offset,
+            &show.global_config,
+            &show.smoothed_preset,
+            &show.audio_input,
+            show.colour_channels,
+            showing,
+            &show.resolved_layout,
         );
-        shared_input.latest_state.buttons = model.buttons.clone();
+        shared_input.latest_state.buttons = show.buttons.clone();
+        shared_input.latest_state.dmx_capture = show.dmx_capture.clone();
 
-        shared_input.hover_preview_request = model.hover_preview_request.clone();
+        shared_input.hover_preview_request = show.hover_preview_request.clone();
 
-        if let Some(last_preset_change) = model.last_preset_change.take() {
+        if let Some(last_preset_change) = show.last_preset_change.take() {
             shared_input.pending_preset_changes.push(last_preset_change);
         }
     }
@@ -1143,9 +2115,9 @@ fn smooth_shader_params_toward(
     let mut target = target;
     let mut smoothed = target;
 
-    let current_params: &mut dyn gui::Params = gui::shader_params(shader, &mut current);
-    let target_params: &mut dyn gui::Params = gui::shader_params(shader, &mut target);
-    let smoothed_params: &mut dyn gui::Params = gui::shader_params(shader, &mut smoothed);
+    let current_params = current.params_mut(shader);
+    let target_params = target.params_mut(shader);
+    let smoothed_params = smoothed.params_mut(shader);
 
     for ix in 0..target_params.param_count() {
         let gui::ParamMut {
@@ -1183,102 +2155,110 @@ fn smooth_shader_params_toward(
     smoothed
 }
 
-fn update_smoothed_preset(model: &mut Model) {
-    let target = model.presets.selected().clone();
-    let should_reset = model.smoothed_preset.id != target.id
-        || model.smoothed_preset.shader_left != target.shader_left
-        || model.smoothed_preset.colourise != target.colourise
-        || model.smoothed_preset.shader_right != target.shader_right;
+fn update_smoothed_preset(show: &mut Show) {
+    let target = show.presets.selected().clone();
+    let should_reset = show.smoothed_preset.id != target.id
+        || show.smoothed_preset.shader_left != target.shader_left
+        || show.smoothed_preset.colourise != target.colourise
+        || show.smoothed_preset.shader_right != target.shader_right;
 
     if should_reset {
-        model.smoothed_preset = target;
+        show.smoothed_preset = target;
         return;
     }
 
-    let current = model.smoothed_preset.clone();
-    model.smoothed_preset = target.clone();
-    model.smoothed_preset.shader_params_left = smooth_shader_params_toward(
+    let current = show.smoothed_preset.clone();
+    show.smoothed_preset = target.clone();
+    show.smoothed_preset.shader_params_left = smooth_shader_params_toward(
         target.shader_left,
         current.shader_params_left,
         target.shader_params_left,
-        model.smoothing_speed,
+        show.smoothing_speed,
     );
-    model.smoothed_preset.shader_params_colourise = smooth_shader_params_toward(
+    show.smoothed_preset.shader_params_colourise = smooth_shader_params_toward(
         target.colourise,
         current.shader_params_colourise,
         target.shader_params_colourise,
-        model.smoothing_speed,
+        show.smoothing_speed,
     );
-    model.smoothed_preset.shader_params_right = smooth_shader_params_toward(
+    show.smoothed_preset.shader_params_right = smooth_shader_params_toward(
         target.shader_right,
         current.shader_params_right,
         target.shader_params_right,
-        model.smoothing_speed,
+        show.smoothing_speed,
     );
 }
 
-fn update_smoothed_master_speed(model: &mut Model) {
-    let target = model.global_config.master_speed;
-    model.smoothed_master_speed = model.smoothed_master_speed * (1.0 - model.smoothing_speed)
-        + target * model.smoothing_speed;
+fn update_smoothed_master_speed(show: &mut Show) {
+    let target = show.global_config.master_speed;
+    show.smoothed_master_speed =
+        show.smoothed_master_speed * (1.0 - show.smoothing_speed) + target * show.smoothing_speed;
 
-    let phase_offset_target = model.global_config.phase_offset;
-    model.smoothed_phase_offset = model.smoothed_phase_offset * (1.0 - model.smoothing_speed)
-        + phase_offset_target * model.smoothing_speed;
+    let phase_offset_target = show.global_config.phase_offset;
+    show.smoothed_phase_offset = show.smoothed_phase_offset * (1.0 - show.smoothing_speed)
+        + phase_offset_target * show.smoothing_speed;
 }
 
-fn apply_led_worker_output(model: &mut Model) {
-    let Ok(shared_output) = model.led_worker.shared_output.lock() else {
+fn apply_led_worker_output(show: &mut Show) {
+    let Ok(shared_output) = show.led_worker.shared_output.lock() else {
         return;
     };
 
-    if shared_output.frame_id == model.led_worker.last_applied_frame_id {
+    if shared_output.frame_id == show.led_worker.last_applied_frame_id {
         return;
     }
 
-    model.led_worker.last_applied_frame_id = shared_output.frame_id;
-    model.led_colors.clone_from(&shared_output.led_colors);
-    model
-        .led_colors_left
+    show.led_worker.last_applied_frame_id = shared_output.frame_id;
+    show.led_colors.clone_from(&shared_output.led_colors);
+    show.led_colors_left
         .clone_from(&shared_output.led_colors_left);
-    model
-        .led_colors_right
+    show.led_colors_right
         .clone_from(&shared_output.led_colors_right);
-    model
-        .led_colors_colourise
+    show.led_colors_colourise
         .clone_from(&shared_output.led_colors_colourise);
-    model
-        .led_colors_hover
+    show.led_colors_hover
         .clone_from(&shared_output.led_colors_hover);
-    model.led_outputs.clone_from(&shared_output.led_outputs);
-    model.dmx.error = shared_output.dmx_error.clone();
-    model.dmx.last_send_route = shared_output.last_send_route;
-
-    let selected_universe = model.dmx.monitor.selected_universe;
-    model
-        .dmx
+    show.led_outputs.clone_from(&shared_output.led_outputs);
+    show.power_estimate
+        .clone_from(&shared_output.power_estimate);
+    show.strobe_intervening = shared_output.strobe_intervening;
+    show.dmx_capture_status
+        .clone_from(&shared_output.capture_status);
+    show.dmx.error = shared_output.dmx_error.clone();
+    show.dmx.last_send_route = shared_output.last_send_route;
+    show.dmx.input.clone_from(&shared_output.sacn_input);
+    show.shader_rx.set_panics(&shared_output.shader_panics);
+    show.runtime_stats.render_passes = shared_output.render_passes;
+
+    let selected_universe = show.dmx.monitor.selected_universe;
+    show.dmx
         .monitor
         .universes
         .clone_from(&shared_output.monitor.universes);
-    model.dmx.monitor.total_frames_sent = shared_output.monitor.total_frames_sent;
-    model.dmx.monitor.total_packets_sent = shared_output.monitor.total_packets_sent;
-    model.dmx.monitor.total_payload_bytes_sent = shared_output.monitor.total_payload_bytes_sent;
-    model.dmx.monitor.smoothed_frame_fps = shared_output.monitor.smoothed_frame_fps;
-    model.dmx.monitor.last_sent_at = shared_output.monitor.last_sent_at;
-    model.dmx.monitor.last_send_error = shared_output.monitor.last_send_error.clone();
-
-    model.dmx.monitor.selected_universe = selected_universe
+    show.dmx
+        .monitor
+        .destinations
+        .clone_from(&shared_output.monitor.destinations);
+    show.dmx.monitor.total_frames_sent = shared_output.monitor.total_frames_sent;
+    show.dmx.monitor.total_packets_sent = shared_output.monitor.total_packets_sent;
+    show.dmx.monitor.total_payload_bytes_sent = shared_output.monitor.total_payload_bytes_sent;
+    show.dmx.monitor.sync_universe = shared_output.monitor.sync_universe;
+    show.dmx.monitor.rejected_sync_universe = shared_output.monitor.rejected_sync_universe;
+    show.dmx.monitor.total_sync_packets_sent = shared_output.monitor.total_sync_packets_sent;
+    show.dmx.monitor.smoothed_frame_fps = shared_output.monitor.smoothed_frame_fps;
+    show.dmx.monitor.last_sent_at = shared_output.monitor.last_sent_at;
+    show.dmx.monitor.last_send_error = shared_output.monitor.last_send_error.clone();
+
+    show.dmx.monitor.selected_universe = selected_universe
         .filter(|selected| {
-            model
-                .dmx
+            show.dmx
                 .monitor
                 .universes
                 .iter()
                 .any(|snapshot| snapshot.universe == *selected)
         })
         .or_else(|| {
-            model
-                .dmx
+            show.dmx
                 .monitor
                 .universes
                 .first()
@@ -1288,6 +2268,8 @@ fn apply_led_worker_output(model: &mut Model) {
 
 struct LedWorkerRuntime {
     shader: Option<Shader>,
+    /// Swaps out shaders that panic in the loaded library.
+    shader_guard: shader::ShaderGuard,
     led_colors: Vec<LinSrgb>,
     led_colors_left: Vec<LinSrgb>,
     led_colors_right: Vec<LinSrgb>,
@@ -1295,12 +2277,23 @@ struct LedWorkerRuntime {
     led_colors_hover: Vec<LinSrgb>,
     led_color_buffer: Vec<LinSrgb>,
     led_outputs: Vec<LinSrgb>,
+    /// Per-LED quantisation error for temporal dithering.
+    dither_errors: Vec<DitherError>,
+    /// The summed channel levels of each LED, for the power estimate.
+    led_drive_levels: Vec<f32>,
+    power_limiter: power::PowerLimiter,
+    power_estimate: power::PowerEstimate,
+    strobe_limiter: strobe_limit::StrobeLimiter,
     led_shader_inputs: Vec<CachedLedShaderInput>,
     cached_led_layout: conf::LedLayout,
     /// True when currently using a MadMapper resolved layout.
     using_mad_layout: bool,
     preset_transitions: Vec<PresetTransitionState>,
+    /// Schedules and samples the isolated shader previews.
+    previews: preview::PreviewRenderer,
+    render_passes: RenderPassTimes,
     dmx: DmxRuntime,
+    capture: dmx_recording::CaptureRuntime,
 }
 
 impl LedWorkerRuntime {
@@ -1308,13 +2301,14 @@ impl LedWorkerRuntime {
         let (led_count, shader_inputs, using_mad) = match &config.resolved_layout {
             Some(rl) => (rl.led_count, rl.shader_inputs.clone(), true),
             None => {
-                let inputs = rebuild_led_shader_inputs(&config.led_layout);
+                let inputs = layout::manual_shader_inputs(&config.led_layout);
                 let count = inputs.len();
                 (count, inputs, false)
             }
         };
         Self {
             shader: None,
+            shader_guard: shader::ShaderGuard::default(),
             led_colors: black_led_buffer(led_count),
             led_colors_left: black_led_buffer(led_count),
             led_colors_right: black_led_buffer(led_count),
@@ -1322,18 +2316,34 @@ impl LedWorkerRuntime {
             led_colors_hover: black_led_buffer(led_count),
             led_color_buffer: black_led_buffer(led_count),
             led_outputs: black_led_buffer(led_count),
+            dither_errors: Vec::new(),
+            led_drive_levels: Vec::new(),
+            power_limiter: power::PowerLimiter::default(),
+            power_estimate: power::PowerEstimate::default(),
+            strobe_limiter: strobe_limit::StrobeLimiter::default(),
             led_shader_inputs: shader_inputs,
             cached_led_layout: config.led_layout.clone(),
             using_mad_layout: using_mad,
             preset_transitions: Vec::new(),
+            previews: preview::PreviewRenderer::default(),
+            render_passes: RenderPassTimes::default(),
             dmx: DmxRuntime {
                 source: None,
-                requested_interface_ip: None,
+                requested_source: None,
+                mirror: None,
+                requested_mirror: None,
+                mirror_error: None,
+                mirror_failed_at: None,
+                last_sent: LastSentOutput::default(),
+                stopping: None,
+                discovery: None,
                 error: None,
                 last_send_route: None,
                 last_send_attempt_at: None,
                 monitor: SacnOutputMonitor::default(),
+                input: SacnInputRuntime::default(),
             },
+            capture: dmx_recording::CaptureRuntime::default(),
         }
     }
 }
@@ -1374,11 +2384,12 @@ fn sync_led_worker_buffers(runtime: &mut LedWorkerRuntime, config: &LedWorkerCon
     if source_changed || runtime.led_shader_inputs.len() != led_count {
         runtime.led_shader_inputs = match new_inputs {
             Some(inputs) => inputs.clone(),
-            None => rebuild_led_shader_inputs(&config.led_layout),
+            None => layout::manual_shader_inputs(&config.led_layout),
         };
         runtime.cached_led_layout = config.led_layout.clone();
         runtime.using_mad_layout = now_mad;
         runtime.preset_transitions.clear();
+        runtime.previews.invalidate();
     }
 }
 
@@ -1411,11 +2422,13 @@ fn run_led_worker(
         };
 
         if shutdown {
+            finish_led_worker_output(&state, &mut runtime);
             break;
         }
 
         if let Some(shader) = pending_shader {
             runtime.shader = Some(shader);
+            runtime.shader_guard.clear();
         }
         for last_preset_change in pending_preset_changes {
             let led_colors = if last_preset_change.led_colors.len() == runtime.led_colors.len() {
@@ -1432,8 +2445,20 @@ fn run_led_worker(
             });
         }
 
-        sync_led_worker_buffers(&mut runtime, &state.config);
-        render_led_worker_frame(&state, &mut runtime, &hover_preview_request);
+        runtime.capture.sync(&state.dmx_capture);
+        if state.config.dmx_protocol.sends_pixel_frames() {
+            // Recordings hold universe payloads, which OPC and DDP can't carry.
+            runtime.capture.refuse_replay(
+                "Can't replay a DMX recording over OPC or DDP. Switch to sACN or Art-Net first.",
+            );
+        }
+        if runtime.capture.is_replaying() {
+            // Replayed frames go straight out without running any shaders.
+            update_led_worker_dmx(&state, &mut runtime);
+        } else {
+            sync_led_worker_buffers(&mut runtime, &state.config);
+            render_led_worker_frame(&state, &mut runtime, &hover_preview_request);
+        }
 
         frame_id = frame_id.wrapping_add(1);
         if let Ok(mut output) = shared_output.lock() {
@@ -1450,9 +2475,15 @@ fn run_led_worker(
                 .led_colors_hover
                 .clone_from(&runtime.led_colors_hover);
             output.led_outputs.clone_from(&runtime.led_outputs);
+            output.power_estimate.clone_from(&runtime.power_estimate);
+            output.strobe_intervening = runtime.strobe_limiter.is_intervening();
+            output.capture_status = runtime.capture.status();
             output.monitor = LedWorkerMonitorSnapshot::from_monitor(&runtime.dmx.monitor);
             output.dmx_error = runtime.dmx.error.clone();
             output.last_send_route = runtime.dmx.last_send_route;
+            output.sacn_input = runtime.dmx.input.status();
+            output.shader_panics = runtime.shader_guard.panics().to_vec();
+            output.render_passes = runtime.render_passes;
         }
 
         thread::yield_now();
@@ -1464,20 +2495,29 @@ fn render_led_worker_frame(
     runtime: &mut LedWorkerRuntime,
     hover_preview_request: &Option<HoverPreviewRequest>,
 ) {
-    let shader: ShaderFnPtr = runtime
+    let shader: ShaderFns = runtime
         .shader
         .as_ref()
-        .map(|shader| *shader.get_fn())
-        .unwrap_or(shader::black);
-    let uniforms = preset_uniforms(state, &state.config.preset);
-    render_preset_graph(
-        shader,
-        &runtime.led_shader_inputs,
-        &uniforms,
-        &runtime.led_colors,
-        &mut runtime.led_color_buffer,
-    );
+        .map(Shader::get_fns)
+        .unwrap_or(shader::BUILTIN);
+    let mut passes = RenderPassTimes::default();
+    let pass_started = Instant::now();
+    let uniforms = led_worker_uniforms(state, &state.config.preset);
+    runtime.shader_guard.render(&uniforms, |uniforms| {
+        render_preset_graph(
+            shader,
+            &runtime.led_shader_inputs,
+            uniforms,
+            &runtime.led_colors,
+            &mut runtime.led_color_buffer,
+        )
+    });
     std::mem::swap(&mut runtime.led_colors, &mut runtime.led_color_buffer);
+    passes.main = elapsed_ms(pass_started);
+
+    let previews = state.config.shader_previews;
+    let render_previews =
+        state.render_shader_previews && runtime.previews.due(&previews, Instant::now());
 
     // White colourise params used to bypass post-processing in left/right previews.
     let white_colourise = ShaderParams {
@@ -1490,7 +2530,8 @@ fn render_led_worker_frame(
     };
 
     // Compute isolated left preview (Add blend, left only, no colourise).
-    {
+    if render_previews {
+        let pass_started = Instant::now();
         let left_only_mix = MixingInfo {
             colourise: shader_shared::Shader::SolidRgbColour,
             blend_mode: shader_shared::BlendMode::Add,
@@ -1504,23 +2545,23 @@ fn render_led_worker_frame(
             mix: left_only_mix,
             ..uniforms.clone()
         };
-        runtime
-            .led_colors_left
-            .par_iter_mut()
-            .zip(runtime.led_shader_inputs.par_iter())
-            .zip(runtime.led_colors.par_iter())
-            .for_each(|((color, led_input), &last_color)| {
-                let vertex = Vertex {
-                    position: led_input.position,
-                    light: led_input.light,
-                    last_color,
-                };
-                *color = shader(vertex, &left_uniforms);
-            });
+        runtime.shader_guard.render(&left_uniforms, |uniforms| {
+            runtime.previews.render(
+                previews.resolution,
+                &runtime.led_shader_inputs,
+                &runtime.led_colors,
+                &mut runtime.led_colors_left,
+                |inputs, last_colors, output| {
+                    render_preset_graph(shader, inputs, uniforms, last_colors, output)
+                },
+            )
+        });
+        passes.left = elapsed_ms(pass_started);
     }
 
     // Compute isolated right preview (Add blend, right only, no colourise).
-    {
+    if render_previews {
+        let pass_started = Instant::now();
         let right_only_mix = MixingInfo {
             colourise: shader_shared::Shader::SolidRgbColour,
             blend_mode: shader_shared::BlendMode::Add,
@@ -1534,23 +2575,23 @@ fn render_led_worker_frame(
             mix: right_only_mix,
             ..uniforms.clone()
         };
-        runtime
-            .led_colors_right
-            .par_iter_mut()
-            .zip(runtime.led_shader_inputs.par_iter())
-            .zip(runtime.led_colors.par_iter())
-            .for_each(|((color, led_input), &last_color)| {
-                let vertex = Vertex {
-                    position: led_input.position,
-                    light: led_input.light,
-                    last_color,
-                };
-                *color = shader(vertex, &right_uniforms);
-            });
+        runtime.shader_guard.render(&right_uniforms, |uniforms| {
+            runtime.previews.render(
+                previews.resolution,
+                &runtime.led_shader_inputs,
+                &runtime.led_colors,
+                &mut runtime.led_colors_right,
+                |inputs, last_colors, output| {
+                    render_preset_graph(shader, inputs, uniforms, last_colors, output)
+                },
+            )
+        });
+        passes.right = elapsed_ms(pass_started);
     }
 
     // Compute isolated colourise preview.
-    {
+    if render_previews {
+        let pass_started = Instant::now();
         use shader_shared::SolidRgbColour;
         let colourise_only_mix = MixingInfo {
             left: shader_shared::Shader::SolidRgbColour,
@@ -1573,22 +2614,24 @@ fn render_led_worker_frame(
             ..uniforms.clone()
         };
         runtime
-            .led_colors_colourise
-            .par_iter_mut()
-            .zip(runtime.led_shader_inputs.par_iter())
-            .zip(runtime.led_colors.par_iter())
-            .for_each(|((color, led_input), &last_color)| {
-                let vertex = Vertex {
-                    position: led_input.position,
-                    light: led_input.light,
-                    last_color,
-                };
-                *color = shader(vertex, &colourise_uniforms);
+            .shader_guard
+            .render(&colourise_uniforms, |uniforms| {
+                runtime.previews.render(
+                    previews.resolution,
+                    &runtime.led_shader_inputs,
+                    &runtime.led_colors,
+                    &mut runtime.led_colors_colourise,
+                    |inputs, last_colors, output| {
+                        render_preset_graph(shader, inputs, uniforms, last_colors, output)
+                    },
+                )
             });
+        passes.colourise = elapsed_ms(pass_started);
     }
 
     // Hover preview: render only when a request is active.
     if let Some(ref request) = hover_preview_request {
+        let pass_started = Instant::now();
         let hover_uniforms = match request {
             HoverPreviewRequest::Shader(s) => {
                 let mix = MixingInfo {
@@ -1609,17 +2652,21 @@ fn render_led_worker_frame(
                     ..uniforms.clone()
                 }
             }
-            HoverPreviewRequest::Preset(preset) => preset_uniforms(state, preset),
+            HoverPreviewRequest::Preset(preset) => led_worker_uniforms(state, preset),
         };
-        render_preset_graph(
-            shader,
-            &runtime.led_shader_inputs,
-            &hover_uniforms,
-            &runtime.led_colors,
-            &mut runtime.led_colors_hover,
-        );
+        runtime.shader_guard.render(&hover_uniforms, |uniforms| {
+            render_preset_graph(
+                shader,
+                &runtime.led_shader_inputs,
+                uniforms,
+                &runtime.led_colors,
+                &mut runtime.led_colors_hover,
+            )
+        });
+        passes.hover = elapsed_ms(pass_started);
     }
 
+    let pass_started = Instant::now();
     if state.config.preset_lerp_secs <= 0.0 {
         runtime.preset_transitions.clear();
     } else {
@@ -1629,20 +2676,26 @@ fn render_led_worker_frame(
                 return false;
             }
 
-            let transition_uniforms = preset_uniforms(state, &transition.preset);
-            render_preset_graph(
-                shader,
-                &runtime.led_shader_inputs,
-                &transition_uniforms,
-                &transition.led_colors,
-                &mut transition.led_color_buffer,
-            );
+            let transition_uniforms = led_worker_uniforms(state, &transition.preset);
+            runtime
+                .shader_guard
+                .render(&transition_uniforms, |uniforms| {
+                    render_preset_graph(
+                        shader,
+                        &runtime.led_shader_inputs,
+                        uniforms,
+                        &transition.led_colors,
+                        &mut transition.led_color_buffer,
+                    )
+                });
             std::mem::swap(&mut transition.led_colors, &mut transition.led_color_buffer);
             transition.lerp_amt =
                 ease_in_out((elapsed_secs / state.config.preset_lerp_secs).clamp(0.0, 1.0));
             true
         });
     }
+    passes.transitions = elapsed_ms(pass_started);
+    runtime.render_passes = runtime.render_passes.smoothed_towards(passes);
 
     let ftb = state.config.fade_to_black_led;
     let l_ftb = lin_srgb(ftb, ftb, ftb);
@@ -1682,6 +2735,27 @@ fn render_led_worker_frame(
             });
     }
 
+    let now = Instant::now();
+    runtime
+        .strobe_limiter
+        .limit(&state.config.strobe_limit, &mut runtime.led_outputs, now);
+
+    let dmx_map = state.config.resolved_layout.as_ref().map(|rl| &rl.dmx_map);
+    led_drive_levels(
+        dmx_map,
+        &pixel_output_config(&state.config, &[]),
+        state.config.dmx_protocol.sends_pixel_frames(),
+        &runtime.led_outputs,
+        &mut runtime.led_drive_levels,
+    );
+    runtime.power_estimate = runtime.power_limiter.limit(
+        &state.config.power_limit,
+        dmx_map,
+        state.config.led_layout.leds_per_row(),
+        &runtime.led_drive_levels,
+        now,
+    );
+
     update_led_worker_dmx(state, runtime);
 }
 
@@ -1690,68 +2764,8 @@ fn ease_in_out(t: f32) -> f32 {
     t * t * (3.0 - 2.0 * t)
 }
 
-fn preset_uniforms(state: &LedWorkerInputState, preset: &conf::Preset) -> Uniforms {
-    let led_layout = &state.config.led_layout;
-
-    /*
-    when t is -1, volumes[0] = 0, volumes[1] = 1
-    when t = 0, volumes[0] = 0.707, volumes[1] = 0.707 (equal-power cross fade)
-    when t = 1, volumes[0] = 1, volumes[1] = 0
-    // Equal power xfade taken from https://dsp.stackexchange.com/questions/14754/equal-power-crossfade
-    */
-    let lr_mix = preset.left_right_mix;
-    let xfade_left = (0.5 * (1.0 + lr_mix)).sqrt();
-    let xfade_right = (0.5 * (1.0 - lr_mix)).sqrt();
-    let env = state.audio_envelope;
-
-    let mut params_left = preset.shader_params_left;
-    {
-        let mut mod_ix = 0;
-        gui::apply_shader_modulation(
-            preset.shader_left,
-            &mut params_left,
-            &mut mod_ix,
-            &preset.shader_mod_amounts_left,
-            env,
-        );
-    }
-    let mut params_colourise = preset.shader_params_colourise;
-    {
-        let mut mod_ix = 0;
-        gui::apply_shader_modulation(
-            preset.colourise,
-            &mut params_colourise,
-            &mut mod_ix,
-            &preset.shader_mod_amounts_colourise,
-            env,
-        );
-    }
-    let mut params_right = preset.shader_params_right;
-    {
-        let mut mod_ix = 0;
-        gui::apply_shader_modulation(
-            preset.shader_right,
-            &mut params_right,
-            &mut mod_ix,
-            &preset.shader_mod_amounts_right,
-            env,
-        );
-    }
-
-    let mix_info = MixingInfo {
-        left: preset.shader_left,
-        right: preset.shader_right,
-        colourise: preset.colourise,
-        blend_mode: preset.blend_mode,
-        tone_mapping: preset.tone_mapping,
-        tone_mapping_amount: preset.tone_mapping_amount,
-        xfade_left,
-        xfade_right,
-        params_left,
-        params_right,
-        params_colourise,
-    };
-
+/// The uniforms for rendering `preset` from the worker's latest input.
+fn led_worker_uniforms(state: &LedWorkerInputState, preset: &conf::Preset) -> Uniforms {
     let buttons = state
         .buttons
         .iter()
@@ -1771,60 +2785,45 @@ fn preset_uniforms(state: &LedWorkerInputState, preset: &conf::Preset) -> Unifor
     let time = state.app_time
         + state.snapshot_at.elapsed().as_secs_f32() * state.config.master_speed
         + phase_offset;
-    Uniforms {
+    render::preset_uniforms(
+        preset,
+        &state.config.led_layout,
         time,
-        resolution: layout::shader_resolution(led_layout),
-        pot6: state.colour_channels[0],
-        pot7: state.colour_channels[1],
-        pot8: state.colour_channels[2],
-        params: ShaderParams::default(),
-        mix: mix_info,
+        state.audio_envelope,
+        state.colour_channels,
         buttons,
-    }
-}
-
-fn render_preset_graph(
-    shader: ShaderFnPtr,
-    led_shader_inputs: &[CachedLedShaderInput],
-    uniforms: &Uniforms,
-    previous_led_colors: &[LinSrgb],
-    output: &mut [LinSrgb],
-) {
-    output
-        .par_iter_mut()
-        .zip(led_shader_inputs.par_iter())
-        .zip(previous_led_colors.par_iter())
-        .for_each(|((color, led_input), &last_color)| {
-            let vertex = Vertex {
-                position: led_input.position,
-                light: led_input.light,
-                last_color,
-            };
-            *color = shader(vertex, uniforms);
-        });
+    )
 }
 
 fn update_led_worker_dmx(state: &LedWorkerInputState, runtime: &mut LedWorkerRuntime) {
     if state.config.dmx_on {
-        if let Ok(desired_interface_ip) =
-            conf::parse_sacn_interface_ip(&state.config.sacn_interface_ip)
-        {
+        // Switching back on cancels a fade in progress.
+        runtime.dmx.stopping = None;
+        if let Ok(desired_source) = dmx_source_request(&state.config) {
             let should_refresh_source = runtime.dmx.source.is_none()
-                || runtime.dmx.requested_interface_ip != desired_interface_ip;
+                || runtime.dmx.requested_source != Some(desired_source);
             if should_refresh_source {
-                match create_dmx_source(desired_interface_ip) {
+                match create_dmx_source(desired_source) {
                     Ok(source) => {
                         runtime.dmx.source = Some(source);
-                        runtime.dmx.requested_interface_ip = desired_interface_ip;
+                        runtime.dmx.discovery = create_discovery_sender(desired_source);
+                        runtime.dmx.requested_source = Some(desired_source);
                         runtime.dmx.error = None;
                         runtime.dmx.last_send_route = None;
                         runtime.dmx.last_send_attempt_at = None;
                     }
                     Err(err) => {
-                        runtime.dmx.requested_interface_ip = desired_interface_ip;
-                        let error = match desired_interface_ip {
-                            Some(ip) => format!("Couldn't bind sACN to {}: {}", ip, err),
-                            None => format!("Couldn't auto-bind sACN: {}", err),
+                        runtime.dmx.requested_source = Some(desired_source);
+                        runtime.dmx.discovery = None;
+                        let protocol = match desired_source.protocol {
+                            conf::DmxProtocol::Sacn => "sACN",
+                            conf::DmxProtocol::ArtNet => "Art-Net",
+                            conf::DmxProtocol::Opc => "OPC",
+                            conf::DmxProtocol::Ddp => "DDP",
+                        };
+                        let error = match desired_source.interface_ip {
+                            Some(ip) => format!("Couldn't bind {} to {}: {}", protocol, ip, err),
+                            None => format!("Couldn't auto-bind {}: {}", protocol, err),
                         };
                         runtime.dmx.monitor.record_send_error(error.clone());
                         runtime.dmx.error = Some(error);
@@ -1834,107 +2833,572 @@ fn update_led_worker_dmx(state: &LedWorkerInputState, runtime: &mut LedWorkerRun
                 }
             }
         } else {
-            runtime.dmx.requested_interface_ip = None;
+            runtime.dmx.requested_source = None;
             runtime.dmx.error = None;
             runtime.dmx.last_send_route = None;
             runtime.dmx.last_send_attempt_at = None;
         }
-    } else if runtime.dmx.source.is_some() {
-        runtime.dmx.source.take();
-        runtime.dmx.requested_interface_ip = None;
-        runtime.dmx.error = None;
-        runtime.dmx.last_send_route = None;
-        runtime.dmx.last_send_attempt_at = None;
+    } else if runtime.dmx.source.is_some() || runtime.dmx.mirror.is_some() {
+        // Rather than dropping the transports, which leaves receivers holding the last frame,
+        // fade out at the output rate and then terminate each stream.
+        sync_sacn_input(&state.config, &mut runtime.dmx.input);
+        let now = Instant::now();
+        if should_send_led_output(
+            state.config.led_output_fps,
+            runtime.dmx.last_send_attempt_at,
+            now,
+        ) {
+            runtime.dmx.last_send_attempt_at = Some(now);
+            send_output_stop_frame(state, &mut runtime.dmx, now);
+        }
+        return;
     }
 
     let now = Instant::now();
-    let should_send_output = should_send_led_output(
-        state.config.led_output_fps,
-        runtime.dmx.last_send_attempt_at,
-        now,
-    );
-    let mut disconnect_source = false;
-    if should_send_output {
-        runtime.dmx.last_send_attempt_at = Some(now);
-    }
-    if should_send_output {
-        if let Some(ref mut dmx_source) = runtime.dmx.source {
-            let dmx_map = state.config.resolved_layout.as_ref().map(|rl| &rl.dmx_map);
-            let payloads = build_sacn_payloads(
-                dmx_map,
-                state.config.led_start_universe,
-                &runtime.led_outputs,
+    sync_mirror_output(&state.config, &mut runtime.dmx, now);
+    sync_sacn_input(&state.config, &mut runtime.dmx.input);
+
+    let payloads = match runtime.capture.replay_mut() {
+        // Replay follows the recording's own timing rather than the output rate.
+        Some(replay) => replay.next_due(now).map(|frame| frame.payloads.clone()),
+        None => {
+            let should_send_output = should_send_led_output(
+                state.config.led_output_fps,
+                runtime.dmx.last_send_attempt_at,
+                now,
             );
-            let mut sent_packet_count = 0usize;
-            let mut sent_payload_bytes = 0usize;
-            let mut sent_payloads = if state.capture_output_monitor {
-                Some(Vec::with_capacity(payloads.len()))
-            } else {
-                None
-            };
-            let mut last_send_route = None;
-            let mut send_error = None;
-
-            for (universe, payload) in payloads {
-                match dmx_source.send(universe, &payload) {
-                    Ok(route) => {
-                        last_send_route = Some(route);
-                        sent_packet_count += 1;
-                        sent_payload_bytes += payload.len();
-                        if let Some(ref mut snapshots) = sent_payloads {
-                            snapshots.push((universe, payload));
-                        }
-                    }
-                    Err(error) => {
-                        send_error = Some(error);
-                        disconnect_source = true;
-                        break;
-                    }
-                }
+            if should_send_output {
+                runtime.dmx.last_send_attempt_at = Some(now);
             }
-
-            if sent_packet_count > 0 {
-                let sent_at = Instant::now();
-                runtime.dmx.monitor.record_successful_frame_stats(
-                    sent_packet_count,
-                    sent_payload_bytes,
-                    sent_at,
+            if should_send_output && state.config.dmx_protocol.sends_pixel_frames() {
+                // Pixel frames bypass the universe pipeline, so there is nothing to merge or
+                // record.
+                send_led_worker_pixel_frames(state, runtime, now);
+                None
+            } else if should_send_output
+                && (runtime.dmx.source.is_some() || runtime.capture.is_recording())
+            {
+                let dmx_map = state.config.resolved_layout.as_ref().map(|rl| &rl.dmx_map);
+                let pixel_output =
+                    pixel_output_config(&state.config, runtime.power_limiter.led_scales());
+                let dither_errors = led_dither_errors(
+                    &mut runtime.dither_errors,
+                    runtime.led_outputs.len(),
+                    state.config.led_dither,
                 );
-                if let Some(ref snapshots) = sent_payloads {
-                    runtime
-                        .dmx
-                        .monitor
-                        .record_universe_snapshots(snapshots, sent_at);
-                }
-            }
-
-            if let Some(error) = send_error {
-                runtime.dmx.monitor.record_send_error(error.clone());
-                runtime.dmx.error = Some(error);
-                runtime.dmx.last_send_route = None;
-            } else if sent_packet_count > 0 {
-                runtime.dmx.error = None;
-                runtime.dmx.last_send_route = last_send_route;
+                let mut payloads = build_sacn_payloads(
+                    dmx_map,
+                    state.config.led_start_universe,
+                    &pixel_output,
+                    &runtime.led_outputs,
+                    dither_errors,
+                );
+                merge_sacn_input(&state.config, &mut runtime.dmx.input, &mut payloads);
+                // Only rendered output is recorded, never frames that are themselves a replay.
+                runtime.capture.record(now, &payloads);
+                Some(payloads)
+            } else {
+                None
+            }
+        }
+    };
+    if let Some(payloads) = payloads {
+        send_led_worker_payloads(state, &mut runtime.dmx, &payloads);
+        send_discovery(&mut runtime.dmx, &payloads, now);
+    }
+}
+
+/// Announce the universes being sent, at the E1.31 discovery interval.
+fn send_discovery(dmx: &mut DmxRuntime, payloads: &[(u16, Vec<u8>)], now: Instant) {
+    let Some(discovery) = dmx.discovery.as_mut() else {
+        return;
+    };
+    let universes: Vec<u16> = payloads.iter().map(|(universe, _)| *universe).collect();
+    if let Err(err) = discovery.send_if_due(&universes, now) {
+        dmx.monitor
+            .record_send_error(format!("Couldn't send sACN universe discovery: {}", err));
+    }
+}
+
+/// The outcome of sending one frame of universes through a single transport.
+#[derive(Default)]
+struct TransportFrameOutcome {
+    last_route: Option<DmxSendRoute>,
+    packet_count: usize,
+    payload_bytes: usize,
+    /// The universes that reached at least one destination.
+    sent_universes: Vec<u16>,
+    destinations: Vec<SacnDestinationStats>,
+    last_error: Option<String>,
+}
+
+impl TransportFrameOutcome {
+    /// Count a single send towards its destination, returning whether it succeeded.
+    fn record(
+        &mut self,
+        label: String,
+        payload_len: usize,
+        result: Result<DmxSendRoute, String>,
+    ) -> bool {
+        let index = match self
+            .destinations
+            .iter()
+            .position(|destination| destination.label == label)
+        {
+            Some(index) => index,
+            None => {
+                self.destinations.push(SacnDestinationStats {
+                    label,
+                    packets_sent: 0,
+                    last_error: None,
+                });
+                self.destinations.len() - 1
+            }
+        };
+        let destination = &mut self.destinations[index];
+        match result {
+            Ok(route) => {
+                destination.packets_sent += 1;
+                self.last_route = Some(route);
+                self.packet_count += 1;
+                self.payload_bytes += payload_len;
+                true
+            }
+            Err(error) => {
+                destination.last_error = Some(error.clone());
+                self.last_error = Some(error);
+                false
+            }
+        }
+    }
+}
+
+/// Send each universe to its unicast destinations, or through the transport's usual route if it
+/// has none. A failing destination is recorded and skipped rather than ending the frame.
+fn send_transport_frame(
+    transport: &mut DmxOutputTransport,
+    label_prefix: &str,
+    config: &LedWorkerConfig,
+    payloads: &[(u16, Vec<u8>)],
+    sync_universe: Option<u16>,
+) -> TransportFrameOutcome {
+    let supports_unicast = transport.supports_unicast();
+    let mut outcome = TransportFrameOutcome::default();
+    let mut used_route = false;
+    let mut unicast_sent_to: Vec<SocketAddr> = Vec::new();
+
+    for (universe, payload) in payloads {
+        let universe = *universe;
+        let priority = universe_priority(&config.sacn_priorities, universe);
+        let destinations = if supports_unicast {
+            unicast_destinations(&config.sacn_unicast_destinations, universe)
+        } else {
+            Vec::new()
+        };
+        let mut universe_sent = false;
+        if destinations.is_empty() {
+            let route = transport.route();
+            let result = transport.send(universe, payload, priority, sync_universe);
+            let label = dmx_send_route_label(*result.as_ref().unwrap_or(&route));
+            let label = format!("{}{}", label_prefix, label);
+            universe_sent |= outcome.record(label, payload.len(), result);
+            used_route = true;
+        }
+        for destination in destinations {
+            let result =
+                transport.send_unicast(destination, universe, payload, priority, sync_universe);
+            let label = format!("{}Unicast {}", label_prefix, destination.ip());
+            if outcome.record(label, payload.len(), result) {
+                universe_sent = true;
+                if !unicast_sent_to.contains(&destination) {
+                    unicast_sent_to.push(destination);
+                }
+            }
+        }
+        if universe_sent {
+            outcome.sent_universes.push(universe);
+        }
+    }
+
+    // Release the frame on all receivers at once.
+    if let Some(sync_universe) = sync_universe.filter(|_| outcome.packet_count > 0) {
+        if used_route {
+            if let Err(error) = transport.send_sync(sync_universe) {
+                let label = format!(
+                    "{}{}",
+                    label_prefix,
+                    dmx_send_route_label(transport.route())
+                );
+                outcome.record(label, 0, Err(error));
+            }
+        }
+        for destination in unicast_sent_to {
+            if let Err(error) = transport.send_sync_unicast(destination, sync_universe) {
+                let label = format!("{}Unicast {}", label_prefix, destination.ip());
+                outcome.record(label, 0, Err(error));
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Send one frame of universes through the primary transport and the mirror, if any.
+///
+/// A transport is only dropped, to be recreated on the next frame, when none of its destinations
+/// could be sent to.
+fn send_led_worker_payloads(
+    state: &LedWorkerInputState,
+    dmx: &mut DmxRuntime,
+    payloads: &[(u16, Vec<u8>)],
+) {
+    let config = &state.config;
+    let (sync_universe, rejected_sync_universe) =
+        frame_sync_universe(config.sacn_sync_universe, payloads);
+    let primary = dmx
+        .source
+        .as_mut()
+        .map(|source| send_transport_frame(source, "", config, payloads, sync_universe));
+    let mirror = dmx
+        .mirror
+        .as_mut()
+        .map(|mirror| send_transport_frame(mirror, "Mirror ", config, payloads, sync_universe));
+
+    let outcomes = || primary.iter().chain(mirror.iter());
+    let packet_count: usize = outcomes().map(|outcome| outcome.packet_count).sum();
+    let payload_bytes: usize = outcomes().map(|outcome| outcome.payload_bytes).sum();
+    if packet_count > 0 {
+        let sent_at = Instant::now();
+        dmx.monitor
+            .record_successful_frame_stats(packet_count, payload_bytes, sent_at);
+        dmx.monitor
+            .record_sync(sync_universe, rejected_sync_universe);
+        let sent_payloads: Vec<_> = payloads
+            .iter()
+            .filter(|(universe, _)| {
+                outcomes().any(|outcome| outcome.sent_universes.contains(universe))
+            })
+            .cloned()
+            .collect();
+        if state.capture_output_monitor {
+            dmx.monitor
+                .record_universe_snapshots(&sent_payloads, sent_at);
+        }
+        // Replaced rather than merged, so universes that are no longer sent aren't faded or
+        // terminated again when the output stops.
+        dmx.last_sent.payloads = sent_payloads.into_iter().collect();
+    }
+
+    let mut destinations: Vec<_> = outcomes()
+        .flat_map(|outcome| outcome.destinations.iter().cloned())
+        .collect();
+    if let (None, Some(error)) = (&mirror, &dmx.mirror_error) {
+        destinations.push(SacnDestinationStats {
+            label: "Mirror".to_string(),
+            packets_sent: 0,
+            last_error: Some(error.clone()),
+        });
+    }
+    dmx.monitor.record_destinations(&destinations);
+
+    if let Some(outcome) = primary {
+        match outcome.last_error {
+            Some(error) if outcome.packet_count == 0 => {
+                if packet_count == 0 {
+                    dmx.monitor.record_send_error(error.clone());
+                }
+                dmx.error = Some(error);
+                dmx.source.take();
+                dmx.last_send_route = None;
+                dmx.last_send_attempt_at = None;
+            }
+            _ if outcome.packet_count > 0 => {
+                dmx.error = None;
+                dmx.last_send_route = outcome.last_route;
+            }
+            _ => (),
+        }
+    }
+    if let Some(outcome) = mirror {
+        if let (Some(error), 0) = (outcome.last_error, outcome.packet_count) {
+            dmx.mirror_error = Some(error);
+            dmx.mirror.take();
+            dmx.mirror_failed_at = Some(Instant::now());
+        }
+    }
+}
+
+/// The sync universe to send a frame of `payloads` with, followed by the configured sync universe
+/// if it's left unused.
+///
+/// E1.31 doesn't allow a sync universe to also carry data, so sync is skipped while it does.
+fn frame_sync_universe(
+    sync_universe: Option<u16>,
+    payloads: &[(u16, Vec<u8>)],
+) -> (Option<u16>, Option<u16>) {
+    match sync_universe {
+        Some(sync) if payloads.iter().any(|(universe, _)| *universe == sync) => (None, Some(sync)),
+        sync_universe => (sync_universe, None),
+    }
+}
+
+/// The receivers that the given universe is unicast to. Invalid addresses are skipped.
+fn unicast_destinations(
+    destinations: &BTreeMap<u16, Vec<String>>,
+    universe: u16,
+) -> Vec<SocketAddr> {
+    destinations
+        .get(&universe)
+        .into_iter()
+        .flatten()
+        .filter_map(|value| conf::parse_sacn_interface_ip(value).ok().flatten())
+        .map(|ip| SocketAddr::new(ip.into(), sacn_receiver::SACN_PORT))
+        .collect()
+}
+
+/// Create, recreate or drop the mirror transport to match the config.
+///
+/// A failing mirror is retried once `MIRROR_RETRY_INTERVAL` has passed, or straight away if its
+/// settings change.
+fn sync_mirror_output(config: &LedWorkerConfig, dmx: &mut DmxRuntime, now: Instant) {
+    let request = mirror_source_request(config);
+    if request.is_none() {
+        dmx.mirror = None;
+        dmx.requested_mirror = None;
+        dmx.mirror_error = None;
+        dmx.mirror_failed_at = None;
+        return;
+    }
+    if dmx.requested_mirror == request
+        && (dmx.mirror.is_some() || !should_retry_mirror(dmx.mirror_failed_at, now))
+    {
+        return;
+    }
+    dmx.requested_mirror = request;
+    if let Some(request) = request {
+        match create_dmx_source(request) {
+            Ok(mirror) => {
+                dmx.mirror = Some(mirror);
+                dmx.mirror_error = None;
+                dmx.mirror_failed_at = None;
+            }
+            Err(err) => {
+                dmx.mirror = None;
+                dmx.mirror_error = Some(err);
+                dmx.mirror_failed_at = Some(now);
+            }
+        }
+    }
+}
+
+fn should_retry_mirror(failed_at: Option<Instant>, now: Instant) -> bool {
+    failed_at.is_none_or(|failed_at| now.duration_since(failed_at) >= MIRROR_RETRY_INTERVAL)
+}
+
+/// The primary transport's settings on the mirror interface, if a mirror is wanted.
+///
+/// Pixel protocols aren't mirrored as they are routed by destination address alone.
+fn mirror_source_request(config: &LedWorkerConfig) -> Option<DmxSourceRequest> {
+    if !config.dmx_on || config.dmx_protocol.sends_pixel_frames() {
+        return None;
+    }
+    let mirror_ip = conf::parse_sacn_interface_ip(&config.mirror_interface_ip).ok()??;
+    let primary = dmx_source_request(config).ok()?;
+    Some(DmxSourceRequest {
+        interface_ip: Some(mirror_ip),
+        ..primary
+    })
+}
+
+/// Send `led_outputs` directly as one OPC or DDP frame per destination.
+///
+/// A failing destination doesn't stop the others. The transport is kept, as each sender
+/// reconnects or resends on its own.
+fn send_led_worker_pixel_frames(
+    state: &LedWorkerInputState,
+    runtime: &mut LedWorkerRuntime,
+    now: Instant,
+) {
+    if runtime.dmx.source.is_none() {
+        return;
+    }
+    let config = &state.config;
+    let pixel_output = pixel_output_config(config, runtime.power_limiter.led_scales());
+    let dither_errors = led_dither_errors(
+        &mut runtime.dither_errors,
+        runtime.led_outputs.len(),
+        config.led_dither,
+    );
+    let frames = build_pixel_frames(
+        &config.pixel_frame_layouts,
+        &pixel_output,
+        &runtime.led_outputs,
+        dither_errors,
+    );
+    send_pixel_frames(&mut runtime.dmx, &frames, now);
+}
+
+/// Send one frame to each OPC or DDP destination, recording the outcome in the monitor.
+fn send_pixel_frames(dmx: &mut DmxRuntime, frames: &[(SocketAddr, Vec<u8>)], now: Instant) {
+    let Some(ref mut dmx_source) = dmx.source else {
+        return;
+    };
+    let mut sent_frame_count = 0usize;
+    let mut sent_bytes = 0usize;
+    let mut last_send_route = None;
+    let mut send_error = None;
+    for (destination, rgb) in frames {
+        match dmx_source.send_pixels(*destination, rgb) {
+            Ok(route) => {
+                last_send_route = Some(route);
+                sent_frame_count += 1;
+                sent_bytes += rgb.len();
+            }
+            Err(error) => send_error = Some(error),
+        }
+    }
+
+    if sent_frame_count > 0 {
+        let monitor = &mut dmx.monitor;
+        monitor.record_successful_frame_stats(sent_frame_count, sent_bytes, now);
+        monitor.record_sync(None);
+        // There are no universes to show.
+        monitor.record_universe_snapshots(&[], now);
+        dmx.last_sent.pixel_frames = frames.to_vec();
+    }
+
+    if frames.is_empty() {
+        let error = "No valid OPC or DDP destination is set".to_string();
+        dmx.monitor.record_send_error(error.clone());
+        dmx.error = Some(error);
+        dmx.last_send_route = None;
+    } else if let Some(error) = send_error {
+        dmx.monitor.record_send_error(error.clone());
+        dmx.error = Some(error);
+        dmx.last_send_route = last_send_route;
+    } else {
+        dmx.error = None;
+        dmx.last_send_route = last_send_route;
+    }
+}
+
+/// Send the next frame of the fade to black or, once it is done, terminate every stream that
+/// was being sent and drop the transports.
+fn send_output_stop_frame(state: &LedWorkerInputState, dmx: &mut DmxRuntime, now: Instant) {
+    let fade = dmx.stopping.get_or_insert_with(|| OutputFade {
+        frame: 0,
+        frames: state.config.dmx_off_fade_frames,
+        bit_depth: state.config.dmx_bit_depth,
+        from: std::mem::take(&mut dmx.last_sent),
+    });
+    if fade.frame < fade.frames {
+        fade.frame += 1;
+        let (payloads, pixel_frames) = fade.next_output();
+        send_led_worker_payloads(state, dmx, &payloads);
+        if !pixel_frames.is_empty() {
+            send_pixel_frames(dmx, &pixel_frames, now);
+        }
+        return;
+    }
+    let fade = dmx.stopping.take().expect("output fade was just started");
+    terminate_dmx_output(&state.config, dmx, &fade.from);
+}
+
+/// Fade out and terminate the output before the worker exits, blocking for the fade.
+fn finish_led_worker_output(state: &LedWorkerInputState, runtime: &mut LedWorkerRuntime) {
+    let fps = state
+        .config
+        .led_output_fps
+        .fps_limit()
+        .unwrap_or(STOP_FADE_FALLBACK_FPS);
+    let frame_interval = Duration::from_secs_f32(1.0 / fps);
+    while runtime.dmx.source.is_some() || runtime.dmx.mirror.is_some() {
+        send_output_stop_frame(state, &mut runtime.dmx, Instant::now());
+        if runtime.dmx.stopping.is_some() {
+            thread::sleep(frame_interval);
+        }
+    }
+}
+
+/// Tell every receiver in `last_sent` that its stream has ended, then drop both transports.
+///
+/// Every receiver is tried even when some fail, and the failures are left in `dmx.error` so the
+/// GUI can show which receivers may still be holding a frame until they time out.
+fn terminate_dmx_output(
+    config: &LedWorkerConfig,
+    dmx: &mut DmxRuntime,
+    last_sent: &LastSentOutput,
+) {
+    let mut errors = Vec::new();
+    for transport in dmx.source.iter_mut().chain(dmx.mirror.iter_mut()) {
+        for (&universe, payload) in &last_sent.payloads {
+            let priority = universe_priority(&config.sacn_priorities, universe);
+            let unicast = if transport.supports_unicast() {
+                unicast_destinations(&config.sacn_unicast_destinations, universe)
+            } else {
+                Vec::new()
+            };
+            errors.extend(
+                transport
+                    .terminate(universe, payload.len(), priority, &unicast)
+                    .err(),
+            );
+        }
+        for (destination, rgb) in &last_sent.pixel_frames {
+            errors.extend(
+                transport
+                    .send_pixels(*destination, &vec![0; rgb.len()])
+                    .err(),
+            );
+        }
+    }
+    dmx.source = None;
+    dmx.requested_source = None;
+    dmx.discovery = None;
+    dmx.mirror = None;
+    dmx.requested_mirror = None;
+    dmx.mirror_error = None;
+    dmx.mirror_failed_at = None;
+    dmx.error = (!errors.is_empty()).then(|| errors.join("; "));
+    dmx.last_send_route = None;
+    dmx.last_send_attempt_at = None;
+    dmx.last_sent = LastSentOutput::default();
+}
+
+/// Scale levels towards black for the given frame of a fade, reaching zero on the last.
+///
+/// 16-bit levels are faded as big-endian pairs, so the fine slot follows the coarse one.
+fn fade_levels(levels: &mut [u8], bit_depth: conf::DmxBitDepth, frame: u32, frames: u32) {
+    let remaining = frames.saturating_sub(frame);
+    let frames = frames.max(1);
+    match bit_depth {
+        conf::DmxBitDepth::Eight => {
+            for level in levels {
+                *level = (*level as u32 * remaining / frames) as u8;
+            }
+        }
+        conf::DmxBitDepth::Sixteen => {
+            for pair in levels.chunks_mut(2) {
+                if let [coarse, fine] = pair {
+                    let level = u16::from_be_bytes([*coarse, *fine]) as u32 * remaining / frames;
+                    [*coarse, *fine] = (level as u16).to_be_bytes();
+                } else {
+                    pair[0] = (pair[0] as u32 * remaining / frames) as u8;
+                }
             }
         }
-    }
-
-    if disconnect_source {
-        runtime.dmx.source.take();
-        runtime.dmx.last_send_route = None;
-        runtime.dmx.last_send_attempt_at = None;
     }
 }
 
 fn sync_preview_window_visibility(app: &App, model: &mut Model) {
-    if model.preview_window_visible == model.global_config.preview_window_on {
+    if model.preview_window_visible == model.show.global_config.preview_window_on {
         return;
     }
 
     if let Some(window) = app.window(model.led_strip_window) {
-        window.set_visible(model.global_config.preview_window_on);
+        window.set_visible(model.show.global_config.preview_window_on);
     }
-    model.preview_window_visible = model.global_config.preview_window_on;
+    model.preview_window_visible = model.show.global_config.preview_window_on;
 }
 
 fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
@@ -1944,54 +3408,63 @@ fn raw_window_event(app: &App, model: &mut Model, event: &ui::RawWindowEvent) {
 fn key_pressed(_app: &App, model: &mut Model, key: Key) {
     if key == Key::Space {
         let button = shader_shared::Button::Cycle;
-        update_button(&mut model.buttons, button, shader_shared::State::On);
+        update_button(&mut model.show.buttons, button, shader_shared::State::On);
     }
 }
 
 fn update(app: &App, model: &mut Model, update: Update) {
-    model.audio_input.update();
-    apply_led_worker_output(model);
+    update_show_inputs(&mut model.show);
     update_preview_textures(app, model);
-    model.runtime_stats.record_app_frame(update.since_last);
+    model.show.runtime_stats.record_app_frame(update.since_last);
 
     // Apply the GUI update.
+    let shader_previews_on = model.show.global_config.shader_previews.is_on();
+    let shader_preview_images = model.preview_images.as_ref().filter(|_| shader_previews_on);
     let mut ui = model.ui.set_widgets();
     let assets = app.assets_path().expect("failed to find assets directory");
     gui::update(
         &mut ui,
         gui::UpdateContext {
-            global_config: &mut model.global_config,
-            presets: &mut model.presets,
+            global_config: &mut model.show.global_config,
+            presets: &mut model.show.presets,
             preset_list_drag: &mut model.preset_list_drag,
-            audio_input: &mut model.audio_input,
+            audio_input: &mut model.show.audio_input,
             left_panel_tab: &mut model.left_panel_tab,
-            sacn_output_monitor: &mut model.dmx.monitor,
-            sacn_error: model.dmx.error.as_deref(),
-            sacn_transport_label: model.dmx.last_send_route.map(dmx_send_route_label),
+            sacn_output_monitor: &mut model.show.dmx.monitor,
+            sacn_error: model.show.dmx.error.as_deref(),
+            sacn_transport_label: model.show.dmx.last_send_route.map(dmx_send_route_label),
             since_start: update.since_start,
-            shader_activity: model.shader_rx.activity(),
-            led_colors: model.led_colors.as_slice(),
-            last_preset_change: &mut model.last_preset_change,
+            shader_activity: model.show.shader_rx.activity(),
+            led_colors: model.show.led_colors.as_slice(),
+            last_preset_change: &mut model.show.last_preset_change,
             assets: assets.as_path(),
             ids: &mut model.ids,
-            mad_project: &mut model.mad_project,
-            resolved_layout: &mut model.resolved_layout,
+            mad_project: &mut model.show.mad_project,
+            resolved_layout: &mut model.show.resolved_layout,
             pending_file_dialog: &mut model.pending_file_dialog,
-            midi_mapping: &mut model.midi_mapping,
-            midi_learn: &mut model.midi_learn,
-            midi_values: &mut model.midi_values,
-            smoothing_speed: &mut model.smoothing_speed,
-            smoothed_master_speed: model.smoothed_master_speed,
-            smoothed_phase_offset: model.smoothed_phase_offset,
-            smoothed_preset: &model.smoothed_preset,
-            preview_left_image_id: model.preview_images.as_ref().map(|pi| pi.left_id),
-            preview_right_image_id: model.preview_images.as_ref().map(|pi| pi.right_id),
-            preview_colourise_image_id: model.preview_images.as_ref().map(|pi| pi.colourise_id),
+            midi_mapping: &mut model.show.midi_mapping,
+            midi_learn: &mut model.show.midi_learn,
+            midi_values: &mut model.show.midi_values,
+            smoothing_speed: &mut model.show.smoothing_speed,
+            smoothed_master_speed: model.show.smoothed_master_speed,
+            smoothed_phase_offset: model.show.smoothed_phase_offset,
+            smoothed_preset: &model.show.smoothed_preset,
+            preview_left_image_id: shader_preview_images.map(|pi| pi.left_id),
+            preview_right_image_id: shader_preview_images.map(|pi| pi.right_id),
+            preview_colourise_image_id: shader_preview_images.map(|pi| pi.colourise_id),
             preview_hover_image_id: model.preview_images.as_ref().map(|pi| pi.hover_id),
-            hover_preview_request: &mut model.hover_preview_request,
+            hover_preview_request: &mut model.show.hover_preview_request,
             shader_left_dropdown: &mut model.shader_left_dropdown,
             shader_right_dropdown: &mut model.shader_right_dropdown,
             hover_preview_state: &mut model.hover_preview_state,
+            fixture_editor: &mut model.fixture_editor,
+            power_estimate: &model.show.power_estimate,
+            strobe_intervening: model.show.strobe_intervening,
+            dmx_capture: &mut model.show.dmx_capture,
+            dmx_capture_status: &model.show.dmx_capture_status,
+            pending_replay_dialog: &mut model.pending_replay_dialog,
+            sacn_input_status: &model.show.dmx.input,
+            render_passes: &model.show.runtime_stats.render_passes,
         },
     );
     drop(ui);
@@ -2007,10 +3480,11 @@ fn update(app: &App, model: &mut Model, update: Update) {
                             project.fixtures.len(),
                             project.total_pixels()
                         );
-                        model.global_config.madmapper_project_path =
+                        model.show.global_config.madmapper_project_path =
                             Some(path.to_string_lossy().into_owned());
-                        model.resolved_layout = Some(layout::resolve_from_mad_project(&project));
-                        model.mad_project = Some(project);
+                        model.show.resolved_layout =
+                            Some(layout::resolve_from_mad_project(&project));
+                        model.show.mad_project = Some(project);
                     }
                     Err(e) => {
                         eprintln!("Failed to parse MadMapper project: {}", e);
@@ -2020,59 +3494,103 @@ fn update(app: &App, model: &mut Model, update: Update) {
             model.pending_file_dialog = None;
         }
     }
+    if let Some(ref rx) = model.pending_replay_dialog {
+        if let Ok(result) = rx.try_recv() {
+            if result.is_some() {
+                model.show.dmx_capture.replay_path = result;
+            }
+            model.pending_replay_dialog = None;
+        }
+    }
 
     sync_preview_window_visibility(app, model);
     update_gui_window_title(app, model);
-    model.global_config.led_layout.normalise();
-    sync_led_buffers(model);
+    let showing = GuiShowing {
+        output_monitor: model.left_panel_tab == gui::LeftPanelTab::Output,
+        shader_previews: model.preview_images.is_some(),
+    };
+    update_show(&mut model.show, update.since_last, showing);
+}
+
+/// Read the audio input and the LED worker's latest output. Runs before any GUI or control
+/// input is applied.
+fn update_show_inputs(show: &mut Show) {
+    show.audio_input.update();
+    apply_led_worker_output(show);
+}
+
+/// Route MIDI and DMX control, pick up shader reloads, advance smoothing and queue the next frame
+/// for the LED worker.
+fn update_show(show: &mut Show, since_last: Duration, showing: GuiShowing) {
+    show.global_config.led_layout.normalise();
+    sync_led_buffers(show);
 
     // Check for an update to the shader.
-    if let Some(shader) = model.shader_rx.update() {
-        if let Ok(mut shared_input) = model.led_worker.shared_input.lock() {
+    if let Some(shader) = show.shader_rx.update() {
+        if let Ok(mut shared_input) = show.led_worker.shared_input.lock() {
             shared_input.pending_shader = Some(shader);
         }
     }
 
     // MIDI routing: CC → mapping lookup → target values → smoothing → apply.
-    model.midi_manager.poll();
-    for msg in model.midi_manager.drain().collect::<Vec<_>>() {
+    show.midi_manager.poll();
+    for msg in show.midi_manager.drain().collect::<Vec<_>>() {
         // Check learn mode first.
-        if model.midi_learn.is_listening() {
-            if let Some((port, cc, target)) = model.midi_learn.receive(&msg) {
-                model.midi_mapping.assign(port, cc, target);
+        if show.midi_learn.is_listening() {
+            if let Some((port, cc, target)) = show.midi_learn.receive(&msg) {
+                show.midi_mapping.assign(port, cc, target);
             }
             continue;
         }
         // Normal routing.
-        if let Some(target) = model.midi_mapping.target_for(&msg.port_name, msg.cc) {
+        if let Some(target) = show.midi_mapping.target_for(&msg.port_name, msg.cc) {
             let normalized = 1.0 - (msg.value as f32 / 127.0);
-            let state = model.midi_values.entry(target).or_insert(MidiTargetState {
-                target: normalized,
-                smoothed: normalized,
-                active: false,
-            });
-            state.target = normalized;
-            state.active = true;
+            set_control_target(show, target, normalized);
+        }
+    }
+
+    // DMX control routing: channel changes are learned and routed just like CCs.
+    let control_levels = show.dmx.input.control_levels.as_ref();
+    let dmx_messages = match (
+        control_levels,
+        show.global_config.sacn_input.control_universe,
+    ) {
+        (Some(levels), Some(universe)) => show.dmx_control.update(universe, levels),
+        _ => {
+            show.dmx_control.reset();
+            Vec::new()
+        }
+    };
+    for msg in dmx_messages {
+        if show.midi_learn.is_listening() {
+            if let Some((universe, channel, target)) = show.midi_learn.receive_dmx(&msg) {
+                show.midi_mapping.assign_dmx(universe, channel, target);
+            }
+            continue;
+        }
+        if let Some(target) = show.midi_mapping.dmx_target_for(msg.universe, msg.channel) {
+            let normalized = msg.value as f32 / 255.0;
+            set_control_target(show, target, normalized);
         }
     }
-    model.midi_learn.update();
+    show.midi_learn.update();
 
     // Smooth active MIDI targets.
-    let s = model.smoothing_speed;
-    for (_target, state) in &mut model.midi_values {
+    let s = show.smoothing_speed;
+    for (_target, state) in &mut show.midi_values {
         if state.active {
             state.smoothed = state.smoothed * (1.0 - s) + state.target * s;
         }
     }
 
     // Apply smoothed MIDI values to their destinations.
-    apply_midi_values(model);
+    apply_midi_values(show);
 
-    update_smoothed_master_speed(model);
-    update_smoothed_preset(model);
-    model.master_phase += update.since_last.as_secs_f32() * model.smoothed_master_speed;
+    update_smoothed_master_speed(show);
+    update_smoothed_preset(show);
+    show.master_phase += since_last.as_secs_f32() * show.smoothed_master_speed;
 
-    queue_led_worker_update(app, model);
+    queue_led_worker_update(show, showing);
 }
 
 fn should_send_led_output(
@@ -2091,17 +3609,92 @@ fn should_send_led_output(
     }
 }
 
-fn create_dmx_source(interface_ip: Option<Ipv4Addr>) -> Result<DmxOutputTransport, String> {
-    match interface_ip {
-        Some(ip) if ip.is_loopback() => sacn_sender::LocalhostSacnSender::new("Cohen Pre-vis")
-            .map(DmxOutputTransport::Localhost)
-            .map_err(|err| format!("Couldn't create localhost sACN sender: {}", err)),
+fn dmx_source_request(config: &LedWorkerConfig) -> Result<DmxSourceRequest, AddrParseError> {
+    let interface_ip = conf::parse_sacn_interface_ip(&config.sacn_interface_ip)?;
+    let (artnet_destination_ip, artnet_sync_ip) = match config.dmx_protocol {
+        conf::DmxProtocol::ArtNet => (
+            conf::parse_sacn_interface_ip(&config.artnet_destination_ip)?,
+            conf::parse_sacn_interface_ip(&config.artnet_sync_ip)?,
+        ),
+        conf::DmxProtocol::Sacn | conf::DmxProtocol::Opc | conf::DmxProtocol::Ddp => (None, None),
+    };
+    Ok(DmxSourceRequest {
+        protocol: config.dmx_protocol,
+        interface_ip,
+        artnet_destination_ip,
+        artnet_sync_ip,
+        artnet_universe_offset: config.artnet_universe_offset,
+    })
+}
+
+/// Discovery follows the sACN source, so it is sent on the same interface and not for other
+/// protocols. It's best effort: output carries on without it if the socket can't be bound.
+fn create_discovery_sender(request: DmxSourceRequest) -> Option<sacn_discovery::DiscoverySender> {
+    if request.protocol != conf::DmxProtocol::Sacn {
+        return None;
+    }
+    let localhost = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), sacn_receiver::SACN_PORT);
+    let sender = match request.interface_ip {
+        Some(ip) if ip.is_loopback() => sacn_discovery::DiscoverySender::with_destinations(
+            sacn_source_cid(),
+            SACN_SOURCE_NAME,
+            ip,
+            vec![localhost],
+        ),
+        Some(ip) => {
+            sacn_discovery::DiscoverySender::multicast(sacn_source_cid(), SACN_SOURCE_NAME, ip)
+        }
+        None => {
+            let multicast = SocketAddr::new(
+                sacn_discovery::discovery_multicast_addr().into(),
+                sacn_receiver::SACN_PORT,
+            );
+            sacn_discovery::DiscoverySender::with_destinations(
+                sacn_source_cid(),
+                SACN_SOURCE_NAME,
+                Ipv4Addr::UNSPECIFIED,
+                vec![localhost, multicast],
+            )
+        }
+    };
+    sender.ok()
+}
+
+fn create_dmx_source(request: DmxSourceRequest) -> Result<DmxOutputTransport, String> {
+    match request.protocol {
+        conf::DmxProtocol::Sacn => (),
+        conf::DmxProtocol::ArtNet => {
+            return artnet_sender::ArtNetSender::new(
+                request.interface_ip,
+                request.artnet_destination_ip,
+                request.artnet_sync_ip,
+                request.artnet_universe_offset,
+            )
+            .map(DmxOutputTransport::ArtNet)
+            .map_err(|err| format!("Couldn't create Art-Net sender: {}", err));
+        }
+        // Connections are made per destination as frames are sent.
+        conf::DmxProtocol::Opc => return Ok(DmxOutputTransport::Opc(opc_sender::OpcSender::new())),
+        conf::DmxProtocol::Ddp => {
+            return ddp_sender::DdpSender::new(request.interface_ip)
+                .map(DmxOutputTransport::Ddp)
+                .map_err(|err| format!("Couldn't create DDP sender: {}", err));
+        }
+    }
+
+    match request.interface_ip {
+        Some(ip) if ip.is_loopback() => {
+            sacn_sender::LocalhostSacnSender::new(sacn_source_cid(), SACN_SOURCE_NAME)
+                .map(DmxOutputTransport::Localhost)
+                .map_err(|err| format!("Couldn't create localhost sACN sender: {}", err))
+        }
         Some(ip) => create_multicast_dmx_source(ip)
             .map(DmxOutputTransport::Network)
             .map_err(|err| err.to_string()),
         None => {
-            let localhost = sacn_sender::LocalhostSacnSender::new("Cohen Pre-vis")
-                .map_err(|err| format!("Couldn't create localhost sACN sender: {}", err))?;
+            let localhost =
+                sacn_sender::LocalhostSacnSender::new(sacn_source_cid(), SACN_SOURCE_NAME)
+                    .map_err(|err| format!("Couldn't create localhost sACN sender: {}", err))?;
             let multicast = create_multicast_dmx_source(Ipv4Addr::UNSPECIFIED).ok();
             Ok(DmxOutputTransport::Auto {
                 multicast,
@@ -2111,10 +3704,122 @@ fn create_dmx_source(interface_ip: Option<Ipv4Addr>) -> Result<DmxOutputTranspor
     }
 }
 
+/// Create, recreate or drop the sACN receiver to match the config, then read any new packets.
+fn sync_sacn_input(config: &LedWorkerConfig, input: &mut SacnInputRuntime) {
+    let mut universes = if config.dmx_on && config.sacn_input.enabled {
+        config.sacn_input.universes.clone()
+    } else {
+        Vec::new()
+    };
+    let control_universe = config.sacn_input.control_universe;
+    if let Some(universe) = control_universe {
+        if !universes.contains(&universe) {
+            universes.push(universe);
+        }
+    }
+    let discover_sources = config.sacn_input.discover_sources;
+    let request = match conf::parse_sacn_interface_ip(&config.sacn_interface_ip) {
+        Ok(interface_ip) if !universes.is_empty() || discover_sources => Some(SacnInputRequest {
+            interface_ip,
+            universes,
+            discover_sources,
+        }),
+        _ => None,
+    };
+    if request != input.requested {
+        *input = SacnInputRuntime::default();
+        if let Some(request) = request {
+            match create_sacn_receiver(&request) {
+                Ok(receiver) => input.receiver = Some(receiver),
+                Err(err) => input.error = Some(err),
+            }
+            input.requested = Some(request);
+        }
+    }
+    input.control_universe = control_universe;
+
+    if let Some(receiver) = input.receiver.as_mut() {
+        match receiver.poll(Instant::now()) {
+            Ok(()) => input.error = None,
+            Err(err) => input.error = Some(format!("Couldn't receive sACN input: {}", err)),
+        }
+    }
+}
+
+fn create_sacn_receiver(request: &SacnInputRequest) -> Result<sacn_receiver::SacnReceiver, String> {
+    let bind_addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), sacn_receiver::SACN_PORT);
+    let receiver =
+        sacn_receiver::SacnReceiver::bind(bind_addr, &request.universes, sacn_source_cid())
+            .map_err(|err| {
+                format!(
+                    "Couldn't listen for sACN on port {}: {}",
+                    sacn_receiver::SACN_PORT,
+                    err
+                )
+            })?;
+    // Unicast and localhost sources still reach us if multicast is unavailable.
+    let interface_ip = request.interface_ip.unwrap_or(Ipv4Addr::UNSPECIFIED);
+    let _ = receiver.join_multicast(interface_ip);
+    if request.discover_sources {
+        let _ = receiver.join_discovery_multicast(interface_ip);
+    }
+    Ok(receiver)
+}
+
+/// Merge any incoming sACN into the matching output universes.
+fn merge_sacn_input(
+    config: &LedWorkerConfig,
+    input: &mut SacnInputRuntime,
+    payloads: &mut [(u16, Vec<u8>)],
+) {
+    let Some(receiver) = input.receiver.as_ref() else {
+        return;
+    };
+    if !config.sacn_input.enabled {
+        return;
+    }
+    for (universe, payload) in payloads.iter_mut() {
+        if !config.sacn_input.universes.contains(universe) {
+            continue;
+        }
+        if let Some(received) = receiver.universe(*universe) {
+            input.merger.merge(
+                config.sacn_input.merge_mode,
+                *universe,
+                payload,
+                universe_priority(&config.sacn_priorities, *universe),
+                &received.payload,
+                received.priority,
+            );
+        }
+    }
+    input
+        .merger
+        .retain(|universe| receiver.universe(universe).is_some());
+}
+
+/// The sACN source priority for the given output universe.
+fn universe_priority(priorities: &BTreeMap<u16, u8>, universe: u16) -> u8 {
+    priorities
+        .get(&universe)
+        .map_or(E131_DEFAULT_PRIORITY, |&priority| {
+            priority.min(MAX_SACN_PRIORITY)
+        })
+}
+
+/// The CID that identifies our output.
+///
+/// Every sACN sender and the discovery sender share it, so receivers see a single source however
+/// the output is routed.
+fn sacn_source_cid() -> Uuid {
+    static CID: OnceLock<Uuid> = OnceLock::new();
+    *CID.get_or_init(Uuid::new_v4)
+}
+
 fn create_multicast_dmx_source(interface_ip: Ipv4Addr) -> sacn::error::errors::Result<SacnSource> {
     let bind_addr = SocketAddr::new(IpAddr::V4(interface_ip), ACN_SDT_MULTICAST_PORT + 1);
-    let mut source = SacnSource::with_ip("Cohen Pre-vis", bind_addr)?;
-    // Preserve the old sender behaviour: data only, no source discovery chatter.
+    let mut source = SacnSource::with_cid_ip(SACN_SOURCE_NAME, sacn_source_cid(), bind_addr)?;
+    // Discovery is sent by our own sender, under the same CID, so it also covers localhost output.
     source.set_is_sending_discovery(false);
     Ok(source)
 }
@@ -2123,32 +3828,50 @@ fn send_multicast_payload(
     source: &mut SacnSource,
     universe: u16,
     payload: &[u8],
+    priority: u8,
+    sync_universe: Option<u16>,
 ) -> Result<DmxSendRoute, String> {
     source
         .register_universe(universe)
         .map_err(|err| format!("Couldn't register sACN universe {}: {}", universe, err))?;
     source
-        .send(
-            &[universe],
-            payload,
-            Some(E131_DEFAULT_PRIORITY),
-            None,
-            None,
-        )
+        .send(&[universe], payload, Some(priority), None, sync_universe)
         .map(|()| DmxSendRoute::Multicast)
         .map_err(|err| format!("Couldn't send sACN universe {}: {}", universe, err))
 }
 
+fn send_multicast_sync(source: &mut SacnSource, sync_universe: u16) -> Result<(), String> {
+    source.register_universe(sync_universe).map_err(|err| {
+        format!(
+            "Couldn't register sACN sync universe {}: {}",
+            sync_universe, err
+        )
+    })?;
+    source.send_sync_packet(sync_universe, None).map_err(|err| {
+        format!(
+            "Couldn't send sACN sync universe {}: {}",
+            sync_universe, err
+        )
+    })
+}
+
 fn dmx_send_route_label(route: DmxSendRoute) -> &'static str {
     match route {
         DmxSendRoute::Multicast => "Network multicast",
+        DmxSendRoute::Unicast => "Network unicast",
         DmxSendRoute::Localhost => "Localhost preview",
+        DmxSendRoute::ArtNet => "Art-Net",
+        DmxSendRoute::Opc => "OPC",
+        DmxSendRoute::Ddp => "DDP",
     }
 }
 
 fn update_gui_window_title(app: &App, model: &Model) {
-    let title = if model.runtime_stats.app_fps > 0.0 {
-        format!("COHEN GIG - GUI - {:.1} FPS", model.runtime_stats.app_fps)
+    let title = if model.show.runtime_stats.app_fps > 0.0 {
+        format!(
+            "COHEN GIG - GUI - {:.1} FPS",
+            model.show.runtime_stats.app_fps
+        )
     } else {
         "COHEN GIG - GUI".to_string()
     };
@@ -2194,14 +3917,14 @@ fn led_strip_view(app: &App, model: &Model, frame: Frame) {
     let draw = app.draw();
     draw.background().color(BLACK);
 
-    if !model.global_config.preview_window_on {
+    if !model.show.global_config.preview_window_on {
         draw.to_frame(app, &frame).unwrap();
         return;
     }
 
     let w = app.window(model.led_strip_window).unwrap().rect();
 
-    if model.mad_project.is_some() {
+    if model.show.mad_project.is_some() {
         led_strip_view_mad(&draw, &w, model);
     } else {
         led_strip_view_manual(app, &draw, &w, model);
@@ -2214,7 +3937,7 @@ fn led_strip_view(app: &App, model: &Model, frame: Frame) {
 /// Preview rendering when a MadMapper project is active.
 /// Uses normalised coords from the resolved layout, centered in the window.
 fn led_strip_view_mad(draw: &Draw, w: &geom::Rect, model: &Model) {
-    let project = model.mad_project.as_ref().unwrap();
+    let project = model.show.mad_project.as_ref().unwrap();
     let fixtures = project.fixtures_by_row();
     let scale = w.h() * 0.4;
 
@@ -2234,7 +3957,7 @@ fn led_strip_view_mad(draw: &Draw, w: &geom::Rect, model: &Model) {
 
         let x_scale = w.w() * 0.45;
         let vs = (0..fixture.pixel_count).filter_map(|pixel_ix| {
-            let color = model.led_outputs.get(led_idx + pixel_ix)?;
+            let color = model.show.led_outputs.get(led_idx + pixel_ix)?;
             let x_norm = if fixture.pixel_count <= 1 {
                 0.0
             } else {
@@ -2249,7 +3972,7 @@ fn led_strip_view_mad(draw: &Draw, w: &geom::Rect, model: &Model) {
 
 /// Preview rendering for manual LED layout (original path).
 fn led_strip_view_manual(app: &App, draw: &Draw, w: &geom::Rect, model: &Model) {
-    let led_layout = &model.global_config.led_layout;
+    let led_layout = &model.show.global_config.led_layout;
 
     let metres_to_points_scale = (w.h() / layout::top_led_row_from_ground(led_layout))
         .min(w.w() / led_layout.metres_per_row as f32)
@@ -2263,7 +3986,7 @@ fn led_strip_view_manual(app: &App, draw: &Draw, w: &geom::Rect, model: &Model)
     let pm_to_ps =
         |x: f32, h: f32| layout::topdown_metres_to_shader_coords(pt2(x, 0.0), h, led_layout);
 
-    let mut leds = layout::led_positions_metres(led_layout).zip(model.led_outputs.iter());
+    let mut leds = layout::led_positions_metres(led_layout).zip(model.show.led_outputs.iter());
     for _ in 0..led_layout.row_count {
         let vs = leds
             .by_ref()
@@ -2290,12 +4013,12 @@ fn led_strip_view_manual(app: &App, draw: &Draw, w: &geom::Rect, model: &Model)
 // Draw hotloading status in top-left corner. Flash screen on build completion.
 fn draw_hotload_feedback(app: &App, model: &Model, draw: &Draw, w: geom::Rect) {
     // If we only recently loaded a new shader, flash the screen a little.
-    let secs_since_load = model.shader_rx.last_timestamp().elapsed().secs();
+    let secs_since_load = model.show.shader_rx.last_timestamp().elapsed().secs();
     if secs_since_load < 1.0 {
         let flash_alpha = (1.0 - secs_since_load).powi(8);
-        let flash_color = match model.shader_rx.last_incoming() {
+        let flash_color = match model.show.shader_rx.last_incoming() {
             shader::LastIncoming::Succeeded => GREEN,
-            shader::LastIncoming::Failed(_) => RED,
+            shader::LastIncoming::Failed(_) | shader::LastIncoming::Incompatible(_) => RED,
         };
         let color = nannou::color::Alpha {
             color: flash_color,
@@ -2305,7 +4028,7 @@ fn draw_hotload_feedback(app: &App, model: &Model, draw: &Draw, w: geom::Rect) {
     }
 
     // If we are building or there was some error compiling recently, display it.
-    match model.shader_rx.activity() {
+    match model.show.shader_rx.activity() {
         shader::Activity::Incoming => {
             let s = "Compiling";
             let r = w.pad(20.0);
@@ -2319,9 +4042,30 @@ fn draw_hotload_feedback(app: &App, model: &Model, draw: &Draw, w: geom::Rect) {
                 .left_justify()
                 .align_text_top();
         }
+        shader::Activity::Panicked(panics) => {
+            let s: Vec<_> = panics
+                .iter()
+                .map(|p| {
+                    format!(
+                        "{} panicked and was swapped out: {}",
+                        p.shader.name(),
+                        p.message
+                    )
+                })
+                .collect();
+            let r = w.pad(20.0);
+            draw.text(&s.join("\n"))
+                .font_size(16)
+                .wh(r.wh())
+                .color(RED)
+                .left_justify()
+                .align_text_top();
+        }
         shader::Activity::LastIncoming(last) => {
-            if let shader::LastIncoming::Failed(ref err) = last {
-                let s = format!("{}", err);
+            if let shader::LastIncoming::Failed(ref err)
+            | shader::LastIncoming::Incompatible(ref err) = last
+            {
+                let s = err.as_str();
                 let r = w.pad(20.0);
                 draw.text(&s)
                     .font_size(16)
@@ -2344,12 +4088,7 @@ fn save_presets(assets: &Path, global_config: &mut GlobalConfig, presets: &conf:
 }
 
 fn exit(_app: &App, mut model: Model) {
-    if let Ok(mut shared_input) = model.led_worker.shared_input.lock() {
-        shared_input.shutdown = true;
-    }
-    if let Some(thread) = model.led_worker.thread.take() {
-        let _ = thread.join();
-    }
+    model.show.shutdown();
 }
 
 fn update_button(
@@ -2371,13 +4110,29 @@ fn update_button(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_led_sacn_payloads, build_per_fixture_payloads, should_send_led_output,
+        build_led_sacn_payloads, build_per_fixture_payloads, build_pixel_frames,
+        build_sacn_payloads, fade_levels, finish_led_worker_output, frame_sync_universe,
+        led_drive_levels, led_worker_config, pack_pixel_levels, pixel_levels,
+        send_led_worker_payloads, should_retry_mirror, should_send_led_output,
+        unicast_destinations, DitherError, DmxOutputTransport, DmxSendRoute, LedWorkerInputState,
+        LedWorkerRuntime, PixelChannels, PixelFormat, PixelOutputConfig, SacnOutputMonitor,
+        TransportFrameOutcome, MIRROR_RETRY_INTERVAL, PIXEL_CHANNEL_KINDS,
         UNIVERSE_CHANNEL_CAPACITY,
     };
-    use crate::conf::LedOutputFps;
+    use crate::conf::{
+        Calibration, CalibrationProfile, ChannelLayouts, ChannelOrder, DmxBitDepth, GlobalConfig,
+        LedOutputFps, Preset,
+    };
+    use crate::layout::DmxMap;
     use crate::layout::FixtureDmxEntry;
+    use crate::layout::{PixelFrameLayout, PixelFrameRange};
+    use crate::sacn_sender::LocalhostSacnSender;
     use nannou::prelude::*;
+    use sacn::packet::{AcnRootLayerProtocol, E131RootLayerData};
+    use std::collections::BTreeMap;
+    use std::net::SocketAddr;
     use std::time::{Duration, Instant};
+    use uuid::Uuid;
 
     fn test_rgb_triplets(count: usize) -> Vec<[u8; 3]> {
         (0..count)
@@ -2465,23 +4220,38 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn failed_mirror_waits_before_retrying() {
+        let now = Instant::now();
+        let just_failed = now.checked_sub(Duration::from_millis(100)).unwrap();
+        let failed_a_while_ago = now.checked_sub(MIRROR_RETRY_INTERVAL).unwrap();
+
+        assert!(should_retry_mirror(None, now));
+        assert!(!should_retry_mirror(Some(just_failed), now));
+        assert!(should_retry_mirror(Some(failed_a_while_ago), now));
+    }
+
     #[test]
     fn per_fixture_payloads_route_pixels_to_correct_universes() {
         // Two fixtures: 4 pixels on universe 5, 3 pixels on universe 10.
         let entries = vec![
             FixtureDmxEntry {
+                fixture_name: "Fixture-Line-1".to_string(),
                 led_offset: 0,
                 led_count: 4,
                 start_universe: 5,
                 start_channel: 1,
                 channels_per_pixel: 3,
+                pixel_destination: None,
             },
             FixtureDmxEntry {
+                fixture_name: "Fixture-Line-2".to_string(),
                 led_offset: 4,
                 led_count: 3,
                 start_universe: 10,
                 start_channel: 1,
                 channels_per_pixel: 3,
+                pixel_destination: None,
             },
         ];
 
@@ -2492,7 +4262,10 @@ mod tests {
             })
             .collect();
 
-        let payloads = build_per_fixture_payloads(&entries, &led_outputs);
+        let channel_layouts = ChannelLayouts::default();
+        let calibration = Calibration::default();
+        let output = pixel_output(&channel_layouts, &calibration);
+        let payloads = build_per_fixture_payloads(&entries, &output, &led_outputs, None);
 
         assert_eq!(payloads.len(), 2);
         assert_eq!(payloads[0].0, 5);
@@ -2506,4 +4279,534 @@ mod tests {
         assert_eq!(payloads[1].1.len(), 10);
         assert_eq!(payloads[1].1[0], 0);
     }
+
+    /// Worker state for the given config, with the default preset and no resolved layout.
+    fn worker_state(global_config: &GlobalConfig) -> LedWorkerInputState {
+        LedWorkerInputState {
+            app_time: 0.0,
+            snapshot_at: Instant::now(),
+            config: led_worker_config(global_config, &Preset::default(), 1.0, 0.0, &None),
+            colour_channels: [1.0; 3],
+            audio_envelope: 0.0,
+            buttons: Default::default(),
+            capture_output_monitor: false,
+            render_shader_previews: false,
+            dmx_capture: Default::default(),
+        }
+    }
+
+    /// A localhost sACN transport sending to a new listener.
+    fn localhost_transport() -> (DmxOutputTransport, std::net::UdpSocket) {
+        let listener = std::net::UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let sender = LocalhostSacnSender::with_destination(
+            Uuid::new_v4(),
+            "Cohen Test",
+            listener.local_addr().unwrap(),
+        )
+        .unwrap();
+        (DmxOutputTransport::Localhost(sender), listener)
+    }
+
+    #[test]
+    fn sync_universe_colliding_with_data_is_rejected() {
+        let payloads = [(1, vec![0, 10]), (2, vec![0, 20])];
+        assert_eq!(frame_sync_universe(Some(2), &payloads), (None, Some(2)));
+        assert_eq!(frame_sync_universe(Some(7), &payloads), (Some(7), None));
+        assert_eq!(frame_sync_universe(None, &payloads), (None, None));
+    }
+
+    #[test]
+    fn stopping_output_fades_then_terminates_every_universe() {
+        let global_config = GlobalConfig {
+            dmx_off_fade_frames: 2,
+            ..GlobalConfig::default()
+        };
+        let state = worker_state(&global_config);
+        let mut runtime = LedWorkerRuntime::new(&state.config);
+        let (transport, listener) = localhost_transport();
+        runtime.dmx.source = Some(transport);
+        send_led_worker_payloads(
+            &state,
+            &mut runtime.dmx,
+            &[(1, vec![0, 200, 100]), (2, vec![0, 50])],
+        );
+
+        finish_led_worker_output(&state, &mut runtime);
+        assert!(runtime.dmx.source.is_none());
+        assert!(runtime.dmx.stopping.is_none());
+        assert!(runtime.dmx.last_sent.payloads.is_empty());
+
+        let mut buffer = [0u8; 1024];
+        let mut received = Vec::new();
+        for _ in 0..12 {
+            let len = listener.recv(&mut buffer).unwrap();
+            match AcnRootLayerProtocol::parse(&buffer[..len])
+                .unwrap()
+                .pdu
+                .data
+            {
+                E131RootLayerData::DataPacket(data) => received.push((
+                    data.universe,
+                    data.stream_terminated,
+                    data.data.property_values.to_vec(),
+                )),
+                _ => panic!("expected a data packet"),
+            }
+        }
+        let levels: Vec<_> = received
+            .iter()
+            .filter(|(_, terminated, _)| !terminated)
+            .map(|(universe, _, payload)| (*universe, payload.clone()))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                (1, vec![0, 200, 100]),
+                (2, vec![0, 50]),
+                (1, vec![0, 100, 50]),
+                (2, vec![0, 25]),
+                (1, vec![0, 0, 0]),
+                (2, vec![0, 0]),
+            ]
+        );
+        for universe in [1, 2] {
+            let terminated = received
+                .iter()
+                .filter(|(u, terminated, _)| *u == universe && *terminated)
+                .count();
+            assert_eq!(terminated, 3);
+        }
+    }
+
+    #[test]
+    fn last_sent_output_holds_only_the_latest_frame() {
+        let state = worker_state(&GlobalConfig::default());
+        let mut runtime = LedWorkerRuntime::new(&state.config);
+        let (transport, _listener) = localhost_transport();
+        runtime.dmx.source = Some(transport);
+
+        send_led_worker_payloads(
+            &state,
+            &mut runtime.dmx,
+            &[(1, vec![0, 10]), (2, vec![0, 20])],
+        );
+        send_led_worker_payloads(&state, &mut runtime.dmx, &[(2, vec![0, 30])]);
+
+        assert_eq!(
+            runtime.dmx.last_sent.payloads,
+            BTreeMap::from([(2, vec![0, 30])])
+        );
+    }
+
+    #[test]
+    fn failing_destinations_are_counted_separately() {
+        let mut outcome = TransportFrameOutcome::default();
+        let ok = || Ok(DmxSendRoute::Unicast);
+        assert!(outcome.record("Unicast 10.0.0.1".to_string(), 10, ok()));
+        assert!(!outcome.record(
+            "Unicast 10.0.0.2".to_string(),
+            10,
+            Err("unreachable".to_string())
+        ));
+        assert!(outcome.record("Unicast 10.0.0.1".to_string(), 10, ok()));
+        assert_eq!(outcome.packet_count, 2);
+        assert_eq!(outcome.payload_bytes, 20);
+        assert_eq!(outcome.destinations.len(), 2);
+        assert_eq!(
+            outcome.destinations[1].last_error.as_deref(),
+            Some("unreachable")
+        );
+
+        let mut monitor = SacnOutputMonitor::default();
+        monitor.record_destinations(&outcome.destinations);
+        // Counts accumulate across frames and unused destinations are dropped.
+        monitor.record_destinations(&outcome.destinations[..1]);
+        assert_eq!(monitor.destinations.len(), 1);
+        assert_eq!(monitor.destinations[0].packets_sent, 4);
+        assert!(monitor.destinations[0].last_error.is_none());
+    }
+
+    #[test]
+    fn unicast_destinations_skip_invalid_addresses() {
+        let mut destinations = BTreeMap::new();
+        destinations.insert(
+            3,
+            vec![
+                "10.0.0.5".to_string(),
+                "10.0".to_string(),
+                " 10.0.0.6 ".to_string(),
+            ],
+        );
+        let addrs: Vec<_> = unicast_destinations(&destinations, 3)
+            .into_iter()
+            .map(|addr| addr.to_string())
+            .collect();
+        assert_eq!(addrs, vec!["10.0.0.5:5568", "10.0.0.6:5568"]);
+        assert!(unicast_destinations(&destinations, 4).is_empty());
+    }
+
+    #[test]
+    fn pixel_frames_concatenate_rgb_in_layout_order() {
+        let led_outputs = vec![
+            lin_srgb(1.0, 0.0, 0.0),
+            lin_srgb(0.0, 1.0, 0.0),
+            lin_srgb(0.0, 0.0, 1.0),
+        ];
+        let destination = SocketAddr::from(([10, 0, 0, 1], 4048));
+        let frame_layouts = vec![PixelFrameLayout {
+            destination,
+            led_ranges: vec![
+                PixelFrameRange {
+                    leds: 2..3,
+                    fixture_name: None,
+                },
+                PixelFrameRange {
+                    leds: 0..1,
+                    fixture_name: None,
+                },
+            ],
+        }];
+        // Channel order overrides are DMX-only; pixel frames are always RGB.
+        let channel_layouts = ChannelLayouts {
+            default: ChannelOrder::Grb,
+            ..ChannelLayouts::default()
+        };
+        let calibration = Calibration::default();
+        let output = pixel_output(&channel_layouts, &calibration);
+        let frames = build_pixel_frames(&frame_layouts, &output, &led_outputs, None);
+
+        assert_eq!(frames, vec![(destination, vec![0, 0, 255, 255, 0, 0])]);
+    }
+
+    #[test]
+    fn pixel_frames_calibrate_each_named_fixture() {
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 2];
+        let destination = SocketAddr::from(([10, 0, 0, 1], 4048));
+        let frame_layouts = vec![PixelFrameLayout {
+            destination,
+            led_ranges: vec![
+                PixelFrameRange {
+                    leds: 0..1,
+                    fixture_name: Some("Fixture-Line-1".to_string()),
+                },
+                PixelFrameRange {
+                    leds: 1..2,
+                    fixture_name: Some("Fixture-Line-2".to_string()),
+                },
+            ],
+        }];
+        let channel_layouts = ChannelLayouts::default();
+        let mut calibration = Calibration::default();
+        let name = calibration.add_profile();
+        calibration.profiles.get_mut(&name).unwrap().max_level = 0.5;
+        calibration
+            .fixtures
+            .insert("Fixture-Line-2".to_string(), name);
+        let output = pixel_output(&channel_layouts, &calibration);
+        let frames = build_pixel_frames(&frame_layouts, &output, &led_outputs, None);
+
+        assert_eq!(
+            frames,
+            vec![(destination, vec![255, 255, 255, 127, 127, 127])]
+        );
+    }
+
+    #[test]
+    fn per_fixture_payloads_apply_channel_order_overrides() {
+        let entries = vec![
+            FixtureDmxEntry {
+                fixture_name: "Fixture-Line-1".to_string(),
+                led_offset: 0,
+                led_count: 1,
+                start_universe: 1,
+                start_channel: 1,
+                channels_per_pixel: 3,
+                pixel_destination: None,
+            },
+            FixtureDmxEntry {
+                fixture_name: "Fixture-Line-2".to_string(),
+                led_offset: 1,
+                led_count: 1,
+                start_universe: 2,
+                start_channel: 1,
+                channels_per_pixel: 3,
+                pixel_destination: None,
+            },
+        ];
+        let mut channel_layouts = ChannelLayouts::default();
+        channel_layouts
+            .fixtures
+            .insert("Fixture-Line-2".to_string(), ChannelOrder::Grb);
+
+        let led_outputs = vec![lin_srgb(1.0, 0.0, 0.5); 2];
+        let calibration = Calibration::default();
+        let output = pixel_output(&channel_layouts, &calibration);
+        let payloads = build_per_fixture_payloads(&entries, &output, &led_outputs, None);
+
+        assert_eq!(
+            payloads,
+            vec![(1, vec![0, 255, 0, 127]), (2, vec![0, 0, 255, 127])]
+        );
+    }
+
+    #[test]
+    fn rgbw_pixels_extract_white() {
+        let pixel = pack_pixel_channels(
+            &lin_srgb(1.0, 0.5, 0.25),
+            eight_bit(ChannelOrder::Rgbw, 4),
+            None,
+            None,
+        );
+        assert_eq!(pixel.as_ref(), &[191, 63, 0, 63]);
+
+        let pixel = pack_pixel_channels(
+            &lin_srgb(1.0, 1.0, 1.0),
+            eight_bit(ChannelOrder::Wrgb, 4),
+            None,
+            None,
+        );
+        assert_eq!(pixel.as_ref(), &[255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn four_channel_pixels_fill_universes_without_padding() {
+        let led_outputs = vec![lin_srgb(0.0, 0.0, 0.0); 129];
+        let payloads = build_led_sacn_payloads(
+            3,
+            led_outputs.iter().map(|colour| {
+                pack_pixel_channels(colour, eight_bit(ChannelOrder::Rgbw, 4), None, None)
+            }),
+        );
+
+        // 128 RGBW pixels = 512 slots exactly, so the 129th starts a new universe.
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].0, 3);
+        assert_eq!(payloads[0].1.len(), UNIVERSE_CHANNEL_CAPACITY);
+        assert_eq!(payloads[1].0, 4);
+        assert_eq!(payloads[1].1.len(), 1 + 4);
+    }
+
+    #[test]
+    fn extra_channels_per_pixel_are_zeroed_and_stay_aligned() {
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 103];
+        let payloads = build_led_sacn_payloads(
+            1,
+            led_outputs.iter().map(|colour| {
+                pack_pixel_channels(colour, eight_bit(ChannelOrder::Rgb, 5), None, None)
+            }),
+        );
+
+        // 102 five-channel pixels = 510 slots, leaving 2 pad slots.
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].1.len(), UNIVERSE_CHANNEL_CAPACITY);
+        assert_eq!(payloads[0].1[1..6], [255, 255, 255, 0, 0]);
+        assert_eq!(payloads[0].1[UNIVERSE_CHANNEL_CAPACITY - 2..], [0, 0]);
+        assert_eq!(payloads[1].1, vec![0, 255, 255, 255, 0, 0]);
+    }
+
+    fn pixel_output<'a>(
+        channel_layouts: &'a ChannelLayouts,
+        calibration: &'a Calibration,
+    ) -> PixelOutputConfig<'a> {
+        PixelOutputConfig {
+            channel_layouts,
+            calibration,
+            bit_depth: DmxBitDepth::Eight,
+            leds_per_row: 2,
+            power_scales: &[],
+        }
+    }
+
+    fn pack_pixel_channels(
+        colour: &LinSrgb,
+        format: PixelFormat,
+        profile: Option<&CalibrationProfile>,
+        dither: Option<&mut DitherError>,
+    ) -> PixelChannels {
+        pack_pixel_levels(&pixel_levels(colour, format.order, profile), format, dither)
+    }
+
+    fn eight_bit(order: ChannelOrder, channels_per_pixel: usize) -> PixelFormat {
+        PixelFormat {
+            order,
+            channels_per_pixel,
+            bit_depth: DmxBitDepth::Eight,
+        }
+    }
+
+    #[test]
+    fn calibration_applies_white_balance_gamma_and_max_level() {
+        let profile = CalibrationProfile {
+            gamma: 2.0,
+            white_balance: [1.0, 0.5, 0.0],
+            max_level: 0.5,
+            ..CalibrationProfile::default()
+        };
+        let pixel = pack_pixel_channels(
+            &lin_srgb(1.0, 1.0, 1.0),
+            eight_bit(ChannelOrder::Rgb, 3),
+            Some(&profile),
+            None,
+        );
+
+        // Red: 1.0^2 * 0.5, green: 0.5^2 * 0.5, blue gained to zero.
+        assert_eq!(pixel.as_ref(), &[127, 31, 0]);
+    }
+
+    #[test]
+    fn calibration_lut_overrides_gamma() {
+        let profile = CalibrationProfile {
+            gamma: 3.0,
+            lut: vec![0.0, 0.25, 1.0],
+            ..CalibrationProfile::default()
+        };
+        let pixel = pack_pixel_channels(
+            &lin_srgb(0.25, 0.5, 1.0),
+            eight_bit(ChannelOrder::Rgb, 3),
+            Some(&profile),
+            None,
+        );
+
+        // 0.25 sits halfway between the first two LUT points.
+        assert_eq!(pixel.as_ref(), &[31, 63, 255]);
+    }
+
+    #[test]
+    fn row_calibration_only_affects_assigned_rows() {
+        let channel_layouts = ChannelLayouts::default();
+        let mut calibration = Calibration::default();
+        let name = calibration.add_profile();
+        calibration.profiles.get_mut(&name).unwrap().max_level = 0.0;
+        calibration.rows.insert(1, name);
+
+        let output = pixel_output(&channel_layouts, &calibration);
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 4];
+        let payloads = build_sacn_payloads(
+            Some(&DmxMap::Sequential { start_universe: 1 }),
+            1,
+            &output,
+            &led_outputs,
+            None,
+        );
+
+        assert_eq!(
+            payloads,
+            vec![(1, vec![0, 255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0])]
+        );
+    }
+
+    #[test]
+    fn drive_levels_follow_packed_channels() {
+        let mut channel_layouts = ChannelLayouts::default();
+        channel_layouts.default = ChannelOrder::Rgbw;
+        let calibration = Calibration::default();
+        let output = pixel_output(&channel_layouts, &calibration);
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 2];
+        let mut levels = Vec::new();
+
+        // White is extracted into the white channel alone.
+        led_drive_levels(None, &output, false, &led_outputs, &mut levels);
+        assert_eq!(levels, vec![1.0, 1.0]);
+        // OPC and DDP frames are RGB, so white drives all three channels.
+        led_drive_levels(None, &output, true, &led_outputs, &mut levels);
+        assert_eq!(levels, vec![3.0, 3.0]);
+    }
+
+    #[test]
+    fn power_scales_apply_after_calibration() {
+        let channel_layouts = ChannelLayouts::default();
+        let mut calibration = Calibration::default();
+        let name = calibration.add_profile();
+        calibration.profiles.get_mut(&name).unwrap().gamma = 2.0;
+        calibration.rows.insert(0, name);
+        let output = PixelOutputConfig {
+            power_scales: &[0.5],
+            ..pixel_output(&channel_layouts, &calibration)
+        };
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 2];
+        let payloads = build_sacn_payloads(None, 1, &output, &led_outputs, None);
+
+        // Half of the calibrated level, rather than half the colour squared.
+        assert_eq!(payloads[0].1[1..7], [127, 127, 127, 255, 255, 255]);
+    }
+
+    #[test]
+    fn sixteen_bit_pixels_pack_coarse_then_fine() {
+        let format = PixelFormat {
+            order: ChannelOrder::Grb,
+            channels_per_pixel: 3,
+            bit_depth: DmxBitDepth::Sixteen,
+        };
+        let pixel = pack_pixel_channels(&lin_srgb(1.0, 0.5, 0.0), format, None, None);
+
+        // 0.5 * 65535 = 32767.5, truncated to 0x7FFF.
+        assert_eq!(pixel.as_ref(), &[0x7F, 0xFF, 0xFF, 0xFF, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn sixteen_bit_levels_fade_as_pairs() {
+        // 0x0180 is 384, which halves to 0x00C0 rather than halving each byte to 0x0040.
+        let mut levels = [0x01, 0x80, 0xFF, 0xFF, 0x40];
+        fade_levels(&mut levels, DmxBitDepth::Sixteen, 1, 2);
+        assert_eq!(levels, [0x00, 0xC0, 0x7F, 0xFF, 0x20]);
+
+        fade_levels(&mut levels, DmxBitDepth::Sixteen, 2, 2);
+        assert_eq!(levels, [0; 5]);
+
+        let mut levels = [0x01, 0x80];
+        fade_levels(&mut levels, DmxBitDepth::Eight, 1, 2);
+        assert_eq!(levels, [0x00, 0x40]);
+    }
+
+    #[test]
+    fn sixteen_bit_universes_hold_85_rgb_pixels() {
+        let channel_layouts = ChannelLayouts::default();
+        let calibration = Calibration::default();
+        let output = PixelOutputConfig {
+            bit_depth: DmxBitDepth::Sixteen,
+            ..pixel_output(&channel_layouts, &calibration)
+        };
+        let led_outputs = vec![lin_srgb(1.0, 1.0, 1.0); 86];
+        let payloads = build_sacn_payloads(None, 1, &output, &led_outputs, None);
+
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].1.len(), UNIVERSE_CHANNEL_CAPACITY);
+        assert_eq!(payloads[0].1[UNIVERSE_CHANNEL_CAPACITY - 2..], [0, 0]);
+        assert_eq!(payloads[1].1.len(), 1 + 6);
+    }
+
+    #[test]
+    fn temporal_dither_averages_to_the_target_level() {
+        let format = eight_bit(ChannelOrder::Rgb, 3);
+        let colour = lin_srgb(0.25 / 255.0, 0.0, 0.0);
+        let mut error = [0.0; PIXEL_CHANNEL_KINDS];
+
+        let frames = 400;
+        let red_total: i32 = (0..frames)
+            .map(|_| {
+                let pixel = pack_pixel_channels(&colour, format, None, Some(&mut error));
+                pixel.as_ref()[0] as i32
+            })
+            .sum();
+
+        // A quarter of a step: one lit frame in four, where truncation would stay dark.
+        assert!((red_total - frames / 4).abs() <= 1);
+        let pixel = pack_pixel_channels(&colour, format, None, None);
+        assert_eq!(pixel.as_ref()[0], 0);
+    }
+
+    #[test]
+    fn temporal_dither_keeps_black_black() {
+        let format = eight_bit(ChannelOrder::Rgb, 3);
+        let mut error = [0.0; PIXEL_CHANNEL_KINDS];
+        error[0] = 0.49;
+
+        let pixel = pack_pixel_channels(&lin_srgb(0.0, 0.0, 0.0), format, None, Some(&mut error));
+
+        assert_eq!(pixel.as_ref(), &[0, 0, 0]);
+        assert_eq!(error[0], 0.0);
+    }
 }
@@ -0,0 +1,86 @@
+//! Channels of an incoming sACN universe used as control inputs alongside MIDI.
+//!
+//! This lets a house console run the show. Each channel that changes level becomes a message
+//! that is routed, learned and smoothed exactly like a MIDI CC.
+
+/// A change of level on a single DMX channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DmxMessage {
+    pub universe: u16,
+    /// The 1-based channel, i.e. the slot after the start code.
+    pub channel: u16,
+    pub value: u8,
+}
+
+/// Turns successive payloads of the control universe into per-channel change messages.
+#[derive(Debug, Default)]
+pub struct DmxControlInput {
+    universe: Option<u16>,
+    levels: Vec<u8>,
+}
+
+impl DmxControlInput {
+    /// Compare the start-code-prefixed `payload` with the last one seen, returning a message for
+    /// every channel that changed.
+    ///
+    /// The first payload of a universe only sets the baseline. Like MIDI, a control takes effect
+    /// once it is moved, and learn picks the channel that was moved rather than every channel
+    /// that happens to be up.
+    pub fn update(&mut self, universe: u16, payload: &[u8]) -> Vec<DmxMessage> {
+        let levels = payload.get(1..).unwrap_or(&[]);
+        if self.universe != Some(universe) {
+            self.universe = Some(universe);
+            self.levels = levels.to_vec();
+            return Vec::new();
+        }
+
+        let messages = levels
+            .iter()
+            .enumerate()
+            .filter(|&(ix, &value)| self.levels.get(ix).copied().unwrap_or(0) != value)
+            .map(|(ix, &value)| DmxMessage {
+                universe,
+                channel: ix as u16 + 1,
+                value,
+            })
+            .collect();
+        self.levels = levels.to_vec();
+        messages
+    }
+
+    /// Forget the baseline, e.g. when the control universe is lost.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DmxControlInput, DmxMessage};
+
+    #[test]
+    fn only_changed_channels_produce_messages() {
+        let mut input = DmxControlInput::default();
+        // The first payload is the baseline.
+        assert!(input.update(5, &[0, 255, 0, 10]).is_empty());
+        assert!(input.update(5, &[0, 255, 0, 10]).is_empty());
+        assert_eq!(
+            input.update(5, &[0, 255, 128, 10]),
+            vec![DmxMessage {
+                universe: 5,
+                channel: 2,
+                value: 128,
+            }]
+        );
+        // A longer payload reports new non-zero channels.
+        assert_eq!(input.update(5, &[0, 255, 128, 10, 0, 7]).len(), 1);
+    }
+
+    #[test]
+    fn changing_universe_resets_the_baseline() {
+        let mut input = DmxControlInput::default();
+        input.update(5, &[0, 1, 2]);
+        assert!(input.update(6, &[0, 9, 9]).is_empty());
+        assert_eq!(input.update(6, &[0, 9, 8])[0].channel, 2);
+    }
+}
@@ -1,3 +1,4 @@
+use super::dmx::DmxMessage;
 use super::MidiMessage;
 use crate::midi::mapping::MidiTarget;
 use std::time::Instant;
@@ -21,10 +22,21 @@ impl LearnState {
 
     /// If currently listening, consume the message and return the (port, cc) to assign.
     pub fn receive(&mut self, msg: &MidiMessage) -> Option<(String, u8, MidiTarget)> {
-        if let Self::Listening(target) = self {
-            let result = (msg.port_name.clone(), msg.cc, *target);
+        self.learn()
+            .map(|target| (msg.port_name.clone(), msg.cc, target))
+    }
+
+    /// If currently listening, consume the DMX message and return the (universe, channel) to
+    /// assign.
+    pub fn receive_dmx(&mut self, msg: &DmxMessage) -> Option<(u16, u16, MidiTarget)> {
+        self.learn()
+            .map(|target| (msg.universe, msg.channel, target))
+    }
+
+    fn learn(&mut self) -> Option<MidiTarget> {
+        if let Self::Listening(target) = *self {
             *self = Self::Learned(Instant::now());
-            Some(result)
+            Some(target)
         } else {
             None
         }
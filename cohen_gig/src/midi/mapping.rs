@@ -12,6 +12,7 @@ pub enum MidiTarget {
     MasterSpeed,
     PhaseOffset,
     PhaseOffsetMod,
+    PresetSelect,
     // Blending/Mix
     LeftRightMix,
     FadeToBlack,
@@ -42,6 +43,7 @@ impl MidiTarget {
             MidiTarget::MasterSpeed,
             MidiTarget::PhaseOffset,
             MidiTarget::PhaseOffsetMod,
+            MidiTarget::PresetSelect,
             // Blending/Mix
             MidiTarget::LeftRightMix,
             MidiTarget::FadeToBlack,
@@ -80,6 +82,7 @@ impl MidiTarget {
             MidiTarget::MasterSpeed => "Global Speed",
             MidiTarget::PhaseOffset => "Phase Offset",
             MidiTarget::PhaseOffsetMod => "Phase Offset Mod",
+            MidiTarget::PresetSelect => "Preset Select",
             MidiTarget::LeftRightMix => "Left/Right Mix",
             MidiTarget::FadeToBlack => "Fade to Black",
             MidiTarget::AudioGain => "Audio Gain",
@@ -117,7 +120,8 @@ impl MidiTarget {
             MidiTarget::SmoothingSpeed
             | MidiTarget::MasterSpeed
             | MidiTarget::PhaseOffset
-            | MidiTarget::PhaseOffsetMod => "Global",
+            | MidiTarget::PhaseOffsetMod
+            | MidiTarget::PresetSelect => "Global",
             MidiTarget::LeftRightMix | MidiTarget::FadeToBlack => "Blending/Mix",
             MidiTarget::AudioGain
             | MidiTarget::AudioThreshold
@@ -152,10 +156,21 @@ pub struct MidiMappingEntry {
     pub target: MidiTarget,
 }
 
+/// A channel of an incoming sACN universe mapped to a control.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DmxMappingEntry {
+    pub universe: u16,
+    /// The 1-based DMX channel.
+    pub channel: u16,
+    pub target: MidiTarget,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MidiMappingPreset {
     pub name: String,
     pub entries: Vec<MidiMappingEntry>,
+    #[serde(default)]
+    pub dmx_entries: Vec<DmxMappingEntry>,
 }
 
 impl MidiMappingPreset {
@@ -163,6 +178,7 @@ impl MidiMappingPreset {
         Self {
             name,
             entries: Vec::new(),
+            dmx_entries: Vec::new(),
         }
     }
 
@@ -173,6 +189,13 @@ impl MidiMappingPreset {
             .collect()
     }
 
+    pub fn dmx_lookup_table(&self) -> HashMap<(u16, u16), MidiTarget> {
+        self.dmx_entries
+            .iter()
+            .map(|e| ((e.universe, e.channel), e.target))
+            .collect()
+    }
+
     pub fn save(&self, dir: &Path) -> io::Result<()> {
         std::fs::create_dir_all(dir)?;
         let path = dir.join(format!("{}.json", self.name));
@@ -213,22 +236,29 @@ impl Default for MidiMappingPreset {
 pub struct MidiMapping {
     pub preset: MidiMappingPreset,
     lookup: HashMap<(String, u8), MidiTarget>,
+    dmx_lookup: HashMap<(u16, u16), MidiTarget>,
 }
 
 impl MidiMapping {
     pub fn new(preset: MidiMappingPreset) -> Self {
         let lookup = preset.lookup_table();
-        Self { preset, lookup }
+        let dmx_lookup = preset.dmx_lookup_table();
+        Self {
+            preset,
+            lookup,
+            dmx_lookup,
+        }
     }
 
     pub fn set_preset(&mut self, preset: MidiMappingPreset) {
         self.lookup = preset.lookup_table();
+        self.dmx_lookup = preset.dmx_lookup_table();
         self.preset = preset;
     }
 
     pub fn assign(&mut self, port_name: String, cc: u8, target: MidiTarget) {
         // Remove any existing entry for this target.
-        self.preset.entries.retain(|e| e.target != target);
+        self.remove_target(target);
         // Remove any existing entry for this (port, cc) combo.
         self.preset
             .entries
@@ -241,10 +271,32 @@ impl MidiMapping {
         self.lookup = self.preset.lookup_table();
     }
 
+    pub fn assign_dmx(&mut self, universe: u16, channel: u16, target: MidiTarget) {
+        // Remove any existing entry for this target.
+        self.remove_target(target);
+        // Remove any existing entry for this (universe, channel) combo.
+        self.preset
+            .dmx_entries
+            .retain(|e| !(e.universe == universe && e.channel == channel));
+        self.preset.dmx_entries.push(DmxMappingEntry {
+            universe,
+            channel,
+            target,
+        });
+        self.dmx_lookup = self.preset.dmx_lookup_table();
+    }
+
     #[allow(dead_code)]
     pub fn unassign(&mut self, target: MidiTarget) {
+        self.remove_target(target);
+    }
+
+    /// Remove the MIDI or DMX entry for the target, as each target has a single source.
+    fn remove_target(&mut self, target: MidiTarget) {
         self.preset.entries.retain(|e| e.target != target);
+        self.preset.dmx_entries.retain(|e| e.target != target);
         self.lookup = self.preset.lookup_table();
+        self.dmx_lookup = self.preset.dmx_lookup_table();
     }
 
     pub fn target_for(&self, port_name: &str, cc: u8) -> Option<MidiTarget> {
@@ -254,6 +306,14 @@ impl MidiMapping {
     pub fn entry_for(&self, target: MidiTarget) -> Option<&MidiMappingEntry> {
         self.preset.entries.iter().find(|e| e.target == target)
     }
+
+    pub fn dmx_target_for(&self, universe: u16, channel: u16) -> Option<MidiTarget> {
+        self.dmx_lookup.get(&(universe, channel)).copied()
+    }
+
+    pub fn dmx_entry_for(&self, target: MidiTarget) -> Option<&DmxMappingEntry> {
+        self.preset.dmx_entries.iter().find(|e| e.target == target)
+    }
 }
 
 impl Default for MidiMapping {
@@ -1,3 +1,4 @@
+pub mod dmx;
 pub mod learn;
 pub mod mapping;
 
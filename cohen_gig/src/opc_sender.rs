@@ -0,0 +1,184 @@
+//! A minimal Open Pixel Control sender.
+//!
+//! OPC runs over TCP, so a connection is kept open to each destination and re-established when
+//! it drops. Connections are made on a background thread so that an absent controller never
+//! blocks the output, and frames for a destination are skipped until it is connected. Each frame
+//! is sent as a single "set pixel colours" message on channel 0, which addresses every output of
+//! the controller in order.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// The TCP port on which OPC servers listen by default.
+pub const OPC_PORT: u16 = 7890;
+
+const CMD_SET_PIXEL_COLOURS: u8 = 0;
+const BROADCAST_CHANNEL: u8 = 0;
+const HEADER_LEN: usize = 4;
+/// The length field is 16 bits, so frames are truncated to whole pixels below this.
+const MAX_DATA_LEN: usize = u16::MAX as usize / 3 * 3;
+/// Connecting happens off the output thread, so this only bounds how long an attempt lasts.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+/// How long to wait after a failed connection before trying the destination again.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct OpcSender {
+    connections: HashMap<SocketAddr, TcpStream>,
+    /// Connection attempts running on a background thread.
+    connecting: HashMap<SocketAddr, mpsc::Receiver<io::Result<TcpStream>>>,
+    failed_at: HashMap<SocketAddr, Instant>,
+}
+
+impl OpcSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a frame of 8-bit RGB pixel data to the given OPC server.
+    ///
+    /// Returns a `NotConnected` error without blocking while the destination is still connecting
+    /// or waiting to reconnect.
+    pub fn send_pixels(&mut self, destination: SocketAddr, rgb: &[u8]) -> io::Result<()> {
+        let packet = build_opc_message(BROADCAST_CHANNEL, rgb);
+        let stream = self.connection(destination)?;
+        if let Err(err) = stream.write_all(&packet) {
+            self.connections.remove(&destination);
+            self.failed_at.insert(destination, Instant::now());
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn connection(&mut self, destination: SocketAddr) -> io::Result<&mut TcpStream> {
+        if !self.connections.contains_key(&destination) {
+            let stream = self.poll_connect(destination).inspect_err(|err| {
+                if err.kind() != io::ErrorKind::NotConnected {
+                    self.failed_at.insert(destination, Instant::now());
+                }
+            })?;
+            self.failed_at.remove(&destination);
+            self.connections.insert(destination, stream);
+        }
+        Ok(self
+            .connections
+            .get_mut(&destination)
+            .expect("connection was just inserted"))
+    }
+
+    /// Check on the connection attempt to the destination, starting one if none is running.
+    fn poll_connect(&mut self, destination: SocketAddr) -> io::Result<TcpStream> {
+        let not_connected = |reason: &str| {
+            io::Error::new(
+                io::ErrorKind::NotConnected,
+                format!("{} {}", reason, destination),
+            )
+        };
+        if let Some(attempt) = self.connecting.get(&destination) {
+            let result = match attempt.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return Err(not_connected("still connecting to")),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Err(io::Error::other("connection attempt ended unexpectedly"))
+                }
+            };
+            self.connecting.remove(&destination);
+            return result;
+        }
+        if let Some(failed_at) = self.failed_at.get(&destination) {
+            if failed_at.elapsed() < RECONNECT_INTERVAL {
+                return Err(not_connected("waiting to reconnect to"));
+            }
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("opc-connect".to_string())
+            .spawn(move || {
+                let result =
+                    TcpStream::connect_timeout(&destination, CONNECT_TIMEOUT).and_then(|stream| {
+                        stream.set_nodelay(true)?;
+                        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                        Ok(stream)
+                    });
+                let _ = tx.send(result);
+            })?;
+        self.connecting.insert(destination, rx);
+        Err(not_connected("connecting to"))
+    }
+}
+
+/// Pack an OPC "set pixel colours" message.
+pub fn build_opc_message(channel: u8, rgb: &[u8]) -> Vec<u8> {
+    let data = &rgb[..rgb.len().min(MAX_DATA_LEN)];
+    let mut packet = Vec::with_capacity(HEADER_LEN + data.len());
+    packet.push(channel);
+    packet.push(CMD_SET_PIXEL_COLOURS);
+    packet.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_opc_message, OpcSender};
+    use std::io::{self, Read};
+    use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+    use std::time::Duration;
+
+    /// Retry until the background connection is made.
+    fn send_when_connected(sender: &mut OpcSender, addr: SocketAddr, rgb: &[u8]) {
+        for _ in 0..200 {
+            match sender.send_pixels(addr, rgb) {
+                Ok(()) => return,
+                Err(err) if err.kind() == io::ErrorKind::NotConnected => {
+                    std::thread::sleep(Duration::from_millis(5))
+                }
+                Err(err) => panic!("couldn't send: {}", err),
+            }
+        }
+        panic!("never connected to {}", addr);
+    }
+
+    #[test]
+    fn build_opc_message_packs_header_and_pixels() {
+        let packet = build_opc_message(0, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(packet, vec![0, 0, 0, 6, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn loopback_listener_receives_framed_messages() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut sender = OpcSender::new();
+
+        let first = sender.send_pixels(addr, &[1, 1, 1]).unwrap_err();
+        assert_eq!(first.kind(), io::ErrorKind::NotConnected);
+        send_when_connected(&mut sender, addr, &[10, 20, 30]);
+        sender.send_pixels(addr, &[40, 50, 60, 70, 80, 90]).unwrap();
+
+        // Both frames arrive on the one connection.
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = [0u8; 4 + 3 + 4 + 6];
+        stream.read_exact(&mut received).unwrap();
+        assert_eq!(&received[..7], &[0, 0, 0, 3, 10, 20, 30]);
+        assert_eq!(&received[7..], &[0, 0, 0, 6, 40, 50, 60, 70, 80, 90]);
+    }
+
+    #[test]
+    fn unreachable_destination_does_not_block() {
+        // TEST-NET-1 is never routed, so the connection attempt can only time out.
+        let addr = SocketAddr::from((Ipv4Addr::new(192, 0, 2, 1), super::OPC_PORT));
+        let mut sender = OpcSender::new();
+
+        let started = std::time::Instant::now();
+        for _ in 0..10 {
+            let err = sender.send_pixels(addr, &[1, 2, 3]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+        }
+        assert!(started.elapsed() < Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,334 @@
+//! Estimated LED power draw and automatic brightness limiting.
+//!
+//! Current is estimated from the channel levels actually sent to each LED, after calibration and
+//! white or amber extraction, assuming each channel draws `milliamps_per_channel` at full level
+//! and scales linearly below that. Each PSU group whose estimate exceeds its budget is scaled down
+//! until it fits. The scales are applied to the channel levels as they are packed, so the limited
+//! draw follows the estimate exactly.
+
+use crate::conf::{PowerLimit, PsuGroup};
+use crate::layout::DmxMap;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Instant;
+
+/// Time constant used when letting a group's brightness recover.
+///
+/// Limiting is applied on the frame a group goes over budget, so only recovery is smoothed.
+const LIMIT_RELEASE_SECS: f32 = 1.0;
+
+/// The estimated draw of a single PSU group for the latest frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupPowerEstimate {
+    pub name: String,
+    /// The estimated draw before limiting.
+    pub amps: f32,
+    pub budget_amps: f32,
+    /// The brightness scale applied to the group, `1.0` when unlimited.
+    pub scale: f32,
+}
+
+/// The estimated draw of a single fixture for the latest frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FixturePowerEstimate {
+    pub name: String,
+    /// The estimated draw after limiting.
+    pub amps: f32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PowerEstimate {
+    /// The estimated draw of all LEDs after limiting.
+    pub total_amps: f32,
+    pub groups: Vec<GroupPowerEstimate>,
+    /// One entry per fixture of a per-fixture DMX map, in layout order. Empty otherwise.
+    pub fixtures: Vec<FixturePowerEstimate>,
+}
+
+/// Tracks the brightness scale of each PSU group between frames.
+#[derive(Debug, Default)]
+pub struct PowerLimiter {
+    /// The scale of each group, by group name, so edits to the group list don't move a scale
+    /// onto another group.
+    scales: HashMap<String, f32>,
+    /// The scale applied to each LED for the latest frame.
+    led_scales: Vec<f32>,
+    last_update: Option<Instant>,
+}
+
+impl PowerLimiter {
+    /// Estimate the draw of each group and, if enabled, limit any group over budget.
+    ///
+    /// `drive_levels` holds the sum of the channel levels each LED is driven at, where a channel
+    /// at full level counts as `1.0`. The resulting scales are given by `led_scales`.
+    pub fn limit(
+        &mut self,
+        config: &PowerLimit,
+        dmx_map: Option<&DmxMap>,
+        leds_per_row: usize,
+        drive_levels: &[f32],
+        now: Instant,
+    ) -> PowerEstimate {
+        let dt_secs = self
+            .last_update
+            .map(|last| now.saturating_duration_since(last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_update = Some(now);
+        self.scales
+            .retain(|name, _| config.groups.iter().any(|group| group.name == *name));
+        self.led_scales.clear();
+        self.led_scales.resize(drive_levels.len(), 1.0);
+
+        let mut groups = Vec::with_capacity(config.groups.len());
+        for group in &config.groups {
+            let ranges = group_led_ranges(group, dmx_map, leds_per_row, drive_levels.len());
+            let amps: f32 = ranges
+                .iter()
+                .map(|range| {
+                    estimate_amps(&drive_levels[range.clone()], config.milliamps_per_channel)
+                })
+                .sum();
+
+            let target = if config.enabled && amps > group.budget_amps {
+                (group.budget_amps.max(0.0) / amps).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            let scale = self.scales.entry(group.name.clone()).or_insert(1.0);
+            *scale = next_scale(*scale, target, dt_secs);
+            if *scale < 1.0 {
+                for range in &ranges {
+                    for led_scale in &mut self.led_scales[range.clone()] {
+                        *led_scale = led_scale.min(*scale);
+                    }
+                }
+            }
+
+            groups.push(GroupPowerEstimate {
+                name: group.name.clone(),
+                amps,
+                budget_amps: group.budget_amps,
+                scale: *scale,
+            });
+        }
+
+        let limited_levels: Vec<f32> = drive_levels
+            .iter()
+            .zip(&self.led_scales)
+            .map(|(level, scale)| level * scale)
+            .collect();
+        let fixtures = match dmx_map {
+            Some(DmxMap::PerFixture(entries)) => entries
+                .iter()
+                .map(|entry| {
+                    let start = entry.led_offset.min(limited_levels.len());
+                    let end = (entry.led_offset + entry.led_count).min(limited_levels.len());
+                    FixturePowerEstimate {
+                        name: entry.fixture_name.clone(),
+                        amps: estimate_amps(
+                            &limited_levels[start..end],
+                            config.milliamps_per_channel,
+                        ),
+                    }
+                })
+                .collect(),
+            Some(DmxMap::Sequential { .. }) | None => Vec::new(),
+        };
+        PowerEstimate {
+            total_amps: estimate_amps(&limited_levels, config.milliamps_per_channel),
+            groups,
+            fixtures,
+        }
+    }
+
+    /// The scale to apply to the channel levels of each LED, as of the latest `limit`.
+    pub fn led_scales(&self) -> &[f32] {
+        &self.led_scales
+    }
+}
+
+/// The estimated current in amps drawn by LEDs driven at the given summed channel levels.
+pub fn estimate_amps(drive_levels: &[f32], milliamps_per_channel: f32) -> f32 {
+    let channel_total: f32 = drive_levels.iter().sum();
+    channel_total * milliamps_per_channel / 1000.0
+}
+
+/// The ranges of LED indices powered by the given group.
+pub fn group_led_ranges(
+    group: &PsuGroup,
+    dmx_map: Option<&DmxMap>,
+    leds_per_row: usize,
+    led_count: usize,
+) -> Vec<Range<usize>> {
+    let clamp = |range: Range<usize>| range.start.min(led_count)..range.end.min(led_count);
+    match dmx_map {
+        Some(DmxMap::PerFixture(entries)) => entries
+            .iter()
+            .filter(|entry| group.fixtures.contains(&entry.fixture_name))
+            .map(|entry| clamp(entry.led_offset..entry.led_offset + entry.led_count))
+            .collect(),
+        Some(DmxMap::Sequential { .. }) | None => {
+            let leds_per_row = leds_per_row.max(1);
+            group
+                .rows
+                .iter()
+                .map(|&row| clamp(row * leds_per_row..(row + 1) * leds_per_row))
+                .collect()
+        }
+    }
+}
+
+/// Drop straight to an over-budget target so no frame exceeds the budget, then ease back up.
+fn next_scale(current: f32, target: f32, dt_secs: f32) -> f32 {
+    if target <= current {
+        return target;
+    }
+    let amount = 1.0 - (-dt_secs / LIMIT_RELEASE_SECS).exp();
+    current + (target - current) * amount
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{estimate_amps, PowerLimiter};
+    use crate::conf::{PowerLimit, PsuGroup};
+    use crate::layout::{DmxMap, FixtureDmxEntry};
+    use std::time::{Duration, Instant};
+
+    fn group(name: &str, budget_amps: f32, row: usize) -> PsuGroup {
+        PsuGroup {
+            name: name.to_string(),
+            budget_amps,
+            fixtures: Vec::new(),
+            rows: vec![row],
+        }
+    }
+
+    fn two_row_config(enabled: bool) -> PowerLimit {
+        PowerLimit {
+            enabled,
+            milliamps_per_channel: 20.0,
+            groups: vec![group("PSU 1", 0.3, 0), group("PSU 2", 10.0, 1)],
+        }
+    }
+
+    /// Ten LEDs per row, each driving three channels at full level.
+    fn full_white(rows: usize) -> Vec<f32> {
+        vec![3.0; rows * 10]
+    }
+
+    #[test]
+    fn full_white_draws_three_channels() {
+        // 50 LEDs * 3 channels * 20mA.
+        assert!((estimate_amps(&[3.0; 50], 20.0) - 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn disabled_limiter_only_estimates() {
+        let mut limiter = PowerLimiter::default();
+        let estimate = limiter.limit(
+            &two_row_config(false),
+            None,
+            10,
+            &full_white(2),
+            Instant::now(),
+        );
+
+        assert!((estimate.groups[0].amps - 0.6).abs() < 1e-4);
+        assert_eq!(estimate.groups[0].scale, 1.0);
+        assert!(limiter.led_scales().iter().all(|&scale| scale == 1.0));
+    }
+
+    #[test]
+    fn over_budget_groups_are_limited_on_the_first_frame() {
+        let config = two_row_config(true);
+        let mut limiter = PowerLimiter::default();
+        let estimate = limiter.limit(&config, None, 10, &full_white(2), Instant::now());
+
+        assert!((estimate.groups[0].scale - 0.5).abs() < 1e-6);
+        assert!((limiter.led_scales()[0] - 0.5).abs() < 1e-6);
+        // The second row is within budget and left untouched.
+        assert_eq!(estimate.groups[1].scale, 1.0);
+        assert_eq!(limiter.led_scales()[10], 1.0);
+        assert!((estimate.total_amps - 0.9).abs() < 1e-4);
+    }
+
+    #[test]
+    fn limited_groups_recover_gradually() {
+        let config = two_row_config(true);
+        let mut limiter = PowerLimiter::default();
+        let start = Instant::now();
+        limiter.limit(&config, None, 10, &full_white(2), start);
+
+        let dark = vec![0.0; 20];
+        let soon = start + Duration::from_millis(100);
+        let estimate = limiter.limit(&config, None, 10, &dark, soon);
+        assert!(estimate.groups[0].scale > 0.5 && estimate.groups[0].scale < 0.6);
+
+        let later = soon + Duration::from_secs(10);
+        let estimate = limiter.limit(&config, None, 10, &dark, later);
+        assert!((estimate.groups[0].scale - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn scales_follow_groups_by_name() {
+        let mut config = two_row_config(true);
+        let mut limiter = PowerLimiter::default();
+        let start = Instant::now();
+        limiter.limit(&config, None, 10, &full_white(2), start);
+
+        // Removing the limited group must not carry its scale onto the group that takes its slot.
+        config.groups.remove(0);
+        let later = start + Duration::from_millis(16);
+        let estimate = limiter.limit(&config, None, 10, &full_white(2), later);
+        assert_eq!(estimate.groups[0].name, "PSU 2");
+        assert_eq!(estimate.groups[0].scale, 1.0);
+        assert!(limiter.led_scales().iter().all(|&scale| scale == 1.0));
+    }
+    #[test]
+    fn fixtures_are_estimated_from_their_led_ranges() {
+        let fixture = |name: &str, led_offset, led_count| FixtureDmxEntry {
+            fixture_name: name.to_string(),
+            led_offset,
+            led_count,
+            start_universe: 1,
+            start_channel: 1,
+            channels_per_pixel: 3,
+            pixel_destination: None,
+        };
+        let dmx_map = DmxMap::PerFixture(vec![fixture("Left", 0, 5), fixture("Right", 5, 15)]);
+        let config = PowerLimit {
+            enabled: true,
+            milliamps_per_channel: 20.0,
+            groups: vec![PsuGroup {
+                name: "PSU 1".to_string(),
+                budget_amps: 0.15,
+                fixtures: vec!["Left".to_string()],
+                rows: Vec::new(),
+            }],
+        };
+        let mut limiter = PowerLimiter::default();
+        let estimate = limiter.limit(&config, Some(&dmx_map), 10, &full_white(2), Instant::now());
+
+        assert_eq!(estimate.fixtures.len(), 2);
+        assert_eq!(estimate.fixtures[0].name, "Left");
+        // 5 LEDs * 3 channels * 20mA, halved to fit the budget.
+        assert!((estimate.fixtures[0].amps - 0.15).abs() < 1e-4);
+        assert_eq!(estimate.fixtures[1].name, "Right");
+        assert!((estimate.fixtures[1].amps - 0.9).abs() < 1e-4);
+        let fixture_total: f32 = estimate.fixtures.iter().map(|fixture| fixture.amps).sum();
+        assert!((fixture_total - estimate.total_amps).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sequential_maps_have_no_fixture_estimates() {
+        let mut limiter = PowerLimiter::default();
+        let estimate = limiter.limit(
+            &two_row_config(false),
+            None,
+            10,
+            &full_white(2),
+            Instant::now(),
+        );
+        assert!(estimate.fixtures.is_empty());
+    }
+}
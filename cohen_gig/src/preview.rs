@@ -0,0 +1,228 @@
+//! Scheduling and sampling for the isolated left, right and colourise previews shown in the GUI.
+//!
+//! The previews are only there to be looked at, so they are rendered no more often than the
+//! configured rate and, at reduced resolutions, only for one LED in each square of LEDs. The
+//! colour of each rendered LED is then spread across the rest of its square.
+
+use crate::conf::{ShaderPreviewResolution, ShaderPreviews};
+use nannou::prelude::*;
+use shader_shared::{CachedLedShaderInput, Light};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Decides when the previews are due and which LEDs they render.
+#[derive(Default)]
+pub struct PreviewRenderer {
+    last_rendered_at: Option<Instant>,
+    sampling: Option<Sampling>,
+}
+
+/// The subset of LEDs rendered at a reduced resolution.
+struct Sampling {
+    divisor: usize,
+    led_count: usize,
+    /// The LEDs that are rendered.
+    inputs: Vec<CachedLedShaderInput>,
+    /// The index of each rendered LED among all LEDs.
+    indices: Vec<usize>,
+    /// For each of all LEDs, the index of the rendered LED whose colour it shows.
+    sources: Vec<usize>,
+    last_colors: Vec<LinSrgb>,
+    colors: Vec<LinSrgb>,
+}
+
+impl PreviewRenderer {
+    /// Whether the previews should be rendered at `now`, marking them as rendered if so.
+    pub fn due(&mut self, settings: &ShaderPreviews, now: Instant) -> bool {
+        let Some(interval) = settings.rate.interval() else {
+            return false;
+        };
+        let due = self
+            .last_rendered_at
+            .is_none_or(|last| now.saturating_duration_since(last) >= interval);
+        if due {
+            self.last_rendered_at = Some(now);
+        }
+        due
+    }
+
+    /// Forget the sampled LEDs so they're chosen again from the latest layout.
+    pub fn invalidate(&mut self) {
+        self.sampling = None;
+    }
+
+    /// Render a single preview pass into `output`.
+    ///
+    /// `render` shades the given LEDs from their previous colours. At full resolution it's given
+    /// all of the LEDs, otherwise only the sampled ones. An error from `render` is returned
+    /// without updating `output`.
+    pub fn render(
+        &mut self,
+        resolution: ShaderPreviewResolution,
+        inputs: &[CachedLedShaderInput],
+        last_colors: &[LinSrgb],
+        output: &mut [LinSrgb],
+        mut render: impl FnMut(
+            &[CachedLedShaderInput],
+            &[LinSrgb],
+            &mut [LinSrgb],
+        ) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let divisor = resolution.divisor();
+        if divisor <= 1 {
+            return render(inputs, last_colors, output);
+        }
+
+        let up_to_date = matches!(
+            &self.sampling,
+            Some(s) if s.divisor == divisor && s.led_count == inputs.len()
+        );
+        if !up_to_date {
+            self.sampling = Some(Sampling::new(inputs, divisor));
+        }
+        let sampling = self.sampling.as_mut().expect("sampling was just built");
+
+        let black = lin_srgb(0.0, 0.0, 0.0);
+        for (last, &ix) in sampling.last_colors.iter_mut().zip(&sampling.indices) {
+            *last = last_colors.get(ix).copied().unwrap_or(black);
+        }
+        render(
+            &sampling.inputs,
+            &sampling.last_colors,
+            &mut sampling.colors,
+        )?;
+        for (out, &source) in output.iter_mut().zip(&sampling.sources) {
+            *out = sampling.colors[source];
+        }
+        Ok(())
+    }
+}
+
+impl Sampling {
+    /// Render the first LED found in each `divisor` x `divisor` square of columns and rows.
+    fn new(inputs: &[CachedLedShaderInput], divisor: usize) -> Self {
+        let mut sampled_inputs = Vec::new();
+        let mut indices = Vec::new();
+        let mut squares = HashMap::new();
+        let sources = inputs
+            .iter()
+            .enumerate()
+            .map(|(ix, input)| {
+                let Light::Led {
+                    col_row: [col, row],
+                    ..
+                } = input.light;
+                *squares
+                    .entry([col / divisor, row / divisor])
+                    .or_insert_with(|| {
+                        sampled_inputs.push(*input);
+                        indices.push(ix);
+                        sampled_inputs.len() - 1
+                    })
+            })
+            .collect();
+        let black = lin_srgb(0.0, 0.0, 0.0);
+        Sampling {
+            divisor,
+            led_count: inputs.len(),
+            last_colors: vec![black; sampled_inputs.len()],
+            colors: vec![black; sampled_inputs.len()],
+            inputs: sampled_inputs,
+            indices,
+            sources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PreviewRenderer;
+    use crate::conf::{ShaderPreviewRate, ShaderPreviewResolution, ShaderPreviews};
+    use nannou::prelude::*;
+    use shader_shared::{CachedLedShaderInput, Light};
+    use std::time::{Duration, Instant};
+
+    /// Colours each LED by its column and row so the source of each output is recognisable.
+    fn shade(inputs: &[CachedLedShaderInput], _: &[LinSrgb], output: &mut [LinSrgb]) {
+        for (out, input) in output.iter_mut().zip(inputs) {
+            let Light::Led {
+                col_row: [col, row],
+                ..
+            } = input.light;
+            *out = lin_srgb(col as f32, row as f32, 0.0);
+        }
+    }
+
+    #[test]
+    fn full_resolution_renders_every_led() {
+        let inputs = CachedLedShaderInput::grid(5, 3);
+        let last_colors = vec![lin_srgb(0.0, 0.0, 0.0); inputs.len()];
+        let mut output = last_colors.clone();
+        let mut rendered = 0;
+        PreviewRenderer::default()
+            .render(
+                ShaderPreviewResolution::Full,
+                &inputs,
+                &last_colors,
+                &mut output,
+                |inputs, last, out| {
+                    rendered += inputs.len();
+                    shade(inputs, last, out);
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert_eq!(rendered, inputs.len());
+        assert_eq!(output[7], lin_srgb(2.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn reduced_resolution_spreads_each_sample_across_its_square() {
+        let inputs = CachedLedShaderInput::grid(5, 3);
+        let last_colors = vec![lin_srgb(0.0, 0.0, 0.0); inputs.len()];
+        let mut output = last_colors.clone();
+        let mut rendered = 0;
+        PreviewRenderer::default()
+            .render(
+                ShaderPreviewResolution::Half,
+                &inputs,
+                &last_colors,
+                &mut output,
+                |inputs, last, out| {
+                    rendered += inputs.len();
+                    shade(inputs, last, out);
+                    Ok(())
+                },
+            )
+            .unwrap();
+        // Columns 0-1, 2-3 and 4 over rows 0-1 and 2.
+        assert_eq!(rendered, 6);
+        for (input, colour) in inputs.iter().zip(&output) {
+            let Light::Led {
+                col_row: [col, row],
+                ..
+            } = input.light;
+            let square = [(col / 2 * 2) as f32, (row / 2 * 2) as f32];
+            assert_eq!([colour.red, colour.green], square);
+        }
+    }
+
+    #[test]
+    fn previews_are_due_at_the_configured_rate() {
+        let mut previews = PreviewRenderer::default();
+        let settings = ShaderPreviews {
+            rate: ShaderPreviewRate::Fps5,
+            ..ShaderPreviews::default()
+        };
+        let start = Instant::now();
+        assert!(previews.due(&settings, start));
+        assert!(!previews.due(&settings, start + Duration::from_millis(100)));
+        assert!(previews.due(&settings, start + Duration::from_millis(250)));
+
+        let off = ShaderPreviews {
+            rate: ShaderPreviewRate::Off,
+            ..ShaderPreviews::default()
+        };
+        assert!(!previews.due(&off, start + Duration::from_secs(1)));
+    }
+}
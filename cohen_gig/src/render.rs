@@ -0,0 +1,149 @@
+//! Rendering the mix graph of a preset to every LED.
+//!
+//! Shared by the LED worker and the render benchmark, so the benchmark times the same chunking and
+//! the same modulated uniforms as the show.
+
+use crate::conf;
+use crate::layout;
+use crate::shader::ShaderFns;
+use nannou::prelude::*;
+use rayon::prelude::*;
+use shader_shared::{
+    Button, ButtonState, CachedLedShaderInput, MixingInfo, ParamKindMut, ParamMut, Shader,
+    ShaderParams, Uniforms, Vertex,
+};
+use std::collections::HashMap;
+
+/// The number of LEDs passed to each call of the batch shader, split across the rayon pool.
+pub const LED_SHADER_CHUNK_LEN: usize = 256;
+
+/// The uniforms for rendering `preset` at `time`, with its params modulated by `audio_envelope`.
+pub fn preset_uniforms(
+    preset: &conf::Preset,
+    led_layout: &conf::LedLayout,
+    time: f32,
+    audio_envelope: f32,
+    colour_channels: [f32; 3],
+    buttons: HashMap<Button, ButtonState>,
+) -> Uniforms {
+    /*
+    when t is -1, volumes[0] = 0, volumes[1] = 1
+    when t = 0, volumes[0] = 0.707, volumes[1] = 0.707 (equal-power cross fade)
+    when t = 1, volumes[0] = 1, volumes[1] = 0
+    // Equal power xfade taken from https://dsp.stackexchange.com/questions/14754/equal-power-crossfade
+    */
+    let lr_mix = preset.left_right_mix;
+    let xfade_left = (0.5 * (1.0 + lr_mix)).sqrt();
+    let xfade_right = (0.5 * (1.0 - lr_mix)).sqrt();
+
+    let modulated = |shader: Shader, mut params: ShaderParams, mod_amounts: &[f32]| {
+        let mut mod_ix = 0;
+        apply_shader_modulation(
+            shader,
+            &mut params,
+            &mut mod_ix,
+            mod_amounts,
+            audio_envelope,
+        );
+        params
+    };
+    let mix_info = MixingInfo {
+        left: preset.shader_left,
+        right: preset.shader_right,
+        colourise: preset.colourise,
+        blend_mode: preset.blend_mode,
+        tone_mapping: preset.tone_mapping,
+        tone_mapping_amount: preset.tone_mapping_amount,
+        xfade_left,
+        xfade_right,
+        params_left: modulated(
+            preset.shader_left,
+            preset.shader_params_left,
+            &preset.shader_mod_amounts_left,
+        ),
+        params_right: modulated(
+            preset.shader_right,
+            preset.shader_params_right,
+            &preset.shader_mod_amounts_right,
+        ),
+        params_colourise: modulated(
+            preset.colourise,
+            preset.shader_params_colourise,
+            &preset.shader_mod_amounts_colourise,
+        ),
+    };
+
+    Uniforms {
+        time,
+        resolution: layout::shader_resolution(led_layout),
+        pot6: colour_channels[0],
+        pot7: colour_channels[1],
+        pot8: colour_channels[2],
+        mix: mix_info,
+        buttons,
+    }
+}
+
+/// Apply envelope modulation to shader params, matching the same iteration
+/// order as gui::set_shader_widgets so mod_slider_ix lines up with mod_amounts.
+pub fn apply_shader_modulation(
+    shader: Shader,
+    params: &mut ShaderParams,
+    mod_slider_ix: &mut usize,
+    mod_amounts: &[f32],
+    envelope: f32,
+) {
+    let p = params.params_mut(shader);
+    for ix in 0..p.param_count() {
+        let ParamMut { kind, .. } = p.param_mut(ix);
+        match kind {
+            ParamKindMut::F32 { value, max } => {
+                if let Some(&mod_amt) = mod_amounts.get(*mod_slider_ix) {
+                    let offset = (envelope * mod_amt) - (mod_amt / 2.0);
+                    *value = (*value + offset).max(0.0).min(max);
+                }
+                *mod_slider_ix += 1;
+            }
+            ParamKindMut::F32Range { value, min, max } => {
+                if let Some(&mod_amt) = mod_amounts.get(*mod_slider_ix) {
+                    let offset = (envelope * mod_amt) - (mod_amt / 2.0);
+                    *value = (*value + offset).max(min).min(max);
+                }
+                *mod_slider_ix += 1;
+            }
+            ParamKindMut::Usize { .. } | ParamKindMut::Bool(_) | ParamKindMut::Select { .. } => {}
+        }
+    }
+}
+
+/// Shade every LED, returning the message of any panic reported by the shader library.
+pub fn render_preset_graph(
+    shader: ShaderFns,
+    led_shader_inputs: &[CachedLedShaderInput],
+    uniforms: &Uniforms,
+    previous_led_colors: &[LinSrgb],
+    output: &mut [LinSrgb],
+) -> Result<(), String> {
+    let Some(batch) = shader.batch else {
+        return output
+            .par_iter_mut()
+            .zip(led_shader_inputs.par_iter())
+            .zip(previous_led_colors.par_iter())
+            .try_for_each(|((color, led_input), &last_color)| {
+                let vertex = Vertex {
+                    position: led_input.position,
+                    light: led_input.light,
+                    last_color,
+                };
+                *color = (shader.vertex)(vertex, uniforms)?;
+                Ok(())
+            });
+    };
+    output
+        .par_chunks_mut(LED_SHADER_CHUNK_LEN)
+        .zip(led_shader_inputs.par_chunks(LED_SHADER_CHUNK_LEN))
+        .zip(previous_led_colors.par_chunks(LED_SHADER_CHUNK_LEN))
+        .try_for_each(|((colors, led_inputs), last_colors)| {
+            batch(led_inputs, last_colors, uniforms, colors)
+        })
+}
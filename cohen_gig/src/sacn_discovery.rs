@@ -0,0 +1,253 @@
+//! E1.31 universe discovery.
+//!
+//! Sources periodically announce every universe they are sending on a dedicated discovery
+//! universe, split across pages of up to 512 universes. Listening to these announcements shows
+//! which other sources are on the network, which is the easiest way to spot two sources sending
+//! the same universe.
+
+use crate::sacn_receiver::{universe_multicast_addr, SACN_PORT};
+use sacn::packet::{
+    AcnRootLayerProtocol, E131RootLayer, E131RootLayerData, UniverseDiscoveryPacketFramingLayer,
+    UniverseDiscoveryPacketUniverseDiscoveryLayer,
+};
+use std::collections::BTreeMap;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// The universe on which discovery packets are sent.
+pub const DISCOVERY_UNIVERSE: u16 = 64214;
+/// How often E1.31 asks sources to announce their universes.
+pub const DISCOVERY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The most universes listed in a single discovery packet.
+const UNIVERSES_PER_PAGE: usize = 512;
+/// Sources that miss three announcements in a row are considered gone.
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Announces our output universes.
+pub struct DiscoverySender {
+    socket: UdpSocket,
+    destinations: Vec<SocketAddr>,
+    cid: Uuid,
+    name: String,
+    last_sent_at: Option<Instant>,
+}
+
+/// A source seen announcing its universes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredSource {
+    pub cid: Uuid,
+    pub name: String,
+    /// Every universe across all pages received so far, in ascending order.
+    pub universes: Vec<u16>,
+    pub last_seen: Instant,
+}
+
+/// Tracks the sources announcing on the discovery universe.
+#[derive(Debug, Default)]
+pub struct DiscoveryBrowser {
+    sources: BTreeMap<Uuid, BrowsedSource>,
+}
+
+#[derive(Debug)]
+struct BrowsedSource {
+    name: String,
+    pages: BTreeMap<u8, Vec<u16>>,
+    last_seen: Instant,
+}
+
+impl DiscoverySender {
+    /// Send to the discovery multicast group from the given interface.
+    ///
+    /// `cid` and `name` should be those of the source sending the data, so that receivers don't
+    /// list discovery as a source of its own.
+    pub fn multicast(cid: Uuid, name: &str, interface_ip: Ipv4Addr) -> io::Result<Self> {
+        let destination = SocketAddr::new(discovery_multicast_addr().into(), SACN_PORT);
+        Self::with_destinations(cid, name, interface_ip, vec![destination])
+    }
+
+    /// Send to each of the given addresses, e.g. a receiver on localhost.
+    pub fn with_destinations(
+        cid: Uuid,
+        name: &str,
+        interface_ip: Ipv4Addr,
+        destinations: Vec<SocketAddr>,
+    ) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddr::new(interface_ip.into(), 0))?;
+        Ok(Self {
+            socket,
+            destinations,
+            cid,
+            name: name.to_string(),
+            last_sent_at: None,
+        })
+    }
+
+    /// Announce the given universes, unless they were announced less than an interval ago.
+    ///
+    /// Returns whether anything was sent.
+    pub fn send_if_due(&mut self, universes: &[u16], now: Instant) -> io::Result<bool> {
+        let due = self
+            .last_sent_at
+            .is_none_or(|sent_at| now.saturating_duration_since(sent_at) >= DISCOVERY_INTERVAL);
+        if !due {
+            return Ok(false);
+        }
+        // Retry on the next interval rather than every frame if the network is down.
+        self.last_sent_at = Some(now);
+        for packet in build_discovery_packets(self.cid, &self.name, universes) {
+            let bytes = packet.pack_alloc().unwrap();
+            for destination in &self.destinations {
+                self.socket.send_to(&bytes, destination)?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl DiscoveryBrowser {
+    /// Record a discovery packet from the source with the given CID.
+    pub fn handle_packet(
+        &mut self,
+        cid: Uuid,
+        discovery: &UniverseDiscoveryPacketFramingLayer,
+        now: Instant,
+    ) {
+        let layer = &discovery.data;
+        let source = self.sources.entry(cid).or_insert_with(|| BrowsedSource {
+            name: String::new(),
+            pages: BTreeMap::new(),
+            last_seen: now,
+        });
+        source.name = discovery.source_name.to_string();
+        source.pages.retain(|&page, _| page <= layer.last_page);
+        source.pages.insert(layer.page, layer.universes.to_vec());
+        source.last_seen = now;
+    }
+
+    /// Forget sources that have stopped announcing.
+    pub fn expire(&mut self, now: Instant) {
+        self.sources
+            .retain(|_, source| now.saturating_duration_since(source.last_seen) < SOURCE_TIMEOUT);
+    }
+
+    /// Every live source, ordered by name.
+    pub fn sources(&self) -> Vec<DiscoveredSource> {
+        let mut sources: Vec<_> = self
+            .sources
+            .iter()
+            .map(|(&cid, source)| {
+                let mut universes: Vec<u16> = source.pages.values().flatten().copied().collect();
+                universes.sort_unstable();
+                universes.dedup();
+                DiscoveredSource {
+                    cid,
+                    name: source.name.clone(),
+                    universes,
+                    last_seen: source.last_seen,
+                }
+            })
+            .collect();
+        sources.sort_by(|a, b| a.name.cmp(&b.name).then(a.cid.cmp(&b.cid)));
+        sources
+    }
+}
+
+/// The multicast group of the discovery universe.
+pub fn discovery_multicast_addr() -> Ipv4Addr {
+    universe_multicast_addr(DISCOVERY_UNIVERSE)
+}
+
+/// Split the given universes into as many discovery pages as needed.
+pub fn build_discovery_packets(
+    cid: Uuid,
+    source_name: &str,
+    universes: &[u16],
+) -> Vec<AcnRootLayerProtocol<'static>> {
+    let mut universes = universes.to_vec();
+    // Receivers rely on the list being sorted.
+    universes.sort_unstable();
+    universes.dedup();
+    let pages: Vec<&[u16]> = if universes.is_empty() {
+        vec![&[]]
+    } else {
+        universes.chunks(UNIVERSES_PER_PAGE).collect()
+    };
+    let last_page = (pages.len() - 1) as u8;
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(page, universes)| AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid,
+                data: E131RootLayerData::UniverseDiscoveryPacket(
+                    UniverseDiscoveryPacketFramingLayer {
+                        source_name: source_name.to_string().into(),
+                        data: UniverseDiscoveryPacketUniverseDiscoveryLayer {
+                            page: page as u8,
+                            last_page,
+                            universes: universes.to_vec().into(),
+                        },
+                    },
+                ),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_discovery_packets, DiscoveryBrowser, SOURCE_TIMEOUT};
+    use sacn::packet::E131RootLayerData;
+    use std::time::Instant;
+    use uuid::Uuid;
+
+    #[test]
+    fn universes_are_sorted_and_split_into_pages() {
+        let mut universes: Vec<u16> = (1..=600).collect();
+        universes.reverse();
+        let packets = build_discovery_packets(Uuid::new_v4(), "Cohen Pre-vis", &universes);
+
+        let pages: Vec<_> = packets
+            .iter()
+            .map(|packet| match &packet.pdu.data {
+                E131RootLayerData::UniverseDiscoveryPacket(discovery) => (
+                    discovery.data.page,
+                    discovery.data.last_page,
+                    discovery.data.universes.to_vec(),
+                ),
+                _ => panic!("expected a discovery packet"),
+            })
+            .collect();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].0, 0);
+        assert_eq!(pages[0].1, 1);
+        assert_eq!(pages[0].2, (1..=512).collect::<Vec<u16>>());
+        assert_eq!(pages[1].0, 1);
+        assert_eq!(pages[1].2, (513..=600).collect::<Vec<u16>>());
+    }
+
+    #[test]
+    fn browser_joins_pages_and_expires_silent_sources() {
+        let cid = Uuid::new_v4();
+        let universes: Vec<u16> = (1..=600).collect();
+        let now = Instant::now();
+        let mut browser = DiscoveryBrowser::default();
+        for packet in build_discovery_packets(cid, "House Desk", &universes) {
+            if let E131RootLayerData::UniverseDiscoveryPacket(discovery) = &packet.pdu.data {
+                browser.handle_packet(cid, discovery, now);
+            }
+        }
+
+        let sources = browser.sources();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].cid, cid);
+        assert_eq!(sources[0].name, "House Desk");
+        assert_eq!(sources[0].universes, universes);
+
+        browser.expire(now + SOURCE_TIMEOUT);
+        assert!(browser.sources().is_empty());
+    }
+}
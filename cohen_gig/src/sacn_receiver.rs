@@ -0,0 +1,320 @@
+//! A minimal sACN (E1.31) receiver, used to merge a lighting console into our output.
+//!
+//! Listens for unicast packets (including those sent to localhost) and joins the multicast group
+//! of each requested universe. Every source is tracked separately and, as E1.31 receivers are
+//! expected to do, the highest priority live source is used for each universe. Universe discovery
+//! packets are collected too, so that other sources on the network can be listed.
+
+use crate::sacn_discovery::{self, DiscoveredSource, DiscoveryBrowser};
+use sacn::packet::{AcnRootLayerProtocol, E131RootLayerData};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::collections::HashMap;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// The UDP port on which sACN receivers listen.
+pub const SACN_PORT: u16 = 5568;
+
+/// Sources that have been silent for this long are considered lost, as per E1.31.
+const SOURCE_TIMEOUT: Duration = Duration::from_millis(2500);
+/// Comfortably larger than the largest E1.31 data packet.
+const MAX_PACKET_LEN: usize = 1500;
+
+/// The latest data received from a single source on a single universe.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceivedUniverse {
+    pub source_name: String,
+    pub priority: u8,
+    /// Start-code-prefixed slot data.
+    pub payload: Vec<u8>,
+    pub received_at: Instant,
+}
+
+pub struct SacnReceiver {
+    socket: UdpSocket,
+    universes: Vec<u16>,
+    /// Packets from this CID are our own output and are ignored. Other sources sharing our name
+    /// are still received.
+    ignored_cid: Uuid,
+    sources: HashMap<u16, HashMap<Uuid, ReceivedUniverse>>,
+    discovery: DiscoveryBrowser,
+}
+
+impl SacnReceiver {
+    /// Bind a non-blocking receiver for the given universes.
+    ///
+    /// The address is bound for reuse so that other sACN receivers on this machine (a visualiser,
+    /// `sacn_view`, a second instance) can listen on the same port.
+    pub fn bind(addr: SocketAddr, universes: &[u16], ignored_cid: Uuid) -> io::Result<Self> {
+        let socket = bind_reusable(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            universes: universes.to_vec(),
+            ignored_cid,
+            sources: HashMap::new(),
+            discovery: DiscoveryBrowser::default(),
+        })
+    }
+
+    /// Join the multicast group of every universe on the given interface.
+    pub fn join_multicast(&self, interface_ip: Ipv4Addr) -> io::Result<()> {
+        for &universe in &self.universes {
+            self.socket
+                .join_multicast_v4(&universe_multicast_addr(universe), &interface_ip)?;
+        }
+        Ok(())
+    }
+
+    /// Join the universe discovery multicast group on the given interface.
+    pub fn join_discovery_multicast(&self, interface_ip: Ipv4Addr) -> io::Result<()> {
+        self.socket
+            .join_multicast_v4(&sacn_discovery::discovery_multicast_addr(), &interface_ip)
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Read every packet that has arrived since the last poll and drop any lost sources.
+    pub fn poll(&mut self, now: Instant) -> io::Result<()> {
+        let mut buffer = [0u8; MAX_PACKET_LEN];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, _)) => self.handle_packet(&buffer[..len], now),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            }
+        }
+        for sources in self.sources.values_mut() {
+            sources.retain(|_, source| {
+                now.saturating_duration_since(source.received_at) < SOURCE_TIMEOUT
+            });
+        }
+        self.sources.retain(|_, sources| !sources.is_empty());
+        self.discovery.expire(now);
+        Ok(())
+    }
+
+    /// The highest priority source for the given universe, preferring the most recent on a tie.
+    pub fn universe(&self, universe: u16) -> Option<&ReceivedUniverse> {
+        self.sources
+            .get(&universe)?
+            .values()
+            .max_by_key(|source| (source.priority, source.received_at))
+    }
+
+    /// The universes that currently have at least one live source, in ascending order.
+    pub fn active_universes(&self) -> Vec<u16> {
+        let mut universes: Vec<_> = self.sources.keys().copied().collect();
+        universes.sort_unstable();
+        universes
+    }
+
+    /// Other sources announcing their universes, ordered by name.
+    pub fn discovered_sources(&self) -> Vec<DiscoveredSource> {
+        self.discovery.sources()
+    }
+
+    fn handle_packet(&mut self, bytes: &[u8], now: Instant) {
+        let Ok(packet) = AcnRootLayerProtocol::parse(bytes) else {
+            return;
+        };
+        let cid = packet.pdu.cid;
+        if cid == self.ignored_cid {
+            return;
+        }
+        let data = match packet.pdu.data {
+            E131RootLayerData::DataPacket(data) => data,
+            E131RootLayerData::UniverseDiscoveryPacket(discovery) => {
+                self.discovery.handle_packet(cid, &discovery, now);
+                return;
+            }
+            _ => return,
+        };
+        if !self.universes.contains(&data.universe) || data.preview_data {
+            return;
+        }
+
+        let sources = self.sources.entry(data.universe).or_default();
+        if data.stream_terminated {
+            sources.remove(&cid);
+            return;
+        }
+        let payload = data.data.property_values.to_vec();
+        // Only level data, i.e. the null start code, is merged.
+        if payload.first() != Some(&0) {
+            return;
+        }
+        sources.insert(
+            cid,
+            ReceivedUniverse {
+                source_name: data.source_name.to_string(),
+                priority: data.priority,
+                payload,
+                received_at: now,
+            },
+        );
+    }
+}
+
+/// Bind a UDP socket with `SO_REUSEADDR` (and `SO_REUSEPORT` on unix) set.
+///
+/// Multicast packets reach every socket bound this way, while unicast packets are only delivered
+/// to one of them.
+fn bind_reusable(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.bind(&addr.into())?;
+    Ok(socket.into())
+}
+
+/// The multicast group on which the given universe is sent.
+pub fn universe_multicast_addr(universe: u16) -> Ipv4Addr {
+    let [hi, lo] = universe.to_be_bytes();
+    Ipv4Addr::new(239, 255, hi, lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{universe_multicast_addr, SacnReceiver};
+    use crate::sacn_discovery::DiscoverySender;
+    use crate::sacn_sender::build_data_packet;
+    use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    /// The CID our own output is sent with.
+    const OUR_CID: Uuid = Uuid::from_u128(0xc0de);
+
+    /// Stands in for a console sending to us over localhost.
+    fn send(to: SocketAddr, name: &str, universe: u16, payload: &[u8], priority: u8) {
+        send_as(Uuid::new_v4(), to, name, universe, payload, priority);
+    }
+
+    fn send_as(cid: Uuid, to: SocketAddr, name: &str, universe: u16, payload: &[u8], priority: u8) {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let packet = build_data_packet(cid, name, 0, universe, payload, priority, 0);
+        socket.send_to(&packet.pack_alloc().unwrap(), to).unwrap();
+    }
+
+    fn poll_until(receiver: &mut SacnReceiver, done: impl Fn(&SacnReceiver) -> bool) {
+        for _ in 0..200 {
+            receiver.poll(Instant::now()).unwrap();
+            if done(receiver) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn universe_multicast_addr_splits_universe() {
+        assert_eq!(
+            universe_multicast_addr(0x0102),
+            Ipv4Addr::new(239, 255, 1, 2)
+        );
+    }
+
+    #[test]
+    fn receivers_share_the_port() {
+        let bind = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let first = SacnReceiver::bind(bind, &[1], OUR_CID).unwrap();
+        let shared = first.local_addr().unwrap();
+
+        let second = SacnReceiver::bind(shared, &[1], OUR_CID)
+            .expect("a second receiver should bind the same port");
+        assert_eq!(second.local_addr().unwrap(), shared);
+    }
+
+    #[test]
+    fn receives_listened_universes_over_localhost() {
+        let bind = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let mut receiver = SacnReceiver::bind(bind, &[1, 2, 4], OUR_CID).unwrap();
+        let addr = receiver.local_addr().unwrap();
+
+        send(addr, "Desk", 1, &[0, 10, 20], 100);
+        send_as(OUR_CID, addr, "Cohen Pre-vis", 2, &[0, 99], 100);
+        send(addr, "Desk", 3, &[0, 99], 100);
+        // Another instance sharing our name is still a source.
+        send(addr, "Cohen Pre-vis", 4, &[0, 55], 100);
+        // Only the highest priority source is used.
+        send(addr, "Backup Desk", 1, &[0, 30, 40], 50);
+        poll_until(&mut receiver, |r| {
+            r.universe(1).map(|u| u.source_name.as_str()) == Some("Desk") && r.universe(4).is_some()
+        });
+
+        let received = receiver.universe(1).expect("universe 1 should be received");
+        assert_eq!(received.payload, vec![0, 10, 20]);
+        assert_eq!(received.priority, 100);
+        // Our own output and unlistened universes are ignored.
+        assert!(receiver.universe(2).is_none());
+        assert!(receiver.universe(3).is_none());
+        assert_eq!(receiver.universe(4).unwrap().payload, vec![0, 55]);
+        assert_eq!(receiver.active_universes(), vec![1, 4]);
+    }
+
+    #[test]
+    fn lists_sources_announcing_their_universes() {
+        let bind = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let mut receiver = SacnReceiver::bind(bind, &[], OUR_CID).unwrap();
+        let addr = receiver.local_addr().unwrap();
+        let now = Instant::now();
+
+        let mut desk = DiscoverySender::with_destinations(
+            Uuid::new_v4(),
+            "House Desk",
+            Ipv4Addr::LOCALHOST,
+            vec![addr],
+        )
+        .unwrap();
+        let mut ours = DiscoverySender::with_destinations(
+            OUR_CID,
+            "Cohen Pre-vis",
+            Ipv4Addr::LOCALHOST,
+            vec![addr],
+        )
+        .unwrap();
+        let mut namesake = DiscoverySender::with_destinations(
+            Uuid::new_v4(),
+            "Cohen Pre-vis",
+            Ipv4Addr::LOCALHOST,
+            vec![addr],
+        )
+        .unwrap();
+        assert!(desk.send_if_due(&[3, 1, 2], now).unwrap());
+        assert!(ours.send_if_due(&[1], now).unwrap());
+        assert!(namesake.send_if_due(&[7], now).unwrap());
+        // Announcements are only repeated once per interval.
+        assert!(!desk.send_if_due(&[3, 1, 2], now).unwrap());
+        poll_until(&mut receiver, |r| r.discovered_sources().len() >= 2);
+
+        // Only our own CID is left out; another instance with our name is listed.
+        let sources = receiver.discovered_sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].name, "Cohen Pre-vis");
+        assert_eq!(sources[0].universes, vec![7]);
+        assert_eq!(sources[1].name, "House Desk");
+        assert_eq!(sources[1].universes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn lost_sources_time_out() {
+        let bind = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let mut receiver = SacnReceiver::bind(bind, &[1], OUR_CID).unwrap();
+        let addr = receiver.local_addr().unwrap();
+
+        send(addr, "Desk", 1, &[0, 10], 100);
+        poll_until(&mut receiver, |r| r.universe(1).is_some());
+        assert!(receiver.universe(1).is_some());
+
+        receiver
+            .poll(Instant::now() + Duration::from_secs(3))
+            .unwrap();
+        assert!(receiver.universe(1).is_none());
+    }
+}
@@ -1,60 +1,120 @@
 use sacn::packet::{
     AcnRootLayerProtocol, DataPacketDmpLayer, DataPacketFramingLayer, E131RootLayer,
-    E131RootLayerData, E131_DEFAULT_PRIORITY,
+    E131RootLayerData, SynchronizationPacketFramingLayer,
 };
 use std::collections::HashMap;
 use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
 use uuid::Uuid;
 
 const LOCALHOST_SACN_DESTINATION_PORT: u16 = 5568;
+/// E1.31 asks sources to send three stream-terminated packets, as any one of them may be lost.
+const STREAM_TERMINATION_PACKETS: usize = 3;
 
 pub struct LocalhostSacnSender {
     socket: UdpSocket,
+    destination: SocketAddr,
     cid: Uuid,
     name: String,
     sequences: HashMap<u16, u8>,
+    /// Sync packets are numbered apart from data packets, even when the sync universe also
+    /// carries data.
+    sync_sequence: u8,
 }
 
 impl LocalhostSacnSender {
-    pub fn new(name: &str) -> std::io::Result<Self> {
+    /// Send as the source identified by `cid` and `name`, which discovery should announce too.
+    pub fn new(cid: Uuid, name: &str) -> std::io::Result<Self> {
+        let destination =
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), LOCALHOST_SACN_DESTINATION_PORT);
+        Self::with_destination(cid, name, destination)
+    }
+
+    /// Send to the given address rather than the standard sACN port on localhost.
+    pub fn with_destination(
+        cid: Uuid,
+        name: &str,
+        destination: SocketAddr,
+    ) -> std::io::Result<Self> {
         let bind_addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
         let socket = UdpSocket::bind(bind_addr)?;
         Ok(Self {
             socket,
-            cid: Uuid::new_v4(),
+            destination,
+            cid,
             name: name.to_string(),
             sequences: HashMap::new(),
+            sync_sequence: 0,
         })
     }
 
+    /// Send a data packet. A non-zero `synchronization_address` asks receivers to hold the data
+    /// until a sync packet arrives on that universe.
     pub fn send_property_values(
         &mut self,
         universe: u16,
         property_values: &[u8],
+        priority: u8,
+        synchronization_address: u16,
     ) -> std::io::Result<()> {
         let sequence = self.sequences.entry(universe).or_insert(0);
-        let packet = build_data_packet(self.cid, &self.name, *sequence, universe, property_values);
-        let dest = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), LOCALHOST_SACN_DESTINATION_PORT);
-        self.socket.send_to(&packet.pack_alloc().unwrap(), dest)?;
+        let packet = build_data_packet(
+            self.cid,
+            &self.name,
+            *sequence,
+            universe,
+            property_values,
+            priority,
+            synchronization_address,
+        );
+        self.socket
+            .send_to(&packet.pack_alloc().unwrap(), self.destination)?;
         *sequence = sequence.wrapping_add(1);
         Ok(())
     }
+
+    /// Tell receivers that we have stopped sending the universe, so they release its last frame
+    /// straight away rather than holding it until they time out.
+    pub fn terminate_stream(&mut self, universe: u16, priority: u8) -> std::io::Result<()> {
+        for _ in 0..STREAM_TERMINATION_PACKETS {
+            let sequence = self.sequences.entry(universe).or_insert(0);
+            let mut packet =
+                build_data_packet(self.cid, &self.name, *sequence, universe, &[0], priority, 0);
+            if let E131RootLayerData::DataPacket(ref mut data) = packet.pdu.data {
+                data.stream_terminated = true;
+            }
+            self.socket
+                .send_to(&packet.pack_alloc().unwrap(), self.destination)?;
+            *sequence = sequence.wrapping_add(1);
+        }
+        Ok(())
+    }
+
+    /// Release all data packets that were tagged with `synchronization_address`.
+    pub fn send_sync_packet(&mut self, synchronization_address: u16) -> std::io::Result<()> {
+        let packet = build_sync_packet(self.cid, self.sync_sequence, synchronization_address);
+        self.socket
+            .send_to(&packet.pack_alloc().unwrap(), self.destination)?;
+        self.sync_sequence = self.sync_sequence.wrapping_add(1);
+        Ok(())
+    }
 }
 
-fn build_data_packet(
+pub(crate) fn build_data_packet(
     cid: Uuid,
     source_name: &str,
     sequence_number: u8,
     universe: u16,
     property_values: &[u8],
+    priority: u8,
+    synchronization_address: u16,
 ) -> AcnRootLayerProtocol<'static> {
     AcnRootLayerProtocol {
         pdu: E131RootLayer {
             cid,
             data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
                 source_name: source_name.to_string().into(),
-                priority: E131_DEFAULT_PRIORITY,
-                synchronization_address: 0,
+                priority,
+                synchronization_address,
                 sequence_number,
                 preview_data: false,
                 stream_terminated: false,
@@ -68,10 +128,28 @@ fn build_data_packet(
     }
 }
 
+fn build_sync_packet(
+    cid: Uuid,
+    sequence_number: u8,
+    synchronization_address: u16,
+) -> AcnRootLayerProtocol<'static> {
+    AcnRootLayerProtocol {
+        pdu: E131RootLayer {
+            cid,
+            data: E131RootLayerData::SynchronizationPacket(SynchronizationPacketFramingLayer {
+                sequence_number,
+                synchronization_address,
+            }),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::build_data_packet;
-    use sacn::packet::AcnRootLayerProtocol;
+    use super::{build_data_packet, build_sync_packet, LocalhostSacnSender};
+    use sacn::packet::{AcnRootLayerProtocol, E131RootLayerData, E131_DEFAULT_PRIORITY};
+    use std::net::{Ipv4Addr, UdpSocket};
+    use std::time::Duration;
     use uuid::Uuid;
 
     #[test]
@@ -82,10 +160,136 @@ mod tests {
             12,
             7,
             &[0, 11, 22, 33, 44, 55],
+            E131_DEFAULT_PRIORITY,
+            0,
         );
         let packed = packet.pack_alloc().expect("packet should pack");
         let parsed = AcnRootLayerProtocol::parse(&packed).expect("packet should parse");
 
         assert_eq!(parsed, packet);
     }
+
+    #[test]
+    fn build_data_packet_carries_synchronization_address() {
+        let packet = build_data_packet(
+            Uuid::new_v4(),
+            "Cohen Test",
+            3,
+            7,
+            &[0, 1, 2, 3],
+            E131_DEFAULT_PRIORITY,
+            64,
+        );
+        let packed = packet.pack_alloc().expect("packet should pack");
+        let parsed = AcnRootLayerProtocol::parse(&packed).expect("packet should parse");
+
+        match parsed.pdu.data {
+            E131RootLayerData::DataPacket(data) => assert_eq!(data.synchronization_address, 64),
+            _ => panic!("expected a data packet"),
+        }
+    }
+
+    #[test]
+    fn build_data_packet_carries_priority() {
+        let packet = build_data_packet(Uuid::new_v4(), "Cohen Test", 0, 7, &[0, 1], 150, 0);
+        let packed = packet.pack_alloc().expect("packet should pack");
+        let parsed = AcnRootLayerProtocol::parse(&packed).expect("packet should parse");
+
+        match parsed.pdu.data {
+            E131RootLayerData::DataPacket(data) => assert_eq!(data.priority, 150),
+            _ => panic!("expected a data packet"),
+        }
+    }
+
+    #[test]
+    fn build_sync_packet_round_trips() {
+        let packet = build_sync_packet(Uuid::new_v4(), 9, 64);
+        let packed = packet.pack_alloc().expect("packet should pack");
+        let parsed = AcnRootLayerProtocol::parse(&packed).expect("packet should parse");
+
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn sync_packets_leave_data_sequence_untouched() {
+        let listener = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let mut sender = LocalhostSacnSender::with_destination(
+            Uuid::new_v4(),
+            "Cohen Test",
+            listener.local_addr().unwrap(),
+        )
+        .unwrap();
+
+        for _ in 0..2 {
+            sender
+                .send_property_values(7, &[0, 255], E131_DEFAULT_PRIORITY, 7)
+                .unwrap();
+            sender.send_sync_packet(7).unwrap();
+        }
+
+        let mut buffer = [0u8; 1024];
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let len = listener.recv(&mut buffer).unwrap();
+            match AcnRootLayerProtocol::parse(&buffer[..len])
+                .unwrap()
+                .pdu
+                .data
+            {
+                E131RootLayerData::DataPacket(data) => {
+                    received.push(("data", data.sequence_number))
+                }
+                E131RootLayerData::SynchronizationPacket(sync) => {
+                    received.push(("sync", sync.sequence_number))
+                }
+                _ => panic!("expected a data or sync packet"),
+            }
+        }
+        assert_eq!(
+            received,
+            vec![("data", 0), ("sync", 0), ("data", 1), ("sync", 1)]
+        );
+    }
+
+    #[test]
+    fn terminate_stream_sends_three_terminated_packets() {
+        let listener = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let mut sender = LocalhostSacnSender::with_destination(
+            Uuid::new_v4(),
+            "Cohen Test",
+            listener.local_addr().unwrap(),
+        )
+        .unwrap();
+
+        sender
+            .send_property_values(7, &[0, 255, 255], E131_DEFAULT_PRIORITY, 0)
+            .unwrap();
+        sender.terminate_stream(7, E131_DEFAULT_PRIORITY).unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let len = listener.recv(&mut buffer).unwrap();
+            match AcnRootLayerProtocol::parse(&buffer[..len])
+                .unwrap()
+                .pdu
+                .data
+            {
+                E131RootLayerData::DataPacket(data) => {
+                    received.push((data.universe, data.sequence_number, data.stream_terminated))
+                }
+                _ => panic!("expected a data packet"),
+            }
+        }
+        assert_eq!(
+            received,
+            vec![(7, 0, false), (7, 1, true), (7, 2, true), (7, 3, true)]
+        );
+    }
 }
@@ -1,25 +1,63 @@
-//! Items related to hotloading the shader crate.
+//! Items related to running the shader crate.
+//!
+//! The `shader` crate is always linked in and used until a hotloaded build is available. With the
+//! `hotload` feature, the crate is also watched, rebuilt and reloaded whenever its sources change.
+//! A failed build leaves the last good shader running.
+
+// Without hotloading, no shader is ever incoming.
+#![cfg_attr(not(feature = "hotload"), allow(dead_code))]
 
-use hotlib::BuildError;
 use nannou::prelude::*;
-use shader_shared::{Uniforms, Vertex};
+use shader_shared::{CachedLedShaderInput, MixingInfo, Uniforms, Vertex};
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "hotload")]
 use std::sync::mpsc;
 
+/// Swapped in for any shader that panics. Its params are set per slot by `MixSlot::make_safe`.
+const SAFE_SHADER: shader_shared::Shader = shader_shared::Shader::SolidRgbColour;
+
 /// Describes the result of the last incoming library.
 #[derive(Debug)]
 pub enum LastIncoming {
     Succeeded,
-    Failed(BuildError),
+    /// The build or load error.
+    Failed(String),
+    /// The library built but was refused, as its `shader_shared` types differ from ours.
+    Incompatible(String),
 }
 
 /// The current activity within the shader receiver.
 #[derive(Debug)]
 pub enum Activity<'a> {
     Incoming,
+    /// Shaders that panicked in the loaded library and have been swapped out.
+    Panicked(&'a [ShaderPanic]),
     LastIncoming(&'a LastIncoming),
 }
 
+/// A shader that panicked while rendering and has been swapped for a safe one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShaderPanic {
+    pub shader: shader_shared::Shader,
+    pub message: String,
+}
+
+/// One of the three shaders within a mix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MixSlot {
+    Left,
+    Right,
+    Colourise,
+}
+
+/// Isolates shaders that panic so that the rest of the mix keeps rendering.
+#[derive(Debug, Default)]
+pub struct ShaderGuard {
+    panics: Vec<ShaderPanic>,
+}
+
 /// A handle for receiving hotloading shader updates on the main thread.
+#[cfg(feature = "hotload")]
 pub struct ShaderReceiver {
     // For receiving notification of processing shaders.
     rx: mpsc::Receiver<Incoming>,
@@ -29,29 +67,78 @@ pub struct ShaderReceiver {
     last_timestamp: std::time::Instant,
     // The result of the last incoming shader.
     last_incoming: LastIncoming,
+    // Shaders that panicked in the current library, as reported by the LED worker.
+    panics: Vec<ShaderPanic>,
+}
+
+/// Never receives a shader, as the linked-in shader is always used.
+#[cfg(not(feature = "hotload"))]
+pub struct ShaderReceiver {
+    last_timestamp: std::time::Instant,
+    last_incoming: LastIncoming,
+    panics: Vec<ShaderPanic>,
 }
 
 /// A loaded instance of the shader crate.
+#[cfg(feature = "hotload")]
 pub struct Shader {
     // The last successfully loaded shader library instance.
     lib: hotlib::TempLibrary,
 }
 
+/// A loaded instance of the shader crate, which can't exist without hotloading.
+#[cfg(not(feature = "hotload"))]
+pub enum Shader {}
+
 /// The function signature of the shader function.
-pub type ShaderFnPtr = fn(Vertex, &Uniforms) -> LinSrgb;
+///
+/// Both entry points catch their own panics and return the message instead, as a panic
+/// unwinding out of the hotloaded library can't be caught here.
+pub type ShaderFnPtr = fn(Vertex, &Uniforms) -> Result<LinSrgb, String>;
+
+/// The function signature of the batch shader function, which shades a chunk of LEDs given each
+/// LED's colour from the previous frame.
+pub type ShaderBatchFnPtr =
+    fn(&[CachedLedShaderInput], &[LinSrgb], &Uniforms, &mut [LinSrgb]) -> Result<(), String>;
+
+/// The entry points of a shader library.
+#[derive(Clone, Copy)]
+pub struct ShaderFns {
+    /// Shades a single LED.
+    pub vertex: ShaderFnPtr,
+    /// Shades a chunk of LEDs at a time, if the library exports it.
+    pub batch: Option<ShaderBatchFnPtr>,
+}
+
+/// The entry points of the shader crate as linked into this binary.
+pub const BUILTIN: ShaderFns = ShaderFns {
+    vertex: builtin,
+    batch: Some(builtin_batch),
+};
 
+#[cfg(feature = "hotload")]
 struct Incoming {
-    rx: mpsc::Receiver<Result<hotlib::TempLibrary, BuildError>>,
+    rx: mpsc::Receiver<Result<hotlib::TempLibrary, String>>,
 }
 
 impl ShaderReceiver {
-    /// Whether or not the shader is currently incoming. If not, whether the last incoming shader
-    /// built successfully or not.
+    /// Whether or not the shader is currently incoming. If not, whether any shaders have panicked
+    /// or otherwise whether the last incoming shader built successfully or not.
     pub fn activity(&self) -> Activity<'_> {
+        #[cfg(feature = "hotload")]
         if self.incoming.is_some() {
-            Activity::Incoming
-        } else {
-            Activity::LastIncoming(&self.last_incoming)
+            return Activity::Incoming;
+        }
+        if !self.panics.is_empty() {
+            return Activity::Panicked(&self.panics);
+        }
+        Activity::LastIncoming(&self.last_incoming)
+    }
+
+    /// Record the shaders that the LED worker has swapped out after they panicked.
+    pub fn set_panics(&mut self, panics: &[ShaderPanic]) {
+        if self.panics != panics {
+            self.panics = panics.to_vec();
         }
     }
 
@@ -67,6 +154,7 @@ impl ShaderReceiver {
     }
 
     /// Update the shader receiver to check for newly received shaders.
+    #[cfg(feature = "hotload")]
     pub fn update(&mut self) -> Option<Shader> {
         loop {
             // If we're already aware of an incoming lib, wait for it.
@@ -75,10 +163,16 @@ impl ShaderReceiver {
                 self.incoming = None;
                 self.last_timestamp = std::time::Instant::now();
                 match res {
-                    Ok(lib) => {
-                        self.last_incoming = LastIncoming::Succeeded;
-                        return Some(Shader::from(lib));
-                    }
+                    Ok(lib) => match check_abi_fingerprint(library_abi_fingerprint(&lib)) {
+                        Ok(()) => {
+                            self.last_incoming = LastIncoming::Succeeded;
+                            return Some(Shader::from(lib));
+                        }
+                        Err(err) => {
+                            self.last_incoming = LastIncoming::Incompatible(err);
+                            return None;
+                        }
+                    },
                     Err(err) => {
                         self.last_incoming = LastIncoming::Failed(err);
                         return None;
@@ -90,25 +184,75 @@ impl ShaderReceiver {
             self.incoming = Some(self.rx.try_iter().last()?);
         }
     }
+
+    /// Update the shader receiver to check for newly received shaders.
+    #[cfg(not(feature = "hotload"))]
+    pub fn update(&mut self) -> Option<Shader> {
+        None
+    }
 }
 
 impl Shader {
-    /// Load the shader function.
-    pub fn get_fn(&self) -> libloading::Symbol<'_, ShaderFnPtr> {
+    /// Load the shader functions. The batch function is optional, falling back to shading one LED
+    /// at a time.
+    #[cfg(feature = "hotload")]
+    pub fn get_fns(&self) -> ShaderFns {
         unsafe {
-            self.lib
-                .get("shader".as_bytes())
-                .expect("failed to load shader fn symbol")
+            let vertex = *self
+                .lib
+                .get::<ShaderFnPtr>("shader".as_bytes())
+                .expect("failed to load shader fn symbol");
+            let batch = self
+                .lib
+                .get::<ShaderBatchFnPtr>("shader_batch".as_bytes())
+                .ok()
+                .map(|symbol| *symbol);
+            ShaderFns { vertex, batch }
         }
     }
+
+    /// Load the shader functions.
+    #[cfg(not(feature = "hotload"))]
+    pub fn get_fns(&self) -> ShaderFns {
+        match *self {}
+    }
 }
 
+#[cfg(feature = "hotload")]
 impl From<hotlib::TempLibrary> for Shader {
     fn from(lib: hotlib::TempLibrary) -> Self {
         Shader { lib }
     }
 }
 
+/// Read the fingerprint exported by a loaded shader library, if it has one.
+#[cfg(feature = "hotload")]
+fn library_abi_fingerprint(lib: &hotlib::TempLibrary) -> Option<u64> {
+    unsafe {
+        let symbol = lib.get::<*const u64>(b"SHADER_ABI_FINGERPRINT").ok()?;
+        Some(**symbol)
+    }
+}
+
+/// Calling into a library built against different `shader_shared` types is undefined behaviour,
+/// e.g. after a field is added to `ShaderParams` while the app is running.
+fn check_abi_fingerprint(fingerprint: Option<u64>) -> Result<(), String> {
+    match fingerprint {
+        Some(fingerprint) if fingerprint == shader_shared::ABI_FINGERPRINT => Ok(()),
+        Some(fingerprint) => Err(format!(
+            "Shader library refused: it was built against different `shader_shared` types \
+             (fingerprint {:016x}, expected {:016x}). Restart the app to use it.",
+            fingerprint,
+            shader_shared::ABI_FINGERPRINT
+        )),
+        None => Err(
+            "Shader library refused: it has no `SHADER_ABI_FINGERPRINT`. Restart the app to use it."
+                .to_string(),
+        ),
+    }
+}
+
+#[cfg(feature = "hotload")]
 fn shader_toml_path() -> std::path::PathBuf {
     let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
     let workspace_dir = path.parent().expect("could not find workspace dir");
@@ -121,6 +265,7 @@ fn shader_toml_path() -> std::path::PathBuf {
 /// Create the shader watch and run it on a separate thread.
 ///
 /// Returns the current state of the library and a handle for receiving shader updates.
+#[cfg(feature = "hotload")]
 pub fn spawn_watch() -> ShaderReceiver {
     let (incoming_tx, rx) = mpsc::channel();
 
@@ -138,7 +283,12 @@ pub fn spawn_watch() -> ShaderReceiver {
             // Attempt to build the library and send the result.
             let res = pkg
                 .build()
-                .map(|build| build.load().expect("failed to load shader library"));
+                .map_err(|err| format!("{}", err))
+                .and_then(|build| {
+                    build
+                        .load()
+                        .map_err(|err| format!("failed to load shader library: {}", err))
+                });
             result_tx.send(res).ok();
         }
 
@@ -168,11 +318,254 @@ pub fn spawn_watch() -> ShaderReceiver {
         incoming,
         last_timestamp,
         last_incoming,
+        panics: Vec::new(),
+    }
+}
+
+/// The linked-in shader is used throughout, so there is nothing to watch.
+#[cfg(not(feature = "hotload"))]
+pub fn spawn_watch() -> ShaderReceiver {
+    ShaderReceiver {
+        last_timestamp: std::time::Instant::now(),
+        last_incoming: LastIncoming::Succeeded,
+        panics: Vec::new(),
     }
 }
 
-// A function that matches the `ShaderFnPtr` that can be used as a fallback while the dylib is
-// building and loading for the first time.
-pub fn black(_: Vertex, _: &Uniforms) -> LinSrgb {
-    lin_srgb(0.0, 0.0, 0.0)
+/// The shader crate as linked into this binary.
+///
+/// Used while the hotloaded library is building and loading for the first time, and whenever
+/// no build has succeeded yet, so the output never drops to black on a bad build.
+pub fn builtin(v: Vertex, uniforms: &Uniforms) -> Result<LinSrgb, String> {
+    ::shader::shader(v, uniforms)
+}
+
+/// The batch entry point of the shader crate as linked into this binary.
+pub fn builtin_batch(
+    inputs: &[CachedLedShaderInput],
+    last_colors: &[LinSrgb],
+    uniforms: &Uniforms,
+    output: &mut [LinSrgb],
+) -> Result<(), String> {
+    ::shader::shader_batch(inputs, last_colors, uniforms, output)
+}
+
+impl MixSlot {
+    pub const ALL: [Self; 3] = [MixSlot::Left, MixSlot::Right, MixSlot::Colourise];
+
+    /// The shader in this slot of the mix.
+    pub fn shader(&self, mix: &MixingInfo) -> shader_shared::Shader {
+        match *self {
+            MixSlot::Left => mix.left,
+            MixSlot::Right => mix.right,
+            MixSlot::Colourise => mix.colourise,
+        }
+    }
+
+    /// Swap this slot's shader for a solid colour that leaves the rest of the mix as it is: black
+    /// for the left and right, white for colourise.
+    pub fn make_safe(&self, mix: &mut MixingInfo) {
+        let (shader, params, level) = match *self {
+            MixSlot::Left => (&mut mix.left, &mut mix.params_left, 0.0),
+            MixSlot::Right => (&mut mix.right, &mut mix.params_right, 0.0),
+            MixSlot::Colourise => (&mut mix.colourise, &mut mix.params_colourise, 1.0),
+        };
+        *shader = SAFE_SHADER;
+        params.solid_rgb_colour = shader_shared::SolidRgbColour {
+            red: level,
+            green: level,
+            blue: level,
+        };
+    }
+}
+
+impl ShaderGuard {
+    /// Shaders that have panicked since the guard was last cleared.
+    pub fn panics(&self) -> &[ShaderPanic] {
+        &self.panics
+    }
+
+    /// Give every shader another chance, e.g. once a new build has been loaded.
+    pub fn clear(&mut self) {
+        self.panics.clear();
+    }
+
+    /// Render a frame of `uniforms` with `render`.
+    ///
+    /// Slots holding a shader that has already panicked are swapped for a safe shader. If the
+    /// frame panics, or `render` returns a panic reported by the shader library, each slot is
+    /// rendered alone to find the shaders responsible, which are recorded and swapped out before
+    /// rendering once more. Anything else that panics, such as the blend itself, leaves the
+    /// output as it was.
+    pub fn render(
+        &mut self,
+        uniforms: &Uniforms,
+        mut render: impl FnMut(&Uniforms) -> Result<(), String>,
+    ) {
+        let swapped;
+        let uniforms = match self.swap_panicked(uniforms) {
+            Some(safe) => {
+                swapped = safe;
+                &swapped
+            }
+            None => uniforms,
+        };
+        if try_render(&mut render, uniforms).is_ok() {
+            return;
+        }
+
+        for slot in MixSlot::ALL {
+            let shader = slot.shader(&uniforms.mix);
+            if shader == SAFE_SHADER || self.panics.iter().any(|p| p.shader == shader) {
+                continue;
+            }
+            let mut alone = uniforms.clone();
+            for other in MixSlot::ALL.into_iter().filter(|&other| other != slot) {
+                other.make_safe(&mut alone.mix);
+            }
+            if let Err(message) = try_render(&mut render, &alone) {
+                self.panics.push(ShaderPanic { shader, message });
+            }
+        }
+
+        if let Some(safe) = self.swap_panicked(uniforms) {
+            try_render(&mut render, &safe).ok();
+        }
+    }
+
+    /// A copy of `uniforms` with any panicked shaders swapped out, if there are any to swap.
+    fn swap_panicked(&self, uniforms: &Uniforms) -> Option<Uniforms> {
+        let panicked = |slot: &MixSlot| {
+            let shader = slot.shader(&uniforms.mix);
+            self.panics.iter().any(|p| p.shader == shader)
+        };
+        if !MixSlot::ALL.iter().any(panicked) {
+            return None;
+        }
+        let mut safe = uniforms.clone();
+        for slot in MixSlot::ALL.iter().filter(|slot| panicked(slot)) {
+            slot.make_safe(&mut safe.mix);
+        }
+        Some(safe)
+    }
+}
+
+/// Render with `render`, returning the message of a panic either caught here or reported by the
+/// shader library.
+fn try_render(
+    render: &mut impl FnMut(&Uniforms) -> Result<(), String>,
+    uniforms: &Uniforms,
+) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| render(uniforms)))
+        .unwrap_or_else(|payload| Err(panic_message(&*payload)))
+}
+
+/// The message passed to `panic!`, if it was a string.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{builtin, builtin_batch, check_abi_fingerprint, MixSlot, ShaderGuard};
+    use nannou::prelude::*;
+    use shader_shared::{CachedLedShaderInput, Shader, Uniforms, Vertex};
+
+    fn uniforms(left: Shader, right: Shader) -> Uniforms {
+        let mut uniforms = Uniforms::solo(left, 0.0);
+        uniforms.mix.right = right;
+        uniforms.mix.xfade_right = 1.0;
+        uniforms
+    }
+
+    #[test]
+    fn panicking_slot_is_swapped_out_and_the_rest_of_the_mix_kept() {
+        let mut guard = ShaderGuard::default();
+        let mut rendered = Vec::new();
+        let mut render = |uniforms: &Uniforms| {
+            let mix = &uniforms.mix;
+            if MixSlot::ALL
+                .iter()
+                .any(|s| s.shader(mix) == Shader::ColourPalettes)
+            {
+                panic!("palette index out of bounds");
+            }
+            rendered.push((mix.left, mix.right));
+            Ok(())
+        };
+
+        let frame = uniforms(Shader::ColourPalettes, Shader::HoopLoop);
+        guard.render(&frame, &mut render);
+        guard.render(&frame, &mut render);
+
+        assert_eq!(guard.panics().len(), 1);
+        assert_eq!(guard.panics()[0].shader, Shader::ColourPalettes);
+        assert_eq!(guard.panics()[0].message, "palette index out of bounds");
+        // The right slot was rendered alone, then the safe mix was rendered for both frames.
+        let safe_frame = (Shader::SolidRgbColour, Shader::HoopLoop);
+        assert_eq!(rendered, vec![safe_frame; 3]);
+
+        guard.clear();
+        assert!(guard.panics().is_empty());
+    }
+
+    #[test]
+    fn batch_shading_matches_shading_each_led() {
+        let frame = uniforms(Shader::AcidGradient, Shader::ThePulse);
+        let inputs = CachedLedShaderInput::grid(10, 10);
+        let last_colors = vec![lin_srgb(0.25, 0.5, 0.75); inputs.len()];
+
+        let mut batched = vec![lin_srgb(0.0, 0.0, 0.0); inputs.len()];
+        builtin_batch(&inputs, &last_colors, &frame, &mut batched).unwrap();
+
+        for ((input, &last_color), batched) in inputs.iter().zip(&last_colors).zip(&batched) {
+            let vertex = Vertex {
+                position: input.position,
+                light: input.light,
+                last_color,
+            };
+            let expected = builtin(vertex, &frame).unwrap();
+            assert_eq!(
+                (batched.red, batched.green, batched.blue),
+                (expected.red, expected.green, expected.blue)
+            );
+        }
+    }
+
+    #[test]
+    fn library_panics_are_returned_rather_than_unwound() {
+        let frame = uniforms(Shader::AcidGradient, Shader::ThePulse);
+        let mut guard = ShaderGuard::default();
+        let mut results = Vec::new();
+        // Mismatched chunks make the real batch entry point panic.
+        guard.render(&frame, |uniforms| {
+            let inputs = CachedLedShaderInput::grid(1, 1);
+            let mut output = [lin_srgb(0.0, 0.0, 0.0); 2];
+            let result = builtin_batch(&inputs, &[], uniforms, &mut output);
+            results.push(result.clone());
+            result
+        });
+
+        assert!(results[0]
+            .as_ref()
+            .unwrap_err()
+            .contains("given 1 inputs, 0 last colours and 2 outputs"));
+        // Both slots failed alone, so both were recorded and swapped out.
+        let panicked: Vec<_> = guard.panics().iter().map(|p| p.shader).collect();
+        assert_eq!(panicked, vec![Shader::AcidGradient, Shader::ThePulse]);
+    }
+
+    #[test]
+    fn libraries_with_other_shared_types_are_refused() {
+        assert!(check_abi_fingerprint(Some(shader_shared::ABI_FINGERPRINT)).is_ok());
+        let err = check_abi_fingerprint(Some(!shader_shared::ABI_FINGERPRINT)).unwrap_err();
+        assert!(err.contains("Restart the app"));
+        assert!(check_abi_fingerprint(None).is_err());
+    }
 }
@@ -0,0 +1,343 @@
+//! A photosensitivity safety stage for the LED output.
+//!
+//! Follows the shape of common flash guidelines: a flash is a pair of opposing luminance
+//! changes of at least `FLASH_LUMA_DELTA` where the darker state is below `FLASH_DARK_LUMA`.
+//! The flash rate of each LED is measured over `FLASH_WINDOW`. When enough LEDs flash faster than
+//! the configured rate to count as a large area, the output is low-pass filtered until the
+//! strobing stops. Counting per LED rather than per frame means chases and flashes spread over a
+//! few frames are caught as well as flashes of the whole area at once.
+
+use crate::conf::StrobeLimit;
+use nannou::prelude::*;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The minimum change in relative luminance that counts as half of a flash.
+const FLASH_LUMA_DELTA: f32 = 0.1;
+/// Changes between two bright states are not considered flashes.
+const FLASH_DARK_LUMA: f32 = 0.8;
+/// The window over which the flash rate is measured.
+const FLASH_WINDOW: Duration = Duration::from_secs(1);
+/// Output smoothing applied while intervening. Long enough to reduce any strobe above ~3Hz to
+/// well under `FLASH_LUMA_DELTA` of swing.
+const INTERVENE_SMOOTHING_SECS: f32 = 1.0;
+/// How quickly smoothing is released once the input stops strobing.
+const RELEASE_SECS: f32 = 1.0;
+/// Below this the output is passed through untouched.
+const MIN_SMOOTHING_SECS: f32 = 0.001;
+
+#[derive(Clone, Debug, Default)]
+struct LedFlashState {
+    /// The most extreme luminance reached since the last transition.
+    extreme: f32,
+    /// `1` when last seen rising, `-1` when falling, `0` before the first transition.
+    direction: i8,
+    /// Times of this LED's transitions within the window, oldest first.
+    transitions: VecDeque<Instant>,
+}
+
+/// Tracks luminance transitions over time and smooths the output while flashing is too fast.
+#[derive(Debug, Default)]
+pub struct StrobeLimiter {
+    leds: Vec<LedFlashState>,
+    /// The last output, used as the low-pass filter state.
+    output: Vec<LinSrgb>,
+    smoothing_secs: f32,
+    last_update: Option<Instant>,
+}
+
+impl StrobeLimiter {
+    /// Whether the limiter is currently altering the output.
+    pub fn is_intervening(&self) -> bool {
+        self.smoothing_secs > MIN_SMOOTHING_SECS
+    }
+
+    /// Limit the flash rate of `leds` in place.
+    pub fn limit(&mut self, config: &StrobeLimit, leds: &mut [LinSrgb], now: Instant) {
+        if !config.enabled || leds.is_empty() {
+            self.reset();
+            return;
+        }
+
+        if self.leds.len() != leds.len() {
+            self.reset();
+            self.leds = leds
+                .iter()
+                .map(|led| LedFlashState {
+                    extreme: luminance(led),
+                    ..LedFlashState::default()
+                })
+                .collect();
+            self.output = leds.to_vec();
+        }
+
+        let dt_secs = self
+            .last_update
+            .map(|last| now.saturating_duration_since(last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_update = Some(now);
+
+        // Two transitions make a flash, so an LED flashes too fast once it exceeds this many
+        // transitions within the window. Older transitions beyond it don't change the outcome.
+        let max_transitions = (2.0 * config.max_flash_hz * FLASH_WINDOW.as_secs_f32()) as usize;
+        let too_fast = self
+            .leds
+            .iter_mut()
+            .zip(leds.iter())
+            .map(|(state, led)| state.update(luminance(led), now, max_transitions + 1))
+            .filter(|&transitions| transitions > max_transitions)
+            .count();
+        let area_fraction = too_fast as f32 / leds.len() as f32;
+        if area_fraction >= config.area_fraction.max(f32::EPSILON) {
+            self.smoothing_secs = INTERVENE_SMOOTHING_SECS;
+        } else {
+            self.smoothing_secs *= (-dt_secs / RELEASE_SECS).exp();
+        }
+
+        if !self.is_intervening() {
+            self.smoothing_secs = 0.0;
+            self.output.copy_from_slice(leds);
+            return;
+        }
+
+        let amount = 1.0 - (-dt_secs / self.smoothing_secs).exp();
+        for (output, led) in self.output.iter_mut().zip(leds.iter_mut()) {
+            *output = lin_srgb(
+                output.red + (led.red - output.red) * amount,
+                output.green + (led.green - output.green) * amount,
+                output.blue + (led.blue - output.blue) * amount,
+            );
+            *led = *output;
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl LedFlashState {
+    /// Track the new luminance, returning the number of transitions within the window.
+    ///
+    /// At most `max_kept` of the latest transitions are kept.
+    fn update(&mut self, luma: f32, now: Instant, max_kept: usize) -> usize {
+        while let Some(&oldest) = self.transitions.front() {
+            if now.saturating_duration_since(oldest) <= FLASH_WINDOW {
+                break;
+            }
+            self.transitions.pop_front();
+        }
+        if self.transition(luma) {
+            self.transitions.push_back(now);
+            while self.transitions.len() > max_kept {
+                self.transitions.pop_front();
+            }
+        }
+        self.transitions.len()
+    }
+
+    /// Track the new luminance, returning `true` if it completes a transition.
+    fn transition(&mut self, luma: f32) -> bool {
+        let direction = self.direction as f32;
+        // Keep following the current direction to its extreme.
+        if direction != 0.0 && (luma - self.extreme) * direction > 0.0 {
+            self.extreme = luma;
+            return false;
+        }
+        let delta = luma - self.extreme;
+        if delta.abs() < FLASH_LUMA_DELTA || luma.min(self.extreme) >= FLASH_DARK_LUMA {
+            return false;
+        }
+        self.direction = if delta > 0.0 { 1 } else { -1 };
+        self.extreme = luma;
+        true
+    }
+}
+
+/// Relative luminance of a linear sRGB colour.
+fn luminance(colour: &LinSrgb) -> f32 {
+    let r = colour.red.clamp(0.0, 1.0);
+    let g = colour.green.clamp(0.0, 1.0);
+    let b = colour.blue.clamp(0.0, 1.0);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{luminance, StrobeLimiter};
+    use crate::conf::StrobeLimit;
+    use nannou::prelude::*;
+    use std::time::{Duration, Instant};
+
+    const FRAME: Duration = Duration::from_micros(16_667);
+
+    fn enabled() -> StrobeLimit {
+        StrobeLimit {
+            enabled: true,
+            ..StrobeLimit::default()
+        }
+    }
+
+    /// Run a square wave strobe over the first `lit_count` of `led_count` LEDs for `secs`,
+    /// returning the mean output luminance of every frame.
+    fn run_strobe(
+        limiter: &mut StrobeLimiter,
+        config: &StrobeLimit,
+        strobe_hz: f32,
+        led_count: usize,
+        lit_count: usize,
+        secs: f32,
+    ) -> Vec<f32> {
+        let start = Instant::now();
+        let frames = (secs / FRAME.as_secs_f32()) as u32;
+        (0..frames)
+            .map(|frame| {
+                let t = frame as f32 * FRAME.as_secs_f32();
+                let on = ((t * strobe_hz * 2.0) as u32).is_multiple_of(2);
+                let level = if on { 1.0 } else { 0.0 };
+                let mut leds: Vec<_> = (0..led_count)
+                    .map(|ix| {
+                        let v = if ix < lit_count { level } else { 0.0 };
+                        lin_srgb(v, v, v)
+                    })
+                    .collect();
+                limiter.limit(config, &mut leds, start + FRAME * frame);
+                leds.iter().map(luminance).sum::<f32>() / led_count as f32
+            })
+            .collect()
+    }
+
+    fn peak_to_peak(lumas: &[f32]) -> f32 {
+        let max = lumas.iter().cloned().fold(f32::MIN, f32::max);
+        let min = lumas.iter().cloned().fold(f32::MAX, f32::min);
+        max - min
+    }
+
+    #[test]
+    fn fast_full_rig_strobe_is_suppressed() {
+        let mut limiter = StrobeLimiter::default();
+        let lumas = run_strobe(&mut limiter, &enabled(), 10.0, 100, 100, 4.0);
+
+        assert!(limiter.is_intervening());
+        // Once engaged the remaining swing is below a single flash transition.
+        let last_second = &lumas[lumas.len() - 60..];
+        assert!(peak_to_peak(last_second) < 0.1, "{:?}", last_second);
+    }
+
+    #[test]
+    fn slow_strobe_passes_through() {
+        let mut limiter = StrobeLimiter::default();
+        let lumas = run_strobe(&mut limiter, &enabled(), 2.0, 100, 100, 3.0);
+
+        assert!(!limiter.is_intervening());
+        assert!((peak_to_peak(&lumas) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn small_area_strobe_passes_through() {
+        let mut limiter = StrobeLimiter::default();
+        let lumas = run_strobe(&mut limiter, &enabled(), 10.0, 100, 10, 3.0);
+
+        assert!(!limiter.is_intervening());
+        assert!((peak_to_peak(&lumas) - 0.1).abs() < 1e-4);
+    }
+
+    /// Run a chase of `band` lit LEDs stepping one LED per frame around `led_count` LEDs,
+    /// returning the output luminance of the first LED on every frame.
+    fn run_chase(
+        limiter: &mut StrobeLimiter,
+        config: &StrobeLimit,
+        led_count: usize,
+        band: usize,
+        secs: f32,
+    ) -> Vec<f32> {
+        let start = Instant::now();
+        let frames = (secs / FRAME.as_secs_f32()) as u32;
+        (0..frames)
+            .map(|frame| {
+                let head = frame as usize % led_count;
+                let mut leds: Vec<_> = (0..led_count)
+                    .map(|ix| {
+                        let lit = (ix + led_count - head) % led_count < band;
+                        let v = if lit { 1.0 } else { 0.0 };
+                        lin_srgb(v, v, v)
+                    })
+                    .collect();
+                limiter.limit(config, &mut leds, start + FRAME * frame);
+                luminance(&leds[0])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fast_chase_is_suppressed() {
+        // Each LED flashes at 6Hz, but only a tenth of them change on any one frame.
+        let mut limiter = StrobeLimiter::default();
+        let lumas = run_chase(&mut limiter, &enabled(), 10, 1, 4.0);
+
+        assert!(limiter.is_intervening());
+        let last_second = &lumas[lumas.len() - 60..];
+        assert!(peak_to_peak(last_second) < 0.2, "{:?}", last_second);
+    }
+
+    #[test]
+    fn slow_chase_passes_through() {
+        // Each LED flashes at 1Hz.
+        let mut limiter = StrobeLimiter::default();
+        let lumas = run_chase(&mut limiter, &enabled(), 60, 6, 3.0);
+
+        assert!(!limiter.is_intervening());
+        assert!((peak_to_peak(&lumas) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn flash_spread_over_frames_is_suppressed() {
+        // A 10Hz strobe where each fifth of the rig switches a frame after the last, so no single
+        // frame changes enough of the rig to count as a large area.
+        let mut limiter = StrobeLimiter::default();
+        let config = enabled();
+        let start = Instant::now();
+        for frame in 0..240u32 {
+            let mut leds: Vec<_> = (0..100)
+                .map(|ix| {
+                    let delayed = frame.saturating_sub(ix as u32 / 20);
+                    let t = delayed as f32 * FRAME.as_secs_f32();
+                    let v = if ((t * 20.0) as u32).is_multiple_of(2) {
+                        1.0
+                    } else {
+                        0.0
+                    };
+                    lin_srgb(v, v, v)
+                })
+                .collect();
+            limiter.limit(&config, &mut leds, start + FRAME * frame);
+        }
+        assert!(limiter.is_intervening());
+    }
+
+    #[test]
+    fn disabled_limiter_passes_through() {
+        let mut limiter = StrobeLimiter::default();
+        let config = StrobeLimit::default();
+        let lumas = run_strobe(&mut limiter, &config, 10.0, 100, 100, 3.0);
+
+        assert!(!limiter.is_intervening());
+        assert!((peak_to_peak(&lumas) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn limiter_releases_once_strobing_stops() {
+        let mut limiter = StrobeLimiter::default();
+        let config = enabled();
+        run_strobe(&mut limiter, &config, 10.0, 100, 100, 2.0);
+        assert!(limiter.is_intervening());
+
+        // A steady image for long enough that both the window and smoothing have released.
+        let start = Instant::now() + Duration::from_secs(10);
+        for frame in 0..600 {
+            let mut leds = vec![lin_srgb(0.5, 0.5, 0.5); 100];
+            limiter.limit(&config, &mut leds, start + FRAME * frame);
+        }
+        assert!(!limiter.is_intervening());
+    }
+}
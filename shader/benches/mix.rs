@@ -0,0 +1,142 @@
+//! Frame time of the full left/right/colourise mix over a few thousand LEDs.
+//!
+//! `borrowed_params` shades one LED per call. `cloned_uniforms_per_slot` reproduces the previous
+//! path, which cloned the `Uniforms` once per slot for every LED, so the two can be compared
+//! directly. `batch` shades the whole frame through `shader_batch`.
+//!
+//! `prepared_shaders` renders each shader marked `#[prepare]` in the registry alone. `per_led`
+//! shades one LED per call, preparing the shader for every LED, while `prepared` shades the frame
+//! through `shader_batch`, which prepares it once.
+//!
+//! Run with `cargo bench -p shader --bench mix`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use nannou_core::prelude::*;
+use shader_shared::{
+    Button, ButtonRow, ButtonState, CachedLedShaderInput, Shader, State, Strip, ToneMapping,
+    Uniforms, Vertex,
+};
+use std::collections::HashMap;
+
+const LEDS_PER_ROW: usize = 60;
+const ROW_COUNTS: &[usize] = &[25, 50, 100];
+const PREPARED_SHADERS: &[Shader] = &[
+    Shader::AcidGradient,
+    Shader::GilmoreAcid,
+    Shader::ShapeEnvelopes,
+];
+
+/// The LEDs with black as their last colour, for shading one at a time.
+fn vertices(inputs: &[CachedLedShaderInput]) -> Vec<Vertex> {
+    inputs
+        .iter()
+        .map(|input| Vertex {
+            position: input.position,
+            light: input.light,
+            last_color: lin_srgb(0.0, 0.0, 0.0),
+        })
+        .collect()
+}
+
+/// `shader` in the left slot, with the cheapest shader in the others and the first row of
+/// buttons held on a few strips.
+fn solo_uniforms(shader: Shader) -> Uniforms {
+    let mut buttons = HashMap::new();
+    for (i, &strip) in [Strip::A, Strip::B, Strip::C, Strip::D].iter().enumerate() {
+        let state = ButtonState {
+            secs: i as f32,
+            state: State::Off,
+        };
+        buttons.insert(Button::Row(ButtonRow::Solo, strip), state);
+    }
+    Uniforms {
+        resolution: vec2(LEDS_PER_ROW as f32, 1.0),
+        buttons,
+        ..Uniforms::solo(shader, 12.5)
+    }
+}
+
+/// The AcidGradient and ThePulse mix, colourised and tone mapped.
+fn uniforms() -> Uniforms {
+    let mut uniforms = solo_uniforms(Shader::AcidGradient);
+    let mix = &mut uniforms.mix;
+    mix.right = Shader::ThePulse;
+    mix.colourise = Shader::SolidHsvColour;
+    mix.tone_mapping = ToneMapping::Aces;
+    mix.tone_mapping_amount = 0.5;
+    mix.xfade_right = 0.5;
+    uniforms
+}
+
+fn mix_frame(c: &mut Criterion) {
+    let uniforms = uniforms();
+    let mut group = c.benchmark_group("mix_frame");
+    for &rows in ROW_COUNTS {
+        let inputs = CachedLedShaderInput::grid(LEDS_PER_ROW, rows);
+        let vertices = vertices(&inputs);
+        group.throughput(Throughput::Elements(vertices.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("borrowed_params", vertices.len()),
+            &vertices,
+            |b, vertices| {
+                b.iter(|| {
+                    for &v in vertices {
+                        black_box(shader::shader(v, &uniforms).unwrap());
+                    }
+                })
+            },
+        );
+        let last_colors: Vec<_> = vertices.iter().map(|v| v.last_color).collect();
+        let mut output = vec![lin_srgb(0.0, 0.0, 0.0); vertices.len()];
+        group.bench_function(BenchmarkId::new("batch", vertices.len()), |b| {
+            b.iter(|| {
+                shader::shader_batch(&inputs, &last_colors, &uniforms, &mut output).unwrap();
+                black_box(&output);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("cloned_uniforms_per_slot", vertices.len()),
+            &vertices,
+            |b, vertices| {
+                b.iter(|| {
+                    for &v in vertices {
+                        for _slot in 0..3 {
+                            black_box(uniforms.clone());
+                        }
+                        black_box(shader::shader(v, &uniforms).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn prepared_shaders(c: &mut Criterion) {
+    let inputs = CachedLedShaderInput::grid(LEDS_PER_ROW, ROW_COUNTS[ROW_COUNTS.len() - 1]);
+    let vertices = vertices(&inputs);
+    let last_colors: Vec<_> = vertices.iter().map(|v| v.last_color).collect();
+    let mut output = vec![lin_srgb(0.0, 0.0, 0.0); vertices.len()];
+    let mut group = c.benchmark_group("prepared_shaders");
+    group.throughput(Throughput::Elements(vertices.len() as u64));
+    for &shader in PREPARED_SHADERS {
+        let uniforms = solo_uniforms(shader);
+        group.bench_function(BenchmarkId::new("per_led", shader.name()), |b| {
+            b.iter(|| {
+                for &v in &vertices {
+                    black_box(shader::shader(v, &uniforms).unwrap());
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("prepared", shader.name()), |b| {
+            b.iter(|| {
+                shader::shader_batch(&inputs, &last_colors, &uniforms, &mut output).unwrap();
+                black_box(&output);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mix_frame, prepared_shaders);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use crate::helpers::*;
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{ColourPalettes, Light, Uniforms, Vertex};
 // Created by inigo quilez - iq/2015
 // License Creative Commons Attribution-NonCommercial-ShareAlike 3.0 Unported License.
 
@@ -24,9 +24,7 @@ fn palette(t: f32, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3 {
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.colour_palettes;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ColourPalettes) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
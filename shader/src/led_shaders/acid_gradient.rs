@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{AcidGradient, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -19,20 +19,37 @@ fn palette(t: f32, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3 {
         (TWO_PI * (c.z * t + d.z)).cos(),
     )
 }
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.acid_gradient;
+/// The parts of the shader that are the same for every LED in the frame.
+pub struct State {
+    t: f32,
+    d: f32,
+    z: f32,
+    aspect: f32,
+}
+
+pub fn prepare(uniforms: &Uniforms, params: &AcidGradient) -> State {
+    State {
+        t: uniforms.time * params.speed,
+        d: 0.3 * (1.0 + params.offset * 10.0),
+        z: 8.0 + (params.zoom * 64.0),
+        aspect: uniforms.resolution.x / uniforms.resolution.y,
+    }
+}
+
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &AcidGradient) -> LinSrgb {
+    shade(&prepare(uniforms, params), v)
+}
 
-    let t = uniforms.time * params.speed;
-    let d = 0.3 * (1.0 + params.offset * 10.0);
+pub fn shade(state: &State, v: Vertex) -> LinSrgb {
+    let State { t, d, z, aspect } = *state;
 
     let Light::Led {
         normalised_coords, ..
     } = v.light;
     let mut uv = normalised_coords;
 
-    uv.x *= uniforms.resolution.x / uniforms.resolution.y;
+    uv.x *= aspect;
 
-    let z = 8.0 + (params.zoom * 64.0);
     let grid = uv * vec2(z, z);
     uv = vec2(d, d) * (vec2(grid.x.floor(), grid.y.floor()) + vec2(0.5, 0.5)) / vec2(z, z);
 
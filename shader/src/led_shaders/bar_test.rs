@@ -1,7 +1,7 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{BarTest, Light, Uniforms, Vertex};
 
-pub fn shader(v: Vertex, _uniforms: &Uniforms) -> LinSrgb {
+pub fn shader(v: Vertex, _uniforms: &Uniforms, _params: &BarTest) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
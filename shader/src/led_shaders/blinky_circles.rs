@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{BlinkyCircles, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -19,9 +19,7 @@ fn palette(t: f32, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3 {
         (TWO_PI * (c.z * t + d.z)).cos(),
     )
 }
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.blinky_circles;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &BlinkyCircles) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let d = 0.3 * (params.offset * 10.0);
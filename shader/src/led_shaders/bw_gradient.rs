@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{BwGradient, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 use crate::signals::*;
@@ -34,9 +34,7 @@ fn palette(t: f32, signal: &Signal, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.bw_gradient;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &BwGradient) -> LinSrgb {
     let direction = Direction::Vertical;
     let signal_type = Signal::SINE;
 
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{ColourGrid, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 use crate::signals::*;
@@ -21,9 +21,7 @@ use crate::signals::*;
 //     zoom_amount: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.colour_grid;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ColourGrid) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
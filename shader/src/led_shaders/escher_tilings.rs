@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{EscherTilings, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -11,9 +11,7 @@ use crate::helpers::*;
 //     shape_iter: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.escher_tilings;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &EscherTilings) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
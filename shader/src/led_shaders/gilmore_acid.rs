@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{GilmoreAcid, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -17,34 +17,70 @@ use crate::helpers::*;
 //     saturation: f32,
 // }
 
-fn calc(tx: Vec2, t: f32, params: &shader_shared::GilmoreAcid, uniforms: &Uniforms) -> f32 {
-    let angle = PI * nsin(t * 0.1) + PI / 6.0;
-    let len = 1.0 - length(vec3(tx.x, tx.y, 0.)) * nsin(t);
+/// The parts of the shader that are the same for every LED in the frame.
+pub struct State {
+    aspect: f32,
+    channels: [Channel; 3],
+    /// The offset of each of the layers summed in each channel.
+    offsets: [f32; 3],
+    wave: f32,
+    exponent: f32,
+    brightness: f32,
+}
+
+/// One of the red, green and blue channels, each a little further along in time.
+struct Channel {
+    t: f32,
+    nsin_t: f32,
+    grid_scale: f32,
+    rotation: f32,
+    /// How strongly the wave displaces each layer.
+    pulses: [f32; 3],
+}
+
+pub fn prepare(uniforms: &Uniforms, params: &GilmoreAcid) -> State {
+    let t = uniforms.time * params.speed;
+    let offsets = [0, 1, 2]
+        .map(|i| i as f32 / (3.0 + (uniforms.time * 0.05).sin() * (params.colour_offset * 2.0)));
+    let channels = [0, 1, 2].map(|i| {
+        let t = t + i as f32 * params.displace;
+        let angle = PI * nsin(t * 0.1) + PI / 6.0;
+        Channel {
+            t,
+            nsin_t: nsin(t),
+            grid_scale: map_range(params.grid_size, 0.0, 1.0, 0.0, 200.0)
+                * nsin(t * params.zoom_amount),
+            rotation: angle * params.rotation_amount,
+            pulses: offsets.map(|offset| nsin(t * 0.5 + offset)),
+        }
+    });
+    State {
+        aspect: uniforms.resolution.x / uniforms.resolution.y,
+        channels,
+        offsets,
+        wave: map_range(params.wave, 0.0, 1.0, 0.0, 10.0),
+        exponent: map_range(params.saturation, 0.0, 1.0, 5.0, 1.0),
+        brightness: params.brightness * 2.0,
+    }
+}
+
+fn calc(tx: Vec2, channel: &Channel, state: &State) -> f32 {
+    let t = channel.t;
+    let len = 1.0 - length(vec3(tx.x, tx.y, 0.)) * channel.nsin_t;
     let mut value = 0.0;
-    let hex = coord_to_hex(
-        tx,
-        map_range(params.grid_size, 0.0, 1.0, 0.0, 200.0) * nsin(t * params.zoom_amount),
-        angle * params.rotation_amount,
-    );
-    for i in 0..3 {
-        let offset = i as f32 / (3.0 + (uniforms.time * 0.05).sin() * (params.colour_offset * 2.0));
+    let hex = coord_to_hex(tx, channel.grid_scale, channel.rotation);
+    for (i, (offset, pulse)) in state.offsets.iter().zip(&channel.pulses).enumerate() {
         let cell = hex_to_cell(hex, 1.0 + i as f32);
-        value += nsin(
-            hex_to_float(cell, nsin(len + t + offset))
-                * map_range(params.wave, 0.0, 1.0, 0.0, 10.0)
-                * nsin(t * 0.5 + offset)
-                + len
-                + t,
-        );
+        value += nsin(hex_to_float(cell, nsin(len + t + offset)) * state.wave * pulse + len + t);
     }
     value / 3.0
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.gilmore_acid;
-
-    let t = uniforms.time * params.speed;
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &GilmoreAcid) -> LinSrgb {
+    shade(&prepare(uniforms, params), v)
+}
 
+pub fn shade(state: &State, v: Vertex) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
@@ -53,14 +89,12 @@ pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
     // let x = map_range(p.x, -0.13, 0.13, -1.0, 1.0);
     // let y = map_range(p.y, 0.3, 1.0, -1.0, 1.0);
     // let mut uv = vec2(x,y);
-    uv.x *= uniforms.resolution.x / uniforms.resolution.y;
+    uv.x *= state.aspect;
     let mut rgb = [0.0, 0.0, 0.0];
-    for (i, channel) in rgb.iter_mut().enumerate() {
-        let t2 = t + i as f32 * params.displace;
-        *channel +=
-            calc(uv, t2, &params, uniforms).powf(map_range(params.saturation, 0.0, 1.0, 5.0, 1.0));
+    for (channel, prepared) in rgb.iter_mut().zip(&state.channels) {
+        *channel += calc(uv, prepared, state).powf(state.exponent);
     }
 
-    let b = params.brightness * 2.0;
+    let b = state.brightness;
     lin_srgb(rgb[0] * b, rgb[1] * b, rgb[2] * b)
 }
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{GradientBars, Light, Uniforms, Vertex};
 
 use crate::helpers::mix;
 use crate::signals::{ease_lfo, EasingType};
@@ -53,9 +53,7 @@ fn adjust_balance(value: f32, balance: f32) -> f32 {
     value.powf(gamma)
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.gradient_bars;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &GradientBars) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
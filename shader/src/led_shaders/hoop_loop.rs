@@ -38,9 +38,7 @@ fn rot(uv: Vec2, angle: f32) -> Vec2 {
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.hoop_loop;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &HoopLoop) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
@@ -75,7 +73,7 @@ pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
             params.thickness,
             params.blur,
             vec3(1.0, 1.0, 1.0),
-            &params,
+            params,
         );
     }
 
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{ImitationRiley, Light, Uniforms, Vertex};
 
 use crate::helpers::mix;
 
@@ -7,9 +7,7 @@ fn glsl_mod(value: f32, modulus: f32) -> f32 {
     value - modulus * (value / modulus).floor()
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.imitation_riley;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ImitationRiley) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
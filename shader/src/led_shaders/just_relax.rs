@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{JustRelax, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -11,9 +11,7 @@ use crate::helpers::*;
 //     iter: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.just_relax;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &JustRelax) -> LinSrgb {
     let t = uniforms.time * (params.speed * 4.0);
 
     let Light::Led {
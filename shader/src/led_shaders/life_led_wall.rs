@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{LifeLedWall, Light, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -24,9 +24,7 @@ use crate::helpers::*;
 //     colour_offset: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.life_led_wall;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &LifeLedWall) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
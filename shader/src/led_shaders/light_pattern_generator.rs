@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, LightPatternGenerator, Uniforms, Vertex};
 
 use crate::helpers::TWO_PI;
 
@@ -19,9 +19,7 @@ fn palette(t: f32, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3 {
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.light_pattern_generator;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &LightPatternGenerator) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
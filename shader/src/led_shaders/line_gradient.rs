@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, LineGradient, Uniforms, Vertex};
 
 use crate::helpers::*;
 use crate::signals::*;
@@ -12,9 +12,7 @@ use crate::signals::*;
 //     smooth_width: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.line_gradient;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &LineGradient) -> LinSrgb {
     //params.angle = map_range(uniforms.time * 0.5, -1.0 ,1.0, 0.0, 0.5);
 
     let signal_type = Signal::TRIANGLE;
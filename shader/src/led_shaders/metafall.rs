@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, Metafall, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -13,9 +13,7 @@ use crate::helpers::*;
 //     blue: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.metafall;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &Metafall) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, ParticleZoom, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -12,16 +12,14 @@ use crate::helpers::*;
 //     tau: f32,
 // }
 
-fn cell(coord: Vec2, p: &shader_shared::ParticleZoom) -> f32 {
+fn cell(coord: Vec2, p: &ParticleZoom) -> f32 {
     let c = vec2(coord.x.fract(), coord.y.fract()) * vec2(0.5, 2.0) - vec2(0.1, 0.5);
     (1.0 - length(vec3(c.x * 2.0 - 1.0, c.y * 2.0 - 1.0, 0.0))
         * step(rand(vec2(coord.x.floor(), coord.y.floor())), p.density))
         * 5.0
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.particle_zoom;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ParticleZoom) -> LinSrgb {
     let y_offset = (uniforms.time * 0.1).sin();
     let t = uniforms.time * params.speed;
 
@@ -43,7 +41,7 @@ pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
 
     for _ in 0..1 {
         coord += delta;
-        c = c.max(cell(coord, &params));
+        c = c.max(cell(coord, params));
     }
 
     c = (c * d) * 15.0;
@@ -1,11 +1,9 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, RadialKeta, Uniforms, Vertex};
 
 use crate::helpers::smoothstep;
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.radial_keta;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &RadialKeta) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, RadialLines, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -10,9 +10,7 @@ use crate::helpers::*;
 //     zoom_amount: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.radial_lines;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &RadialLines) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
@@ -1,9 +1,7 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
-
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.row_test;
+use shader_shared::{Light, RowTest, Uniforms, Vertex};
 
+pub fn shader(v: Vertex, _uniforms: &Uniforms, params: &RowTest) -> LinSrgb {
     let Light::Led { col_row, .. } = v.light;
     let row = col_row[1];
 
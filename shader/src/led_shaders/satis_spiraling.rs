@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, SatisSpiraling, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -22,9 +22,7 @@ use crate::helpers::*;
 //     rotate: bool,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.satis_spiraling;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &SatisSpiraling) -> LinSrgb {
     //params.speed = map_range((uniforms.time*0.01).sin() * (uniforms.time*0.001 * 10.0).cos(), -1.0, 1.0, 0.05, 0.2);
 
     let t = uniforms.time * -params.speed;
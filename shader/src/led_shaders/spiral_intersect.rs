@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, SpiralIntersect, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -31,9 +31,7 @@ fn sinp(p: f32) -> f32 {
     0.5 + p.sin() * 0.5
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.spiral_intersect;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &SpiralIntersect) -> LinSrgb {
     let mut t = uniforms.time * params.speed;
 
     let Light::Led {
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, SquareTunnel, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -12,9 +12,7 @@ use crate::helpers::*;
 //     zoom: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.square_tunnel;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &SquareTunnel) -> LinSrgb {
     let t = uniforms.time * (params.speed * 2.0);
 
     let Light::Led {
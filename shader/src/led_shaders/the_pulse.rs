@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, ThePulse, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -37,9 +37,7 @@ fn circle(uv: Vec2, r: f32, thickness: f32) -> f32 {
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.the_pulse;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ThePulse) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
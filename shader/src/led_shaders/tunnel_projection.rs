@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, TunnelProjection, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -10,9 +10,7 @@ use crate::helpers::*;
 //     res: f32,
 // }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.tunnel_projection;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &TunnelProjection) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, TwoDTiles, Uniforms, Vertex};
 
 use crate::helpers::{mix, smoothstep, step};
 
@@ -11,9 +11,7 @@ fn glsl_fract_vec2(v: Vec2) -> Vec2 {
     vec2(glsl_fract(v.x), glsl_fract(v.y))
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.two_d_tiles;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &TwoDTiles) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
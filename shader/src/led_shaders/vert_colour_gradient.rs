@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Light, Uniforms, Vertex};
+use shader_shared::{Light, Uniforms, VertColourGradient, Vertex};
 
 use crate::helpers::*;
 
@@ -51,9 +51,7 @@ fn hue(colour: Vec3, shift: f32) -> Vec3 {
     //colour.z = yIQ.dot(kYIQToB);
     //colour
 }
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let params = uniforms.params.vert_colour_gradient;
-
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &VertColourGradient) -> LinSrgb {
     let t = uniforms.time * params.speed;
 
     let Light::Led {
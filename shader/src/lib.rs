@@ -1,7 +1,12 @@
-//! The shader function hotloaded at runtime by the cohen_gig crate.
+//! The shader function used by the cohen_gig crate, either hotloaded at runtime or linked in
+//! directly.
 
 use nannou_core::prelude::*;
-use shader_shared::{BlendMode, Shader, ToneMapping, Uniforms, Vertex};
+use shader_shared::{
+    BlendMode, CachedLedShaderInput, MixingInfo, Shader, ShaderParams, ToneMapping, Uniforms,
+    Vertex,
+};
+use std::panic::{self, AssertUnwindSafe};
 
 mod blend_modes;
 pub mod helpers;
@@ -17,21 +22,84 @@ mod led_shaders;
 
 mod wash_shaders;
 
+/// Checked by the exe before loading this library, to make sure both were built against the same
+/// `shader_shared` types.
 #[no_mangle]
-fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let mix = &uniforms.mix;
+pub static SHADER_ABI_FINGERPRINT: u64 = shader_shared::ABI_FINGERPRINT;
 
-    // Retrieve the shader functions for left, right and colourising.
-    let left_shader = get_shader(mix.left);
-    let right_shader = get_shader(mix.right);
-    let colourise = get_shader(mix.colourise);
+/// Shade a single LED.
+///
+/// A panic while shading is returned as its message. Unwinding out of the hotloaded library
+/// can't be caught by the exe, so both entry points catch their own panics.
+#[no_mangle]
+pub fn shader(v: Vertex, uniforms: &Uniforms) -> Result<LinSrgb, String> {
+    catch_panic(|| {
+        let mix = &uniforms.mix;
+
+        // Run each sub-shader with its own params so there's no collision when the same shader
+        // type is used in multiple slots. The params are borrowed from the mix so nothing is
+        // cloned per LED.
+        let left = slot_fn(mix.left)(v, uniforms, &mix.params_left);
+        let right = slot_fn(mix.right)(v, uniforms, &mix.params_right);
+        let colour = slot_fn(mix.colourise)(v, uniforms, &mix.params_colourise);
+
+        mix_slots(left, right, colour, mix)
+    })
+}
+
+/// Shade a chunk of LEDs, writing the colour of each of `inputs` to `output`.
+///
+/// `last_colors` holds the colour produced for each LED in the previous frame. Work that is the
+/// same for every LED in the frame, such as looking up the shader in each slot and preparing
+/// shaders marked `#[prepare]` in the registry, is done once per call rather than once per LED.
+///
+/// As with `shader`, a panic is returned as its message, leaving `output` partly shaded.
+#[no_mangle]
+pub fn shader_batch(
+    inputs: &[CachedLedShaderInput],
+    last_colors: &[LinSrgb],
+    uniforms: &Uniforms,
+    output: &mut [LinSrgb],
+) -> Result<(), String> {
+    catch_panic(|| {
+        assert!(
+            inputs.len() == last_colors.len() && inputs.len() == output.len(),
+            "shader_batch given {} inputs, {} last colours and {} outputs",
+            inputs.len(),
+            last_colors.len(),
+            output.len()
+        );
+        let mix = &uniforms.mix;
+        let left = prepare_slot(mix.left, uniforms, &mix.params_left);
+        let right = prepare_slot(mix.right, uniforms, &mix.params_right);
+        let colourise = prepare_slot(mix.colourise, uniforms, &mix.params_colourise);
+        let leds = output.iter_mut().zip(inputs).zip(last_colors);
+        for ((color, input), &last_color) in leds {
+            let v = Vertex {
+                position: input.position,
+                light: input.light,
+                last_color,
+            };
+            *color = mix_slots(left(v), right(v), colourise(v), mix);
+        }
+    })
+}
 
-    // Run each sub-shader with its own params so there's no collision
-    // when the same shader type is used in multiple slots.
-    let left = left_shader(v, &with_params(uniforms, mix.params_left));
-    let right = right_shader(v, &with_params(uniforms, mix.params_right));
-    let colour = colourise(v, &with_params(uniforms, mix.params_colourise));
+/// Run `f`, returning the message passed to `panic!` if it panics.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown panic".to_string()
+        }
+    })
+}
 
+/// Blend, colourise and tone map the output of each slot.
+fn mix_slots(left: LinSrgb, right: LinSrgb, colour: LinSrgb, mix: &MixingInfo) -> LinSrgb {
     // Mix the left and right shaders.
     let xfl = lin_srgb(mix.xfade_left, mix.xfade_left, mix.xfade_left);
     let xfr = lin_srgb(mix.xfade_right, mix.xfade_right, mix.xfade_right);
@@ -53,49 +121,57 @@ fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
     apply_tone_mapping(col, mix.tone_mapping, mix.tone_mapping_amount)
 }
 
-fn with_params(uniforms: &Uniforms, params: shader_shared::ShaderParams) -> Uniforms {
-    Uniforms {
-        params,
-        ..uniforms.clone()
-    }
+// Every shader module by the name of its `ShaderParams` field, for looking up shader functions.
+mod registry {
+    pub(crate) use crate::led_shaders::*;
+    pub(crate) use crate::wash_shaders::*;
+    pub(crate) use crate::{colour_palettes, solid_hsv_colour, solid_rgb_colour};
 }
 
-fn get_shader(shader: Shader) -> fn(Vertex, &Uniforms) -> LinSrgb {
-    match shader {
-        Shader::SolidHsvColour => solid_hsv_colour::shader,
-        Shader::SolidRgbColour => solid_rgb_colour::shader,
-        Shader::ColourPalettes => colour_palettes::shader,
-        Shader::AcidGradient => led_shaders::acid_gradient::shader,
-        Shader::BlinkyCircles => led_shaders::blinky_circles::shader,
-        Shader::BwGradient => led_shaders::bw_gradient::shader,
-        Shader::ColourGrid => led_shaders::colour_grid::shader,
-        Shader::EscherTilings => led_shaders::escher_tilings::shader,
-        Shader::GilmoreAcid => led_shaders::gilmore_acid::shader,
-        Shader::GradientBars => led_shaders::gradient_bars::shader,
-        Shader::HoopLoop => led_shaders::hoop_loop::shader,
-        Shader::ImitationRiley => led_shaders::imitation_riley::shader,
-        Shader::JustRelax => led_shaders::just_relax::shader,
-        Shader::LifeLedWall => led_shaders::life_led_wall::shader,
-        Shader::LightPatternGenerator => led_shaders::light_pattern_generator::shader,
-        Shader::LineGradient => led_shaders::line_gradient::shader,
-        Shader::Metafall => led_shaders::metafall::shader,
-        Shader::ParticleZoom => led_shaders::particle_zoom::shader,
-        Shader::RadialLines => led_shaders::radial_lines::shader,
-        Shader::SatisSpiraling => led_shaders::satis_spiraling::shader,
-        Shader::SpiralIntersect => led_shaders::spiral_intersect::shader,
-        Shader::RadialKeta => led_shaders::radial_keta::shader,
-        Shader::SquareTunnel => led_shaders::square_tunnel::shader,
-        Shader::ThePulse => led_shaders::the_pulse::shader,
-        Shader::TunnelProjection => led_shaders::tunnel_projection::shader,
-        Shader::TwoDTiles => led_shaders::two_d_tiles::shader,
-        Shader::VertColourGradient => led_shaders::vert_colour_gradient::shader,
-        Shader::RowTest => led_shaders::row_test::shader,
-        Shader::BarTest => led_shaders::bar_test::shader,
-        Shader::MitchWash => wash_shaders::mitch_wash::shader,
-        Shader::ShapeEnvelopes => wash_shaders::shape_envelopes::shader,
-    }
+/// A shader in a slot of the mix, reading its own params from the slot's `ShaderParams`.
+type SlotFn = fn(Vertex, &Uniforms, &ShaderParams) -> LinSrgb;
+
+/// A shader in a slot of the mix, ready to shade each LED of the frame.
+type PreparedSlot<'a> = Box<dyn Fn(Vertex) -> LinSrgb + 'a>;
+
+/// Prepare the shader `$field` once for the frame if it's marked `#[prepare]`, otherwise shade
+/// each LED with its `shader` function.
+macro_rules! prepared_slot {
+    ($field:ident, $uniforms:ident, $params:ident) => {
+        Box::new(move |v| registry::$field::shader(v, $uniforms, &$params.$field))
+    };
+    ($field:ident, $uniforms:ident, $params:ident, prepare) => {{
+        let state = registry::$field::prepare($uniforms, &$params.$field);
+        Box::new(move |v| registry::$field::shade(&state, v))
+    }};
 }
 
+macro_rules! slot_fn {
+    ($($(#[$prepare:ident])? $Shader:ident, $field:ident, $name:literal $params:tt)*) => {
+        /// The function running the given shader.
+        fn slot_fn(shader: Shader) -> SlotFn {
+            match shader {
+                $(Shader::$Shader => |v: Vertex, uniforms: &Uniforms, params: &ShaderParams| {
+                    registry::$field::shader(v, uniforms, &params.$field)
+                },)*
+            }
+        }
+
+        /// The given shader, prepared for the frame described by `uniforms`.
+        fn prepare_slot<'a>(
+            shader: Shader,
+            uniforms: &'a Uniforms,
+            params: &'a ShaderParams,
+        ) -> PreparedSlot<'a> {
+            match shader {
+                $(Shader::$Shader => prepared_slot!($field, uniforms, params $(, $prepare)?),)*
+            }
+        }
+    };
+}
+
+shader_shared::for_each_shader!(slot_fn);
+
 fn apply_tone_mapping(col: LinSrgb, tone_mapping: ToneMapping, amount: f32) -> LinSrgb {
     let amount = amount.clamp(0.0, 1.0);
     if amount <= f32::EPSILON || matches!(tone_mapping, ToneMapping::None) {
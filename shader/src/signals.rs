@@ -2,6 +2,7 @@ use nannou_core::math::fmod;
 use nannou_core::prelude::*;
 use nannou_core::rand::random_f32;
 use pennereq::*;
+use std::cell::Cell;
 
 pub const ALL: &[Signal] = &[
     Signal::SINE,
@@ -150,7 +151,40 @@ fn sawtooth(phase: f32) -> f32 {
     fmod(phase, 1.0) * -2.0 + 1.0
 }
 fn noise(_phase: f32) -> f32 {
-    random_f32() * 2.0 - 1.0
+    let seeded = NOISE_STATE.with(|state| {
+        let next = state.get()?.wrapping_add(SPLITMIX_GAMMA);
+        state.set(Some(next));
+        Some(splitmix64(next))
+    });
+    let unit = match seeded {
+        // The top 24 bits, which an `f32` in `0..1` represents exactly.
+        Some(bits) => (bits >> 40) as f32 / (1u64 << 24) as f32,
+        None => random_f32(),
+    };
+    unit * 2.0 - 1.0
+}
+
+thread_local! {
+    /// The state of the `NOISE` LFO's generator on this thread, if seeded.
+    static NOISE_STATE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+const SPLITMIX_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Make the `NOISE` LFO on the calling thread repeatable, starting from `seed`, or random again
+/// for `None`.
+///
+/// The LFO is random by default. Tests seed it so that shaders using it render the same frames
+/// every run.
+pub fn seed_noise(seed: Option<u64>) {
+    NOISE_STATE.with(|state| state.set(seed));
+}
+
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 //------------------ EASINGS
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Uniforms, Vertex};
+use shader_shared::{SolidHsvColour, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -25,8 +25,7 @@ fn hsv2rgb_smooth(c: Vec3) -> Vec3 {
     )
 }
 
-pub fn shader(_v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let p = uniforms.params.solid_hsv_colour;
+pub fn shader(_v: Vertex, _uniforms: &Uniforms, p: &SolidHsvColour) -> LinSrgb {
     let rgb = hsv2rgb_smooth(vec3(p.hue, p.saturation, p.value));
     lin_srgb(rgb.x, rgb.y, rgb.z)
 }
@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Uniforms, Vertex};
+use shader_shared::{SolidRgbColour, Uniforms, Vertex};
 
 // struct Params {
 //     red: f32,
@@ -7,7 +7,6 @@ use shader_shared::{Uniforms, Vertex};
 //     blue: f32,
 // }
 
-pub fn shader(_v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let p = uniforms.params.solid_rgb_colour;
+pub fn shader(_v: Vertex, _uniforms: &Uniforms, p: &SolidRgbColour) -> LinSrgb {
     lin_srgb(p.red, p.green, p.blue)
 }
@@ -1,8 +1,8 @@
 use nannou_core::prelude::*;
-use shader_shared::{Button, Uniforms, Vertex};
+use shader_shared::{Button, MitchWash, Uniforms, Vertex};
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let pulse_speed = uniforms.params.mitch_wash.pulse_speed;
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &MitchWash) -> LinSrgb {
+    let pulse_speed = params.pulse_speed;
 
     let p = v.position;
     let mut col = vec3(0.0, 0.0, 0.0); //vec3(b*r*0.5, g*b, b);
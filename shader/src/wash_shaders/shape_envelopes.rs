@@ -1,5 +1,5 @@
 use nannou_core::prelude::*;
-use shader_shared::{Button, ButtonRow, Light, Strip, Uniforms, Vertex};
+use shader_shared::{Button, ButtonRow, Light, ShapeEnvelopes, Strip, Uniforms, Vertex};
 
 use crate::helpers::*;
 
@@ -33,19 +33,103 @@ fn halo_ring(uv: Vec2, pos: Vec2, radius: f32, thick: f32) -> f32 {
     )
 }
 
-pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
-    let speed = uniforms.params.shape_envelopes.speed;
-    let pulse_speed = uniforms.params.shape_envelopes.pulse_speed;
-    let line_thickness = uniforms.params.shape_envelopes.line_thickness;
-    let shape_thickness = uniforms.params.shape_envelopes.shape_thickness;
+/// The rows of buttons that each fire an envelope on their strip.
+const ROWS: [ButtonRow; 3] = [ButtonRow::Solo, ButtonRow::Mute, ButtonRow::Record];
 
-    let t = uniforms.time * speed;
+/// The parts of the shader that are the same for every LED in the frame.
+///
+/// Each envelope is `None` while its button hasn't been pressed.
+pub struct State {
+    aspect: f32,
+    ring_thickness: f32,
+    square_thickness: f32,
+    line_weight: f32,
+    /// Turns the frames fired from strip C by 45 degrees and more over time.
+    rotation: Mat2,
+    /// The radius of each halo ring fired from strip A.
+    rings: [Option<f32>; 3],
+    /// The size of each rounded frame fired from strip B.
+    frames: [Option<Vec2>; 3],
+    /// The size of each rotated frame fired from strip C.
+    rotated_frames: [Option<Vec2>; 3],
+    /// Two points on each line fired from strips D, E, F and G.
+    lines: [[Option<[Vec2; 2]>; 3]; 4],
+}
+
+pub fn prepare(uniforms: &Uniforms, params: &ShapeEnvelopes) -> State {
+    let pulse_speed = params.pulse_speed;
+    let line_thickness = params.line_thickness;
+    let shape_thickness = params.shape_thickness;
+
+    let t = uniforms.time * params.speed;
+
+    let envelopes = |strip: Strip| {
+        ROWS.map(|row| {
+            uniforms
+                .buttons
+                .get(&Button::Row(row, strip))
+                .map(|state| state.secs * (0.1 + pulse_speed))
+        })
+    };
+    let sizes = |strip: Strip| {
+        envelopes(strip).map(|s| {
+            s.map(|s| {
+                let env = s.max(0.0).powf(2.0);
+                vec2(env * 2.0, env * 2.0)
+            })
+        })
+    };
+    let lines = |strip: Strip, from: f32, to: f32, sign: f32, vertical: bool| {
+        envelopes(strip).map(|s| {
+            s.map(|s| {
+                let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, from, to);
+                env *= sign;
+                if vertical {
+                    [vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0)]
+                } else {
+                    [vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0)]
+                }
+            })
+        })
+    };
+    State {
+        aspect: uniforms.resolution.x / uniforms.resolution.y,
+        ring_thickness: 2.0 + (shape_thickness * 40.0),
+        square_thickness: 0.07 + (shape_thickness * 1.7),
+        line_weight: 0.016 + line_thickness,
+        rotation: rotate_2d(t * 0.25),
+        rings: envelopes(Strip::A).map(|s| s.map(|s| s.max(0.0).powf(2.0) * 2.0)),
+        frames: sizes(Strip::B),
+        rotated_frames: sizes(Strip::C),
+        // Left, right, top and bottom.
+        lines: [
+            lines(Strip::D, 0.8, 1.3, 1.0, true),
+            lines(Strip::E, 0.8, 1.3, -1.0, true),
+            lines(Strip::F, 1.0, 1.9, -1.0, false),
+            lines(Strip::G, 1.0, 1.9, 1.0, false),
+        ],
+    }
+}
+
+/// Add `color` to `col` in proportion to `intensity`.
+fn add_mix(col: Vec3, color: Vec3, intensity: f32) -> Vec3 {
+    col + vec3(
+        mix(col.x, color.x, intensity),
+        mix(col.y, color.y, intensity),
+        mix(col.z, color.z, intensity),
+    )
+}
+
+pub fn shader(v: Vertex, uniforms: &Uniforms, params: &ShapeEnvelopes) -> LinSrgb {
+    shade(&prepare(uniforms, params), v)
+}
 
+pub fn shade(state: &State, v: Vertex) -> LinSrgb {
     let Light::Led {
         normalised_coords, ..
     } = v.light;
     let mut uv = normalised_coords;
-    uv.x *= uniforms.resolution.x / uniforms.resolution.y;
+    uv.x *= state.aspect;
 
     let mut col = vec3(0.0, 0.0, 0.0);
     let circle_amp = 2.0;
@@ -53,311 +137,30 @@ pub fn shader(v: Vertex, uniforms: &Uniforms) -> LinSrgb {
 
     //--- HALO RING ---
     let ring_color = vec3(1.0, 1.0, 1.5) * vec3(circle_amp, circle_amp, circle_amp);
-    let ring_thickness = 2.0 + (shape_thickness * 40.0);
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::A))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let intensity = halo_ring(uv, vec2(0.0, 0.0), env * 2.0, ring_thickness);
-        col += vec3(
-            mix(col.x, ring_color.x, intensity),
-            mix(col.y, ring_color.y, intensity),
-            mix(col.z, ring_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::A))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let intensity = halo_ring(uv, vec2(0.0, 0.0), env * 2.0, ring_thickness);
-        col += vec3(
-            mix(col.x, ring_color.x, intensity),
-            mix(col.y, ring_color.y, intensity),
-            mix(col.z, ring_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::A))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let intensity = halo_ring(uv, vec2(0.0, 0.0), env * 2.0, ring_thickness);
-        col += vec3(
-            mix(col.x, ring_color.x, intensity),
-            mix(col.y, ring_color.y, intensity),
-            mix(col.z, ring_color.z, intensity),
-        );
+    for &radius in state.rings.iter().flatten() {
+        let intensity = halo_ring(uv, vec2(0.0, 0.0), radius, state.ring_thickness);
+        col = add_mix(col, ring_color, intensity);
     }
 
     //--- rounded frame ---
     let frame_color = vec3(1.0, 1.0, 1.5) * vec3(square_amp, square_amp, square_amp);
-    let square_thickness = 0.07 + (shape_thickness * 1.7);
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::B))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::B))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::B))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
+    for &size in state.frames.iter().flatten() {
+        let intensity = rounded_frame(uv, vec2(0.0, 0.0), size, 0.2, state.square_thickness);
+        col = add_mix(col, frame_color, intensity);
     }
 
     // 45 degree square
-    let mut uv2 = uv;
-    uv2 = multiply_mat2_with_vec2(rotate_2d(t * 0.25), uv2);
-
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::C))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv2, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::C))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv2, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::C))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(2.0);
-        let size = vec2(env * 2.0, env * 2.0);
-        let intensity = rounded_frame(uv2, vec2(0.0, 0.0), size, 0.2, square_thickness);
-        col += vec3(
-            mix(col.x, frame_color.x, intensity),
-            mix(col.y, frame_color.y, intensity),
-            mix(col.z, frame_color.z, intensity),
-        );
+    let uv2 = multiply_mat2_with_vec2(state.rotation, uv);
+    for &size in state.rotated_frames.iter().flatten() {
+        let intensity = rounded_frame(uv2, vec2(0.0, 0.0), size, 0.2, state.square_thickness);
+        col = add_mix(col, frame_color, intensity);
     }
 
-    //--- horizontal line ---
+    //--- lines ---
     let line_color = vec3(1.0, 1.0, 1.7);
-    let line_weight = 0.016 + line_thickness;
-    // Line Left
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::D))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::D))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::D))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-
-    // Line Right
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::E))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        env *= -1.0;
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::E))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        env *= -1.0;
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::E))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 0.8, 1.3);
-        env *= -1.0;
-        let intensity = line(uv, vec2(env * 2.0, -1.0), vec2(env * 2.0, 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-
-    // Line Top
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::F))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        env *= -1.0;
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::F))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        env *= -1.0;
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::F))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let mut env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        env *= -1.0;
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-
-    // Line Bottom
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Solo, Strip::G))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Mute, Strip::G))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
-    }
-    if let Some(state) = uniforms
-        .buttons
-        .get(&Button::Row(ButtonRow::Record, Strip::G))
-    {
-        let s = state.secs * (0.1 + pulse_speed);
-        let env = s.max(0.0).powf(1.5) - map_range(line_thickness, 0.0, 1.0, 1.0, 1.9);
-        let intensity = line(uv, vec2(-1.0, env * 1.0), vec2(1.0, env * 1.0), line_weight);
-        col += vec3(
-            mix(col.x, line_color.x, intensity),
-            mix(col.y, line_color.y, intensity),
-            mix(col.z, line_color.z, intensity),
-        );
+    for &[a, b] in state.lines.iter().flatten().flatten() {
+        let intensity = line(uv, a, b, state.line_weight);
+        col = add_mix(col, line_color, intensity);
     }
 
     lin_srgb(col.x, col.y, col.z)
@@ -0,0 +1,251 @@
+//! Golden frame regression tests for every shader.
+//!
+//! Each shader is rendered alone with its default params at a few fixed times over a small grid
+//! of LEDs, once with no buttons and once with a few buttons held, and compared against the frames
+//! checked in under `tests/golden_frames`. After a change
+//! that is meant to alter how shaders look, re-bless the frames with:
+//!
+//! ```text
+//! BLESS_GOLDEN_FRAMES=1 cargo test -p shader --test golden_frames
+//! ```
+//!
+//! A shader without golden frames fails too, until they're blessed and checked in.
+
+use nannou_core::prelude::*;
+use serde_json::{json, Value};
+use shader::signals::{self, LfoType};
+use shader_shared::{
+    Button, ButtonRow, ButtonState, CachedLedShaderInput, Shader, State, Strip, Uniforms,
+    ALL_SHADERS,
+};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const COLS: usize = 16;
+const ROWS: usize = 6;
+/// Exactly representable, so they read back from JSON unchanged.
+const TIMES: &[f32] = &[0.0, 1.5, 12.25];
+/// The buttons held for the second pass over `TIMES`, with the seconds since each was pressed.
+const HELD_BUTTONS: &[(Button, f32)] = &[
+    (Button::Row(ButtonRow::Solo, Strip::A), 0.5),
+    (Button::Row(ButtonRow::Mute, Strip::B), 0.75),
+    (Button::Row(ButtonRow::Record, Strip::C), 0.25),
+    (Button::Row(ButtonRow::Solo, Strip::D), 0.5),
+    (Button::Row(ButtonRow::Mute, Strip::E), 1.0),
+    (Button::Row(ButtonRow::Record, Strip::G), 0.75),
+    (Button::Cycle, 0.125),
+];
+const NOISE_SEED: u64 = 0x5EED;
+/// The largest difference allowed in any channel, a single step of an 8-bit channel. Channels far
+/// outside 0 to 1 are allowed the same difference relative to their size.
+const TOLERANCE: f64 = 1.0 / 255.0;
+/// Channels are rounded to this many decimal places to keep the golden files readable.
+const DECIMALS: i32 = 5;
+const BLESS_VAR: &str = "BLESS_GOLDEN_FRAMES";
+
+/// The colour of each LED, row by row.
+type Frame = Vec<[f64; 3]>;
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden_frames")
+}
+
+fn golden_path(shader: Shader) -> PathBuf {
+    golden_dir().join(format!("{:?}.json", shader))
+}
+
+/// Each frame's time and whether `HELD_BUTTONS` are held, in the order they're rendered.
+fn frame_keys() -> impl Iterator<Item = (f32, bool)> {
+    let times = || TIMES.iter().copied();
+    times()
+        .map(|time| (time, false))
+        .chain(times().map(|time| (time, true)))
+}
+
+/// The shader in the left slot, passed through untouched by the rest of the mix.
+fn uniforms(shader: Shader, time: f32, held: bool) -> Uniforms {
+    let held_buttons = if held { HELD_BUTTONS } else { &[] };
+    let buttons = held_buttons
+        .iter()
+        .map(|&(button, secs)| {
+            (
+                button,
+                ButtonState {
+                    secs,
+                    state: State::On,
+                },
+            )
+        })
+        .collect();
+    let mut uniforms = Uniforms {
+        resolution: vec2(COLS as f32, ROWS as f32),
+        buttons,
+        ..Uniforms::solo(shader, time)
+    };
+    if held {
+        // At their default thickness the envelope shapes fall between the LEDs of such a coarse
+        // grid, so thicken them enough to be seen.
+        let envelopes = &mut uniforms.mix.params_left.shape_envelopes;
+        envelopes.line_thickness = 0.5;
+        envelopes.shape_thickness = 0.5;
+    }
+    uniforms
+}
+
+/// Render the shader for each of `frame_keys`, each frame starting from black.
+fn render(shader: Shader) -> Vec<Frame> {
+    let inputs = CachedLedShaderInput::grid(COLS, ROWS);
+    let black = vec![lin_srgb(0.0, 0.0, 0.0); inputs.len()];
+    let round = |channel: f32| {
+        let scale = 10f64.powi(DECIMALS);
+        (channel as f64 * scale).round() / scale
+    };
+    frame_keys()
+        .map(|(time, held)| {
+            signals::seed_noise(Some(NOISE_SEED));
+            let mut output = black.clone();
+            let uniforms = uniforms(shader, time, held);
+            shader::shader_batch(&inputs, &black, &uniforms, &mut output)
+                .unwrap_or_else(|err| panic!("{} panicked: {}", shader.name(), err));
+            output
+                .iter()
+                .map(|c| [round(c.red), round(c.green), round(c.blue)])
+                .collect()
+        })
+        .collect()
+}
+
+fn to_json(shader: Shader, frames: &[Frame]) -> Value {
+    let frames: Vec<_> = frame_keys()
+        .zip(frames)
+        .map(|((time, held), pixels)| {
+            json!({ "time": time, "buttons_held": held, "pixels": pixels })
+        })
+        .collect();
+    json!({
+        "shader": shader.name(),
+        "cols": COLS,
+        "rows": ROWS,
+        "frames": frames,
+    })
+}
+
+fn write_golden(shader: Shader, frames: &[Frame]) {
+    let path = golden_path(shader);
+    let json = serde_json::to_string_pretty(&to_json(shader, frames))
+        .expect("failed to serialise golden frames");
+    fs::create_dir_all(golden_dir()).expect("failed to create the golden frames directory");
+    fs::write(&path, json + "\n")
+        .unwrap_or_else(|err| panic!("failed to write {:?}: {}", path, err));
+}
+
+/// Non-finite channels are written as `null`, so they only match one another.
+fn within_tolerance(expected: &Value, actual: f64) -> bool {
+    match expected.as_f64() {
+        Some(expected) => (expected - actual).abs() <= TOLERANCE * expected.abs().max(1.0),
+        None => !actual.is_finite(),
+    }
+}
+
+/// Describe the first LED that differs from the golden frames by more than the tolerance.
+fn compare(golden: &Value, frames: &[Frame]) -> Result<(), String> {
+    if golden["cols"] != json!(COLS) || golden["rows"] != json!(ROWS) {
+        return Err(format!(
+            "golden frames are {}x{} LEDs rather than {}x{}",
+            golden["cols"], golden["rows"], COLS, ROWS
+        ));
+    }
+    let golden_frames = golden["frames"].as_array().ok_or("golden frames missing")?;
+    let golden_keys: Vec<_> = golden_frames
+        .iter()
+        .map(|f| (f["time"].as_f64(), f["buttons_held"].as_bool()))
+        .collect();
+    let keys: Vec<_> = frame_keys()
+        .map(|(time, held)| (Some(time as f64), Some(held)))
+        .collect();
+    if golden_keys != keys {
+        return Err(format!(
+            "golden frames are for (time, buttons held) {:?}",
+            golden_keys
+        ));
+    }
+    for ((golden_frame, (time, held)), pixels) in golden_frames.iter().zip(frame_keys()).zip(frames)
+    {
+        let golden_pixels = golden_frame["pixels"]
+            .as_array()
+            .ok_or("golden pixels missing")?;
+        if golden_pixels.len() != pixels.len() {
+            return Err(format!(
+                "golden frame at time {} (buttons held: {}) has the wrong LED count",
+                time, held
+            ));
+        }
+        for (ix, (expected, actual)) in golden_pixels.iter().zip(pixels).enumerate() {
+            let matches =
+                (0..3).all(|channel| within_tolerance(&expected[channel], actual[channel]));
+            if !matches {
+                return Err(format!(
+                    "at time {} (buttons held: {}) LED (col {}, row {}) is {:?}, expected {}",
+                    time,
+                    held,
+                    ix % COLS,
+                    ix / COLS,
+                    actual,
+                    expected
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn shaders_match_golden_frames() {
+    let bless = std::env::var_os(BLESS_VAR).is_some();
+    let mut failures = Vec::new();
+    for &shader in ALL_SHADERS {
+        let frames = render(shader);
+        let path = golden_path(shader);
+        match fs::read_to_string(&path) {
+            _ if bless => write_golden(shader, &frames),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                failures.push(format!("{}: no golden frames at {:?}", shader.name(), path))
+            }
+            Err(err) => failures.push(format!(
+                "{}: failed to read {:?}: {}",
+                shader.name(),
+                path,
+                err
+            )),
+            Ok(json) => {
+                let result = serde_json::from_str::<Value>(&json)
+                    .map_err(|err| format!("failed to parse {:?}: {}", path, err))
+                    .and_then(|golden| compare(&golden, &frames));
+                if let Err(err) = result {
+                    failures.push(format!("{}: {}", shader.name(), err));
+                }
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} shader(s) don't match their golden frames. If that's intended, bless them with \
+         `{}=1 cargo test -p shader --test golden_frames`.\n{}",
+        failures.len(),
+        BLESS_VAR,
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn seeded_noise_repeats() {
+    let sample = || (0..8).map(|_| LfoType::Noise.amp(0.0)).collect::<Vec<_>>();
+    signals::seed_noise(Some(NOISE_SEED));
+    let first = sample();
+    signals::seed_noise(Some(NOISE_SEED));
+    assert_eq!(first, sample());
+    assert!(first.iter().all(|amp| (-1.0..=1.0).contains(amp)));
+    assert!(first.windows(2).any(|pair| pair[0] != pair[1]));
+    signals::seed_noise(None);
+}
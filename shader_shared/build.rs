@@ -0,0 +1,65 @@
+//! Fingerprints the shared types so that the app can refuse a shader library built against
+//! different ones.
+//!
+//! The fingerprint covers this crate's sources and the compiler version, either of which may
+//! change how the shared types are laid out.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let manifest_dir = Path::new(&manifest_dir);
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let mut hash = Fnv64::default();
+    let mut files = Vec::new();
+    collect_files(&manifest_dir.join("src"), &mut files);
+    files.push(manifest_dir.join("Cargo.toml"));
+    files.sort();
+    for file in &files {
+        let contents = std::fs::read(file).expect("failed to read shader_shared source");
+        hash.write(&contents);
+    }
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    if let Ok(output) = Command::new(rustc).arg("-V").output() {
+        hash.write(&output.stdout);
+    }
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    let source = format!("const SOURCE_FINGERPRINT: u64 = {:#018x};\n", hash.0);
+    std::fs::write(Path::new(&out_dir).join("fingerprint.rs"), source)
+        .expect("failed to write fingerprint");
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let entries = std::fs::read_dir(dir).expect("failed to read shader_shared sources");
+    for entry in entries {
+        let path = entry.expect("failed to read directory entry").path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// FNV-1a, which is stable across builds unlike `DefaultHasher`.
+struct Fnv64(u64);
+
+impl Default for Fnv64 {
+    fn default() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv64 {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
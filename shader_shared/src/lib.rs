@@ -2,18 +2,24 @@
 //! important in order to ensure types are laid out the same way between the dynamic library and
 //! the exe.
 
-use devault::Devault;
+#[macro_use]
+mod registry;
+
 use nannou_core::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-fn default_half() -> f32 {
-    0.5
-}
+include!(concat!(env!("OUT_DIR"), "/fingerprint.rs"));
 
-fn default_one() -> f32 {
-    1.0
-}
+/// Identifies the layout of the types in this crate, so that the exe can check that a hotloaded
+/// shader library was built against the same types before calling into it.
+///
+/// Derived from this crate's sources and compiler version at build time, along with the sizes of
+/// the types passed to the shader.
+pub const ABI_FINGERPRINT: u64 = SOURCE_FINGERPRINT
+    ^ std::mem::size_of::<Vertex>() as u64
+    ^ ((std::mem::size_of::<Uniforms>() as u64) << 16)
+    ^ ((std::mem::size_of::<ShaderParams>() as u64) << 32);
 
 // Button-related types (previously from korg_nano_kontrol_2).
 
@@ -76,6 +82,40 @@ pub struct Vertex {
     pub last_color: LinSrgb,
 }
 
+/// The parts of a `Vertex` that stay the same from frame to frame, cached per LED by the exe and
+/// passed to the batch shader entry point.
+#[derive(Copy, Clone)]
+pub struct CachedLedShaderInput {
+    pub position: Point3,
+    pub light: Light,
+}
+
+impl CachedLedShaderInput {
+    /// A grid of `cols` by `rows` LEDs, row by row, spread evenly from -1.0 to 1.0 in both
+    /// normalised coordinates and position. For rendering shaders in tests and benchmarks.
+    pub fn grid(cols: usize, rows: usize) -> Vec<Self> {
+        let coord = |ix: usize, count: usize| match count {
+            0 | 1 => 0.0,
+            _ => ix as f32 / (count - 1) as f32 * 2.0 - 1.0,
+        };
+        (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (col, row)))
+            .enumerate()
+            .map(|(index, (col, row))| {
+                let normalised_coords = vec2(coord(col, cols), coord(row, rows));
+                CachedLedShaderInput {
+                    position: pt3(normalised_coords.x, normalised_coords.y, 0.0),
+                    light: Light::Led {
+                        index,
+                        col_row: [col, row],
+                        normalised_coords,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum Light {
     /// Single LED light info.
@@ -103,12 +143,47 @@ pub struct Uniforms {
     pub pot6: f32,
     pub pot7: f32,
     pub pot8: f32,
-    pub params: ShaderParams,
     pub mix: MixingInfo,
     /// Only contains buttons that have been pressed at least once.
     pub buttons: HashMap<Button, ButtonState>,
 }
 
+impl Uniforms {
+    /// `shader` alone in the left slot with its default params, passed through untouched by the
+    /// rest of the mix. For rendering a single shader in tests and benchmarks.
+    pub fn solo(shader: Shader, time: f32) -> Self {
+        let white = ShaderParams {
+            solid_rgb_colour: SolidRgbColour {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+            },
+            ..ShaderParams::default()
+        };
+        Uniforms {
+            time,
+            resolution: vec2(1.0, 1.0),
+            pot6: 0.5,
+            pot7: 0.5,
+            pot8: 0.5,
+            mix: MixingInfo {
+                left: shader,
+                right: Shader::SolidRgbColour,
+                colourise: Shader::SolidRgbColour,
+                blend_mode: BlendMode::Add,
+                tone_mapping: ToneMapping::None,
+                tone_mapping_amount: 0.0,
+                xfade_left: 1.0,
+                xfade_right: 0.0,
+                params_left: ShaderParams::default(),
+                params_right: ShaderParams::default(),
+                params_colourise: white,
+            },
+            buttons: HashMap::new(),
+        }
+    }
+}
+
 /// Describes one of the buttons on the korg.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Button {
@@ -146,72 +221,144 @@ pub struct MixingInfo {
     pub params_colourise: ShaderParams,
 }
 
-#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
-pub struct ShaderParams {
-    #[serde(default)]
-    pub acid_gradient: AcidGradient,
-    #[serde(default)]
-    pub blinky_circles: BlinkyCircles,
-    #[serde(default)]
-    pub bw_gradient: BwGradient,
-    #[serde(default)]
-    pub colour_grid: ColourGrid,
-    #[serde(default)]
-    pub escher_tilings: EscherTilings,
-    #[serde(default)]
-    pub gilmore_acid: GilmoreAcid,
-    #[serde(default)]
-    pub gradient_bars: GradientBars,
-    #[serde(default)]
-    pub just_relax: JustRelax,
-    #[serde(default)]
-    pub life_led_wall: LifeLedWall,
-    #[serde(default)]
-    pub light_pattern_generator: LightPatternGenerator,
-    #[serde(default)]
-    pub line_gradient: LineGradient,
-    #[serde(default)]
-    pub metafall: Metafall,
-    #[serde(default)]
-    pub particle_zoom: ParticleZoom,
-    #[serde(default)]
-    pub radial_lines: RadialLines,
-    #[serde(default)]
-    pub satis_spiraling: SatisSpiraling,
-    #[serde(default)]
-    pub spiral_intersect: SpiralIntersect,
-    #[serde(default)]
-    pub square_tunnel: SquareTunnel,
-    #[serde(default)]
-    pub the_pulse: ThePulse,
-    #[serde(default)]
-    pub tunnel_projection: TunnelProjection,
-    #[serde(default)]
-    pub vert_colour_gradient: VertColourGradient,
-    #[serde(default)]
-    pub solid_hsv_colour: SolidHsvColour,
-    #[serde(default)]
-    pub solid_rgb_colour: SolidRgbColour,
-    #[serde(default)]
-    pub colour_palettes: ColourPalettes,
-    #[serde(default)]
-    pub mitch_wash: MitchWash,
-    #[serde(default)]
-    pub shape_envelopes: ShapeEnvelopes,
-    #[serde(default)]
-    pub row_test: RowTest,
-    #[serde(default)]
-    pub bar_test: BarTest,
-    #[serde(default)]
-    pub two_d_tiles: TwoDTiles,
-    #[serde(default)]
-    pub radial_keta: RadialKeta,
-    #[serde(default)]
-    pub imitation_riley: ImitationRiley,
-    #[serde(default)]
-    pub hoop_loop: HoopLoop,
+/// Implemented for all sets of shader parameters to allow for generic GUI layout.
+pub trait Params {
+    /// The total number of parameters.
+    fn param_count(&self) -> usize;
+    /// The parameter at the given index.
+    fn param_mut(&mut self, ix: usize) -> ParamMut<'_>;
+}
+
+pub struct ParamMut<'a> {
+    pub name: &'static str,
+    pub kind: ParamKindMut<'a>,
+}
+
+pub enum ParamKindMut<'a> {
+    F32 {
+        value: &'a mut f32,
+        max: f32,
+    },
+    F32Range {
+        value: &'a mut f32,
+        min: f32,
+        max: f32,
+    },
+    Bool(&'a mut bool),
+    Select {
+        value: &'a mut usize,
+        labels: &'static [&'static str],
+    },
+    Usize {
+        value: &'a mut usize,
+        max: usize,
+    },
+}
+
+macro_rules! param_kind {
+    ($value:expr, Bool) => {
+        ParamKindMut::Bool($value)
+    };
+    ($value:expr, $kind:ident { $($meta:ident: $meta_value:expr),* $(,)? }) => {
+        ParamKindMut::$kind { value: $value, $($meta: $meta_value),* }
+    };
 }
 
+/// Generates `Shader`, `ShaderParams` and each shader's params from the registry.
+macro_rules! define_shaders {
+    ($(
+        $(#[$prepare:ident])? $Shader:ident, $field:ident, $name:literal {
+            $(
+                $param:ident: $ty:ty = $default:expr, $label:literal,
+                $kind:ident $({ $($meta:ident: $meta_value:expr),* $(,)? })?;
+            )*
+        }
+    )*) => {
+        /// For selecting between each of the available shaders at runtime.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum Shader {
+            $($Shader,)*
+        }
+
+        pub const ALL_SHADERS: &[Shader] = &[$(Shader::$Shader,)*];
+
+        impl Shader {
+            /// The name of the variant in the form of a string for GUI presentation.
+            pub fn name(&self) -> &str {
+                match *self {
+                    $(Shader::$Shader => $name,)*
+                }
+            }
+
+            pub fn to_index(&self) -> usize {
+                *self as usize
+            }
+
+            pub fn from_index(index: usize) -> Option<Self> {
+                ALL_SHADERS.get(index).copied()
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+        pub struct ShaderParams {
+            $(
+                #[serde(default)]
+                pub $field: $Shader,
+            )*
+        }
+
+        impl ShaderParams {
+            /// The params of the given shader, for generic GUI layout.
+            pub fn params_mut(&mut self, shader: Shader) -> &mut dyn Params {
+                match shader {
+                    $(Shader::$Shader => &mut self.$field,)*
+                }
+            }
+        }
+
+        $(
+            // Params missing from older presets take their default.
+            #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+            #[serde(default)]
+            pub struct $Shader {
+                $(pub $param: $ty,)*
+            }
+
+            impl Default for $Shader {
+                fn default() -> Self {
+                    $Shader {
+                        $($param: $default,)*
+                    }
+                }
+            }
+
+            impl Params for $Shader {
+                fn param_count(&self) -> usize {
+                    <[&str]>::len(&[$($label),*])
+                }
+
+                fn param_mut(&mut self, ix: usize) -> ParamMut<'_> {
+                    let params = [$(
+                        ParamMut {
+                            name: $label,
+                            kind: param_kind!(
+                                &mut self.$param,
+                                $kind $({ $($meta: $meta_value),* })?
+                            ),
+                        },
+                    )*];
+                    params
+                        .into_iter()
+                        .nth(ix)
+                        .unwrap_or_else(|| panic!("no parameter for index {}", ix))
+                }
+            }
+        )*
+    };
+}
+
+for_each_shader!(define_shaders);
+
 /// Refers to the selected blend mode type for a preset.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlendMode {
@@ -234,145 +381,6 @@ pub enum ToneMapping {
     Tanh,
 }
 
-/// For selecting between each of the available shaders at runtime.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Shader {
-    SolidHsvColour,
-    SolidRgbColour,
-    ColourPalettes,
-    AcidGradient,
-    BlinkyCircles,
-    BwGradient,
-    ColourGrid,
-    EscherTilings,
-    GilmoreAcid,
-    GradientBars,
-    JustRelax,
-    LifeLedWall,
-    LightPatternGenerator,
-    LineGradient,
-    Metafall,
-    ParticleZoom,
-    RadialLines,
-    SatisSpiraling,
-    SpiralIntersect,
-    SquareTunnel,
-    ThePulse,
-    TunnelProjection,
-    VertColourGradient,
-    MitchWash,
-    ShapeEnvelopes,
-    RowTest,
-    BarTest,
-    TwoDTiles,
-    RadialKeta,
-    ImitationRiley,
-    HoopLoop,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct AcidGradient {
-    #[devault("0.5125")]
-    pub speed: f32,
-    #[devault("0.0")]
-    pub zoom: f32,
-    #[devault("0.75")]
-    pub offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct BlinkyCircles {
-    #[devault("0.5125")]
-    pub speed: f32,
-    #[devault("0.05")]
-    pub zoom: f32,
-    #[devault("0.25")]
-    pub offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct BwGradient {
-    #[devault("0.5125")]
-    pub speed: f32,
-    #[devault("0.05")]
-    pub dc: f32,
-    #[devault("0.5")]
-    pub amp: f32,
-    #[devault("0.5")]
-    pub freq: f32,
-    #[devault("false")]
-    pub mirror: bool,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ColourGrid {
-    #[devault("0.5")]
-    pub speed: f32,
-    #[devault("0.1")]
-    pub zoom_amount: f32,
-    #[serde(default = "default_half")]
-    #[devault("0.5")]
-    pub colour_amount: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct EscherTilings {
-    #[devault("0.2")]
-    pub speed: f32,
-    #[devault("0.2")]
-    pub scale: f32,
-    #[devault("0.2")]
-    pub shape_iter: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct GilmoreAcid {
-    #[devault("0.025")]
-    pub speed: f32,
-    #[devault("0.01")]
-    pub displace: f32,
-    #[devault("0.85")]
-    pub colour_offset: f32,
-    #[devault("0.345")]
-    pub grid_size: f32,
-    #[devault("0.088")]
-    pub wave: f32,
-    #[devault("0.0")]
-    pub zoom_amount: f32,
-    #[devault("0.0")]
-    pub rotation_amount: f32,
-    #[devault("1.0")]
-    pub brightness: f32,
-    #[devault("0.15")]
-    pub saturation: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct GradientBars {
-    #[devault("0")]
-    pub easing_type: usize,
-    #[devault("0.2")]
-    pub gradient_pow: f32,
-    #[devault("0.15")]
-    pub balance: f32,
-    #[devault("0.1")]
-    pub speed: f32,
-    #[devault("0.2")]
-    pub invert_speed: f32,
-    #[devault("4.0")]
-    pub offset: f32,
-    #[devault("false")]
-    pub use_odd_dirs: bool,
-    #[devault("2.0")]
-    pub phase_iter: f32,
-    #[devault("15.0")]
-    pub num_columns: f32,
-    #[devault("2.0")]
-    pub x_iter: f32,
-    #[devault("false")]
-    pub use_columns: bool,
-}
-
 pub const GRADIENT_BARS_EASING_LABELS: &[&str] = &[
     "Ease In Sine",
     "Ease Out Sine",
@@ -406,310 +414,6 @@ pub const GRADIENT_BARS_EASING_LABELS: &[&str] = &[
     "Ease InOut Bounce",
 ];
 
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct JustRelax {
-    #[devault("0.6")]
-    pub speed: f32,
-    #[devault("0.728")]
-    pub shape_offset: f32,
-    #[devault("1.0")]
-    pub iter: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct LifeLedWall {
-    #[devault("0.25")]
-    pub speed: f32,
-    #[devault("0.73")]
-    pub size: f32,
-    #[devault("0.5")]
-    pub red: f32,
-    #[devault("0.2")]
-    pub green: f32,
-    #[devault("0.1")]
-    pub blue: f32,
-    #[devault("1.0")]
-    pub saturation: f32,
-    #[devault("0.01")]
-    pub colour_offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct LightPatternGenerator {
-    #[devault("0.5")]
-    pub zoom: f32,
-    #[devault("0.2")]
-    pub offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct LineGradient {
-    #[devault("0.03")]
-    pub speed: f32,
-    #[devault("1.0")]
-    pub num_stripes: f32,
-    #[devault("0.9")]
-    pub stripe_width: f32,
-    #[devault("0.5")]
-    pub angle: f32,
-    #[devault("0.155")]
-    pub smooth_width: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct Metafall {
-    #[devault("0.47")]
-    pub speed: f32,
-    #[devault("0.0")]
-    pub scale: f32,
-    #[devault("1.0")]
-    pub red: f32,
-    #[devault("1.0")]
-    pub green: f32,
-    #[devault("1.0")]
-    pub blue: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ParticleZoom {
-    #[devault("0.01")]
-    pub speed: f32,
-    #[devault("0.01")]
-    pub density: f32,
-    #[devault("0.35")]
-    pub shape: f32,
-    #[devault("1.0")]
-    pub tau: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct RadialLines {
-    #[devault("0.05")]
-    pub speed: f32,
-    #[devault("0.8")]
-    pub zoom_amount: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct SatisSpiraling {
-    #[devault("0.5")]
-    pub speed: f32,
-    #[devault("0.8")]
-    pub loops: f32,
-    #[devault("true")]
-    pub mirror: bool,
-    #[devault("true")]
-    pub rotate: bool,
-    #[serde(default)]
-    #[devault("0.0")]
-    pub colour_offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct SpiralIntersect {
-    #[devault("0.02")]
-    pub speed: f32,
-    #[devault("0.4")]
-    pub g1: f32,
-    #[devault("0.6")]
-    pub g2: f32,
-    #[devault("1.0")]
-    pub rot1: f32,
-    #[devault("0.5")]
-    pub rot2: f32,
-    #[devault("1.0")]
-    pub colours: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct SquareTunnel {
-    #[devault("0.6")]
-    pub speed: f32,
-    #[devault("0.025")]
-    pub rotation_speed: f32,
-    #[devault("0.0")]
-    pub rotation_offset: f32,
-    #[devault("0.8")]
-    pub zoom: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ThePulse {
-    #[devault("0.08")]
-    pub speed: f32,
-    #[devault("0.1")]
-    pub scale: f32,
-    #[devault("0.25")]
-    pub colour_iter: f32,
-    #[devault("0.0")]
-    pub thickness: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct TunnelProjection {
-    #[devault("0.5")]
-    pub speed: f32,
-    #[devault("0.5")]
-    pub res: f32,
-    #[serde(default = "default_half")]
-    #[devault("0.5")]
-    pub y_offset: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct VertColourGradient {
-    #[devault("0.5")]
-    pub speed: f32,
-    #[devault("0.83")]
-    pub scale: f32,
-    #[devault("0.015")]
-    pub colour_iter: f32,
-    #[devault("0.0")]
-    pub line_amp: f32,
-    #[devault("0.0")]
-    pub diag_amp: f32,
-    #[devault("0.65")]
-    pub boarder_amp: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct MitchWash {
-    #[devault("1.0")]
-    pub speed: f32,
-    #[devault("1.0")]
-    pub pulse_speed: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ShapeEnvelopes {
-    #[devault("1.0")]
-    pub speed: f32,
-    #[devault("1.0")]
-    pub pulse_speed: f32,
-    #[devault("0.0")]
-    pub line_thickness: f32,
-    #[devault("0.0")]
-    pub shape_thickness: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct SolidHsvColour {
-    #[devault("1.0")]
-    pub hue: f32,
-    #[devault("0.0")]
-    pub saturation: f32,
-    #[devault("1.0")]
-    pub value: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct SolidRgbColour {
-    #[devault("1.0")]
-    pub red: f32,
-    #[devault("1.0")]
-    pub green: f32,
-    #[devault("1.0")]
-    pub blue: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ColourPalettes {
-    #[devault("0.1")]
-    pub speed: f32,
-    #[devault("0.05")]
-    pub interval: f32,
-    #[devault("0")]
-    pub selected: usize,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct RowTest {
-    #[devault("0.0")]
-    pub row: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct BarTest {
-    #[devault("0.0")]
-    pub row: f32,
-    #[devault("0.0")]
-    pub bar: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct TwoDTiles {
-    #[devault("20.0")]
-    pub size: f32,
-    #[devault("20.0")]
-    pub offset: f32,
-    #[devault("1.0")]
-    pub step_thresh: f32,
-    #[devault("6.2")]
-    pub power: f32,
-    #[devault("0.40")]
-    pub speed: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct RadialKeta {
-    #[devault("0.035")]
-    pub speed: f32,
-    #[devault("25.0")]
-    pub iter: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct ImitationRiley {
-    #[devault("20.0")]
-    pub steps: f32,
-    #[devault("1.32")]
-    pub offset: f32,
-    #[devault("0.0")]
-    pub x_mirror: f32,
-    #[devault("0.2")]
-    pub speed: f32,
-    #[serde(default)]
-    #[devault("0.0")]
-    pub black_level: f32,
-    #[serde(default = "default_one")]
-    #[devault("1.0")]
-    pub white_level: f32,
-}
-
-#[derive(Copy, Clone, Debug, Devault, PartialEq, Serialize, Deserialize)]
-pub struct HoopLoop {
-    #[devault("false")]
-    pub invert: bool,
-    #[devault("2.0")]
-    pub zoom: f32,
-    #[devault("0.3")]
-    pub animate: f32,
-    #[devault("0.35")]
-    pub size: f32,
-    #[devault("0.001")]
-    pub thickness: f32,
-    #[devault("0.001")]
-    pub line_effect: f32,
-    #[devault("0.0")]
-    pub pattern_offset: f32,
-    #[devault("0.0")]
-    pub r_sin: f32,
-    #[devault("1.0")]
-    pub x_cos: f32,
-    #[devault("1.0")]
-    pub y_sin: f32,
-    #[devault("0.005")]
-    pub blur: f32,
-    #[devault("0")]
-    pub function: usize,
-    #[devault("0.0")]
-    pub rotate: f32,
-    #[devault("0.5")]
-    pub pos_x: f32,
-    #[devault("0.5")]
-    pub pos_y: f32,
-}
-
 pub const HOOP_LOOP_FUNCTION_LABELS: &[&str] = &["abs", "fract"];
 
 pub const ALL_BLEND_MODES: &[BlendMode] = &[
@@ -730,40 +434,6 @@ pub const ALL_TONE_MAPPINGS: &[ToneMapping] = &[
     ToneMapping::Tanh,
 ];
 
-pub const ALL_SHADERS: &[Shader] = &[
-    Shader::SolidHsvColour,
-    Shader::SolidRgbColour,
-    Shader::ColourPalettes,
-    Shader::AcidGradient,
-    Shader::BlinkyCircles,
-    Shader::BwGradient,
-    Shader::ColourGrid,
-    Shader::EscherTilings,
-    Shader::GilmoreAcid,
-    Shader::GradientBars,
-    Shader::JustRelax,
-    Shader::LifeLedWall,
-    Shader::LightPatternGenerator,
-    Shader::LineGradient,
-    Shader::Metafall,
-    Shader::ParticleZoom,
-    Shader::RadialLines,
-    Shader::SatisSpiraling,
-    Shader::SpiralIntersect,
-    Shader::SquareTunnel,
-    Shader::ThePulse,
-    Shader::TunnelProjection,
-    Shader::VertColourGradient,
-    Shader::MitchWash,
-    Shader::ShapeEnvelopes,
-    Shader::RowTest,
-    Shader::BarTest,
-    Shader::TwoDTiles,
-    Shader::RadialKeta,
-    Shader::ImitationRiley,
-    Shader::HoopLoop,
-];
-
 pub const SOLID_COLOUR_SHADERS: &[Shader] = &[
     Shader::SolidHsvColour,
     Shader::SolidRgbColour,
@@ -844,116 +514,3 @@ impl ToneMapping {
         Some(mode)
     }
 }
-
-impl Shader {
-    /// The name of the variant in the form of a string for GUI presentation.
-    pub fn name(&self) -> &str {
-        match *self {
-            Shader::SolidHsvColour => "SolidHsvColour",
-            Shader::SolidRgbColour => "SolidRgbColour",
-            Shader::ColourPalettes => "ColourPalettes",
-            Shader::AcidGradient => "AcidGradient",
-            Shader::BlinkyCircles => "BlinkyCircles",
-            Shader::BwGradient => "BwGradient",
-            Shader::ColourGrid => "ColourGrid",
-            Shader::EscherTilings => "EscherTilings",
-            Shader::GilmoreAcid => "GilmoreAcid",
-            Shader::GradientBars => "GradientBars",
-            Shader::JustRelax => "JustRelax",
-            Shader::LifeLedWall => "LifeLedWall",
-            Shader::LightPatternGenerator => "LightPatternGenerator",
-            Shader::LineGradient => "LineGradient",
-            Shader::Metafall => "Metafall",
-            Shader::ParticleZoom => "ParticleZoom",
-            Shader::RadialLines => "RadialLines",
-            Shader::SatisSpiraling => "SatisSpiraling",
-            Shader::SpiralIntersect => "SpiralIntersect",
-            Shader::SquareTunnel => "SquareTunnel",
-            Shader::ThePulse => "ThePulse",
-            Shader::TunnelProjection => "TunnelProjection",
-            Shader::VertColourGradient => "VertColourGradient",
-            Shader::MitchWash => "MitchWash",
-            Shader::ShapeEnvelopes => "ShapeEnvelopes",
-            Shader::RowTest => "RowTest",
-            Shader::BarTest => "BarTest",
-            Shader::TwoDTiles => "2dTiles",
-            Shader::RadialKeta => "RadialKeta",
-            Shader::ImitationRiley => "ImitationRiley",
-            Shader::HoopLoop => "HoopLoop",
-        }
-    }
-
-    pub fn to_index(&self) -> usize {
-        match *self {
-            Shader::SolidHsvColour => 0,
-            Shader::SolidRgbColour => 1,
-            Shader::ColourPalettes => 2,
-            Shader::AcidGradient => 3,
-            Shader::BlinkyCircles => 4,
-            Shader::BwGradient => 5,
-            Shader::ColourGrid => 6,
-            Shader::EscherTilings => 7,
-            Shader::GilmoreAcid => 8,
-            Shader::GradientBars => 9,
-            Shader::JustRelax => 10,
-            Shader::LifeLedWall => 11,
-            Shader::LightPatternGenerator => 12,
-            Shader::LineGradient => 13,
-            Shader::Metafall => 14,
-            Shader::ParticleZoom => 15,
-            Shader::RadialLines => 16,
-            Shader::SatisSpiraling => 17,
-            Shader::SpiralIntersect => 18,
-            Shader::SquareTunnel => 19,
-            Shader::ThePulse => 20,
-            Shader::TunnelProjection => 21,
-            Shader::VertColourGradient => 22,
-            Shader::MitchWash => 23,
-            Shader::ShapeEnvelopes => 24,
-            Shader::RowTest => 25,
-            Shader::BarTest => 26,
-            Shader::TwoDTiles => 27,
-            Shader::RadialKeta => 28,
-            Shader::ImitationRiley => 29,
-            Shader::HoopLoop => 30,
-        }
-    }
-
-    pub fn from_index(index: usize) -> Option<Self> {
-        let shader = match index {
-            0 => Shader::SolidHsvColour,
-            1 => Shader::SolidRgbColour,
-            2 => Shader::ColourPalettes,
-            3 => Shader::AcidGradient,
-            4 => Shader::BlinkyCircles,
-            5 => Shader::BwGradient,
-            6 => Shader::ColourGrid,
-            7 => Shader::EscherTilings,
-            8 => Shader::GilmoreAcid,
-            9 => Shader::GradientBars,
-            10 => Shader::JustRelax,
-            11 => Shader::LifeLedWall,
-            12 => Shader::LightPatternGenerator,
-            13 => Shader::LineGradient,
-            14 => Shader::Metafall,
-            15 => Shader::ParticleZoom,
-            16 => Shader::RadialLines,
-            17 => Shader::SatisSpiraling,
-            18 => Shader::SpiralIntersect,
-            19 => Shader::SquareTunnel,
-            20 => Shader::ThePulse,
-            21 => Shader::TunnelProjection,
-            22 => Shader::VertColourGradient,
-            23 => Shader::MitchWash,
-            24 => Shader::ShapeEnvelopes,
-            25 => Shader::RowTest,
-            26 => Shader::BarTest,
-            27 => Shader::TwoDTiles,
-            28 => Shader::RadialKeta,
-            29 => Shader::ImitationRiley,
-            30 => Shader::HoopLoop,
-            _ => return None,
-        };
-        Some(shader)
-    }
-}
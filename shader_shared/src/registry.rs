@@ -0,0 +1,239 @@
+//! The registry of every shader, along with its params and their GUI metadata.
+//!
+//! Everything that needs to handle each shader in turn, such as the `Shader` enum, `ShaderParams`,
+//! preset storage and the shader dispatch, is generated from this one list. To add a shader, add
+//! an entry here and a module of the same name to the `shader` crate, with a `shader` function that
+//! takes the vertex, the uniforms and a reference to the shader's params.
+//!
+//! Each entry is the `Shader` variant, which is also the name of its params type, the field of
+//! `ShaderParams` holding those params, the name shown in the GUI and the params themselves. Each
+//! param is listed with its type, default value, GUI label and GUI kind, in the order shown in the
+//! GUI. The kind is one of the `ParamKindMut` variants, along with its range or labels.
+//!
+//! An entry marked `#[prepare]` does work that is the same for every LED once per frame. Its module
+//! also has a `prepare` function taking the uniforms and params and returning a `State`, and a
+//! `shade` function taking that state and the vertex. Batches of LEDs are shaded through these,
+//! while `shader` remains for shading a single LED.
+
+/// Invoke the macro `$callback` with every shader entry in the registry.
+///
+/// Callbacks that only need the shader names can match each entry with
+/// `$(#[$prepare:ident])? $Shader:ident, $field:ident, $name:literal $params:tt`.
+#[macro_export]
+macro_rules! for_each_shader {
+    ($callback:ident) => {
+        $callback! {
+            SolidHsvColour, solid_hsv_colour, "SolidHsvColour" {
+                hue: f32 = 1.0, "hue", F32 { max: 1.0 };
+                saturation: f32 = 0.0, "saturation", F32 { max: 1.0 };
+                value: f32 = 1.0, "value", F32 { max: 1.0 };
+            }
+            SolidRgbColour, solid_rgb_colour, "SolidRgbColour" {
+                red: f32 = 1.0, "red", F32 { max: 1.0 };
+                green: f32 = 1.0, "green", F32 { max: 1.0 };
+                blue: f32 = 1.0, "blue", F32 { max: 1.0 };
+            }
+            ColourPalettes, colour_palettes, "ColourPalettes" {
+                speed: f32 = 0.1, "speed", F32 { max: 1.0 };
+                interval: f32 = 0.05, "interval", F32 { max: 1.0 };
+                selected: usize = 0, "selected", Usize { max: 16 };
+            }
+            #[prepare]
+            AcidGradient, acid_gradient, "AcidGradient" {
+                speed: f32 = 0.5125, "speed", F32 { max: 1.0 };
+                zoom: f32 = 0.0, "zoom", F32 { max: 1.0 };
+                offset: f32 = 0.75, "offset", F32 { max: 1.0 };
+            }
+            BlinkyCircles, blinky_circles, "BlinkyCircles" {
+                speed: f32 = 0.5125, "speed", F32 { max: 1.0 };
+                zoom: f32 = 0.05, "zoom", F32 { max: 1.0 };
+                offset: f32 = 0.25, "offset", F32 { max: 1.0 };
+            }
+            BwGradient, bw_gradient, "BwGradient" {
+                speed: f32 = 0.5125, "speed", F32 { max: 1.0 };
+                dc: f32 = 0.05, "dc", F32 { max: 1.0 };
+                amp: f32 = 0.5, "amp", F32 { max: 1.0 };
+                freq: f32 = 0.5, "freq", F32 { max: 1.0 };
+                mirror: bool = false, "mirror", Bool;
+            }
+            ColourGrid, colour_grid, "ColourGrid" {
+                speed: f32 = 0.5, "speed", F32 { max: 1.0 };
+                zoom_amount: f32 = 0.1, "zoom_amount", F32 { max: 1.0 };
+                colour_amount: f32 = 0.5, "colour_amount", F32 { max: 1.0 };
+            }
+            EscherTilings, escher_tilings, "EscherTilings" {
+                speed: f32 = 0.2, "speed", F32 { max: 1.0 };
+                scale: f32 = 0.2, "scale", F32 { max: 1.0 };
+                shape_iter: f32 = 0.2, "shape_iter", F32 { max: 1.0 };
+            }
+            #[prepare]
+            GilmoreAcid, gilmore_acid, "GilmoreAcid" {
+                speed: f32 = 0.025, "speed", F32 { max: 1.0 };
+                displace: f32 = 0.01, "displace", F32 { max: 1.0 };
+                colour_offset: f32 = 0.85, "colour_offset", F32 { max: 1.0 };
+                grid_size: f32 = 0.345, "grid_size", F32 { max: 1.0 };
+                wave: f32 = 0.088, "wave", F32 { max: 1.0 };
+                zoom_amount: f32 = 0.0, "zoom_amount", F32 { max: 1.0 };
+                rotation_amount: f32 = 0.0, "rotation_amount", F32 { max: 1.0 };
+                brightness: f32 = 1.0, "brightness", F32 { max: 1.0 };
+                saturation: f32 = 0.15, "saturation", F32 { max: 1.0 };
+            }
+            GradientBars, gradient_bars, "GradientBars" {
+                easing_type: usize = 0, "Easing Type", Select {
+                    labels: $crate::GRADIENT_BARS_EASING_LABELS,
+                };
+                gradient_pow: f32 = 0.2, "Gradient Power", F32Range { min: 0.0, max: 1.0 };
+                balance: f32 = 0.15, "Balance", F32Range { min: 0.0, max: 1.0 };
+                speed: f32 = 0.1, "Speed", F32Range { min: 0.0, max: 1.0 };
+                invert_speed: f32 = 0.2, "Invert Speed", F32Range { min: 0.0, max: 1.0 };
+                offset: f32 = 4.0, "Offset", F32Range { min: 0.0, max: 16.0 };
+                use_odd_dirs: bool = false, "Use Odd Directions", Bool;
+                phase_iter: f32 = 2.0, "Phase Iterations", F32Range { min: 1.0, max: 16.0 };
+                num_columns: f32 = 15.0, "Number of Columns", F32Range { min: 1.0, max: 32.0 };
+                x_iter: f32 = 2.0, "X Iterations", F32Range { min: 1.0, max: 2.0 };
+                use_columns: bool = false, "Use Columns", Bool;
+            }
+            JustRelax, just_relax, "JustRelax" {
+                speed: f32 = 0.6, "speed", F32 { max: 1.0 };
+                shape_offset: f32 = 0.728, "shape_offset", F32 { max: 1.0 };
+                iter: f32 = 1.0, "iter", F32 { max: 1.0 };
+            }
+            LifeLedWall, life_led_wall, "LifeLedWall" {
+                speed: f32 = 0.25, "speed", F32 { max: 1.0 };
+                size: f32 = 0.73, "size", F32 { max: 1.0 };
+                red: f32 = 0.5, "red", F32 { max: 1.0 };
+                green: f32 = 0.2, "green", F32 { max: 1.0 };
+                blue: f32 = 0.1, "blue", F32 { max: 1.0 };
+                saturation: f32 = 1.0, "saturation", F32 { max: 1.0 };
+                colour_offset: f32 = 0.01, "colour_offset", F32 { max: 1.0 };
+            }
+            LightPatternGenerator, light_pattern_generator, "LightPatternGenerator" {
+                zoom: f32 = 0.5, "Zoom", F32Range { min: 0.0, max: 1.0 };
+                offset: f32 = 0.2, "Offset", F32Range { min: 0.0, max: 10.0 };
+            }
+            LineGradient, line_gradient, "LineGradient" {
+                speed: f32 = 0.03, "speed", F32 { max: 1.0 };
+                num_stripes: f32 = 1.0, "num_stripes", F32 { max: 1.0 };
+                stripe_width: f32 = 0.9, "stripe_width", F32 { max: 1.0 };
+                angle: f32 = 0.5, "angle", F32 { max: 1.0 };
+                smooth_width: f32 = 0.155, "smooth_width", F32 { max: 1.0 };
+            }
+            Metafall, metafall, "Metafall" {
+                speed: f32 = 0.47, "speed", F32 { max: 1.0 };
+                scale: f32 = 0.0, "scale", F32 { max: 1.0 };
+                red: f32 = 1.0, "red", F32 { max: 1.0 };
+                green: f32 = 1.0, "green", F32 { max: 1.0 };
+                blue: f32 = 1.0, "blue", F32 { max: 1.0 };
+            }
+            ParticleZoom, particle_zoom, "ParticleZoom" {
+                speed: f32 = 0.01, "speed", F32 { max: 1.0 };
+                density: f32 = 0.01, "density", F32 { max: 1.0 };
+                shape: f32 = 0.35, "shape", F32 { max: 1.0 };
+                tau: f32 = 1.0, "tau", F32 { max: 1.0 };
+            }
+            RadialLines, radial_lines, "RadialLines" {
+                speed: f32 = 0.05, "speed", F32 { max: 1.0 };
+                zoom_amount: f32 = 0.8, "zoom_amount", F32 { max: 1.0 };
+            }
+            SatisSpiraling, satis_spiraling, "SatisSpiraling" {
+                speed: f32 = 0.5, "speed", F32 { max: 1.0 };
+                loops: f32 = 0.8, "loops", F32 { max: 1.0 };
+                mirror: bool = true, "mirror", Bool;
+                rotate: bool = true, "rotate", Bool;
+                colour_offset: f32 = 0.0, "colour_offset", F32 { max: 1.0 };
+            }
+            SpiralIntersect, spiral_intersect, "SpiralIntersect" {
+                speed: f32 = 0.02, "speed", F32 { max: 1.0 };
+                g1: f32 = 0.4, "g1", F32 { max: 1.0 };
+                g2: f32 = 0.6, "g2", F32 { max: 1.0 };
+                rot1: f32 = 1.0, "rot1", F32 { max: 1.0 };
+                rot2: f32 = 0.5, "rot2", F32 { max: 1.0 };
+                colours: f32 = 1.0, "colours", F32 { max: 1.0 };
+            }
+            SquareTunnel, square_tunnel, "SquareTunnel" {
+                speed: f32 = 0.6, "speed", F32 { max: 1.0 };
+                rotation_speed: f32 = 0.025, "rotation_speed", F32 { max: 1.0 };
+                rotation_offset: f32 = 0.0, "rotation_offset", F32 { max: 1.0 };
+                zoom: f32 = 0.8, "zoom", F32 { max: 1.0 };
+            }
+            ThePulse, the_pulse, "ThePulse" {
+                speed: f32 = 0.08, "speed", F32 { max: 1.0 };
+                scale: f32 = 0.1, "scale", F32 { max: 1.0 };
+                colour_iter: f32 = 0.25, "colour_iter", F32 { max: 1.0 };
+                thickness: f32 = 0.0, "thickness", F32 { max: 1.0 };
+            }
+            TunnelProjection, tunnel_projection, "TunnelProjection" {
+                speed: f32 = 0.5, "speed", F32 { max: 1.0 };
+                res: f32 = 0.5, "res", F32 { max: 1.0 };
+                y_offset: f32 = 0.5, "y_offset", F32 { max: 1.0 };
+            }
+            VertColourGradient, vert_colour_gradient, "VertColourGradient" {
+                speed: f32 = 0.5, "speed", F32 { max: 1.0 };
+                scale: f32 = 0.83, "scale", F32 { max: 1.0 };
+                colour_iter: f32 = 0.015, "colour_iter", F32 { max: 1.0 };
+                line_amp: f32 = 0.0, "line_amp", F32 { max: 1.0 };
+                diag_amp: f32 = 0.0, "diag_amp", F32 { max: 1.0 };
+                boarder_amp: f32 = 0.65, "border_amp", F32 { max: 1.0 };
+            }
+            MitchWash, mitch_wash, "MitchWash" {
+                speed: f32 = 1.0, "speed", F32 { max: 1.0 };
+                pulse_speed: f32 = 1.0, "pulse_speed", F32 { max: 1.0 };
+            }
+            #[prepare]
+            ShapeEnvelopes, shape_envelopes, "ShapeEnvelopes" {
+                speed: f32 = 1.0, "speed", F32 { max: 1.0 };
+                pulse_speed: f32 = 1.0, "pulse_speed", F32 { max: 1.0 };
+                line_thickness: f32 = 0.0, "line_thickness", F32 { max: 1.0 };
+                shape_thickness: f32 = 0.0, "shape_thickness", F32 { max: 1.0 };
+            }
+            RowTest, row_test, "RowTest" {
+                row: f32 = 0.0, "row", F32 { max: 8.0 };
+            }
+            BarTest, bar_test, "BarTest" {
+                row: f32 = 0.0, "row", F32 { max: 8.0 };
+                bar: f32 = 0.0, "bar", F32 { max: 8.0 };
+            }
+            TwoDTiles, two_d_tiles, "2dTiles" {
+                size: f32 = 20.0, "size", F32Range { min: 2.0, max: 40.0 };
+                offset: f32 = 20.0, "offset", F32Range { min: 0.0, max: 20.0 };
+                step_thresh: f32 = 1.0, "step_thresh", F32Range { min: 0.0, max: 1.0 };
+                power: f32 = 6.2, "power", F32Range { min: 0.0, max: 100.0 };
+                speed: f32 = 0.40, "speed", F32Range { min: 0.0, max: 1.0 };
+            }
+            RadialKeta, radial_keta, "RadialKeta" {
+                speed: f32 = 0.035, "speed", F32Range { min: 0.001, max: 0.3 };
+                iter: f32 = 25.0, "iter", F32Range { min: 10.0, max: 50.0 };
+            }
+            ImitationRiley, imitation_riley, "ImitationRiley" {
+                steps: f32 = 20.0, "steps", F32Range { min: 1.0, max: 40.0 };
+                offset: f32 = 1.32, "offset", F32Range { min: 0.1, max: 2.0 };
+                x_mirror: f32 = 0.0, "xMirror", F32Range { min: -0.1, max: 0.1 };
+                speed: f32 = 0.2, "speed", F32Range { min: 0.0, max: 1.0 };
+                black_level: f32 = 0.0, "blackLevel", F32Range { min: 0.0, max: 1.0 };
+                white_level: f32 = 1.0, "whiteLevel", F32Range { min: 0.0, max: 1.0 };
+            }
+            HoopLoop, hoop_loop, "HoopLoop" {
+                invert: bool = false, "invert", Bool;
+                zoom: f32 = 2.0, "zoom", F32Range { min: 0.0, max: 20.0 };
+                animate: f32 = 0.3, "animate", F32Range {
+                    min: -std::f32::consts::PI,
+                    max: std::f32::consts::PI,
+                };
+                size: f32 = 0.35, "size", F32Range { min: 0.0, max: 1.0 };
+                thickness: f32 = 0.001, "thickness", F32Range { min: 0.001, max: 0.5 };
+                line_effect: f32 = 0.001, "lineEffect", F32Range { min: 0.0, max: 0.2 };
+                pattern_offset: f32 = 0.0, "patternOffset", F32Range { min: -1.0, max: 1.0 };
+                r_sin: f32 = 0.0, "rSin", F32Range { min: -5.0, max: 5.0 };
+                x_cos: f32 = 1.0, "xCos", F32Range { min: -5.0, max: 5.0 };
+                y_sin: f32 = 1.0, "ySin", F32Range { min: -5.0, max: 5.0 };
+                blur: f32 = 0.005, "blur", F32Range { min: 0.001, max: 0.5 };
+                function: usize = 0, "function", Select {
+                    labels: $crate::HOOP_LOOP_FUNCTION_LABELS,
+                };
+                rotate: f32 = 0.0, "rotate", F32Range { min: -1.0, max: 1.0 };
+                pos_x: f32 = 0.5, "posX", F32Range { min: 0.0, max: 1.0 };
+                pos_y: f32 = 0.5, "posY", F32Range { min: 0.0, max: 1.0 };
+            }
+        }
+    };
+}